@@ -1,6 +1,7 @@
 use crate::client::terminal_io::input::ClientInputAction;
+use std::convert::Infallible;
 use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::ipc::ClientToServerMsg;
 use crate::ipc::InterProcessCommunication;
@@ -8,25 +9,84 @@ use crate::ipc::ServerToClientMsg;
 use anyhow::Context;
 use interprocess::local_socket::tokio::OwnedWriteHalf;
 
-use crate::ipc::get_socket_name;
 use futures::io::BufReader;
 use interprocess::local_socket::tokio::LocalSocketStream;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::task::JoinHandle;
-use tokio::{select, task::yield_now};
+use tokio::{select, task::yield_now, time};
+use zentime_rs_timer::pomodoro_timer::TimerKind;
 
 use super::terminal_io::terminal_event::TerminalEvent;
 
+/// Upper bound for the delay between two reconnect attempts
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Time in which a requested skip has to be confirmed (see [SkipConfirmation])
+const SKIP_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Client side state of skips which wait for confirmation.
+/// If enabled, the first skip during a focus interval is only requested and has to be confirmed
+/// by a second skip within [SKIP_CONFIRMATION_TIMEOUT].
+#[derive(Debug)]
+struct SkipConfirmation {
+    is_enabled: bool,
+    is_focus: bool,
+    requested_at: Option<Instant>,
+}
+
+impl SkipConfirmation {
+    fn new(is_enabled: bool) -> Self {
+        Self {
+            is_enabled,
+            is_focus: false,
+            requested_at: None,
+        }
+    }
+
+    /// Returns `true` if the skip should be sent to the server right away.
+    /// Otherwise the skip is waiting for confirmation now.
+    fn request(&mut self) -> bool {
+        let is_confirmed = self
+            .requested_at
+            .is_some_and(|requested_at| requested_at.elapsed() < SKIP_CONFIRMATION_TIMEOUT);
+
+        if !self.is_enabled || !self.is_focus || is_confirmed {
+            self.requested_at = None;
+            return true;
+        }
+
+        self.requested_at = Some(Instant::now());
+        false
+    }
+
+    /// Updates the confirmation with the latest timer state.
+    /// Returns `true` if a pending skip has expired (or the timer is not focused anymore).
+    fn update(&mut self, is_focus: bool) -> bool {
+        self.is_focus = is_focus;
+
+        let is_expired = self.requested_at.is_some_and(|requested_at| {
+            !is_focus || requested_at.elapsed() >= SKIP_CONFIRMATION_TIMEOUT
+        });
+
+        if is_expired {
+            self.requested_at = None;
+        }
+
+        is_expired
+    }
+}
+
 /// Tokio task handling the connection between the client and the zentime server
 pub struct ClientConnectionTask {}
 
 impl ClientConnectionTask {
     pub async fn spawn(
-        terminal_in_rx: UnboundedReceiver<ClientInputAction>,
+        socket_name: String,
+        reconnect_attempts: u32,
+        confirm_skip: bool,
+        mut terminal_in_rx: UnboundedReceiver<ClientInputAction>,
         terminal_out_tx: UnboundedSender<TerminalEvent>,
     ) -> JoinHandle<()> {
-        let socket_name = get_socket_name();
-
         let mut connection_tries = 0;
 
         // Try to receive a connection to the server (will timeout after the third attempt)
@@ -42,7 +102,7 @@ impl ClientConnectionTask {
                     .expect("Could not send to terminal out");
             }
 
-            let result = LocalSocketStream::connect(socket_name).await;
+            let result = LocalSocketStream::connect(socket_name.as_str()).await;
 
             if let Ok(conn) = result {
                 break conn;
@@ -52,27 +112,91 @@ impl ClientConnectionTask {
         };
 
         tokio::spawn(async move {
-            if let Err(error) =
-                handle_connection(connection, terminal_out_tx.clone(), terminal_in_rx).await
-            {
-                terminal_out_tx
-                    .send(TerminalEvent::Quit {
-                        msg: Some(format!("{}.\nServer connection closed.", error)),
-                        error: true,
-                    })
-                    .expect("Could not send to terminal out");
+            let mut connection = connection;
+            let mut skip_confirmation = SkipConfirmation::new(confirm_skip);
+
+            loop {
+                let Err(error) = handle_connection(
+                    connection,
+                    terminal_out_tx.clone(),
+                    &mut terminal_in_rx,
+                    &mut skip_confirmation,
+                )
+                .await;
+
+                // Try to reestablish the connection (e.g. because the server has been restarted)
+                match reconnect(
+                    &socket_name,
+                    reconnect_attempts,
+                    &terminal_out_tx,
+                    &mut terminal_in_rx,
+                )
+                .await
+                {
+                    Some(new_connection) => connection = new_connection,
+                    None => {
+                        terminal_out_tx
+                            .send(TerminalEvent::Quit {
+                                msg: Some(format!("{}.\nServer connection closed.", error)),
+                                error: true,
+                            })
+                            .expect("Could not send to terminal out");
+
+                        return;
+                    }
+                }
             }
         })
     }
 }
 
+/// Tries to reconnect to the server with an exponential backoff.
+/// Returns `None` if all attempts have been exhausted or the user decided to quit/detach in the
+/// meantime.
+async fn reconnect(
+    socket_name: &str,
+    max_attempts: u32,
+    terminal_out_tx: &UnboundedSender<TerminalEvent>,
+    terminal_in_rx: &mut UnboundedReceiver<ClientInputAction>,
+) -> Option<LocalSocketStream> {
+    for attempt in 1..=max_attempts {
+        terminal_out_tx
+            .send(TerminalEvent::Reconnecting {
+                attempt,
+                max_attempts,
+            })
+            .ok()?;
+
+        let delay = Duration::from_millis(250 * 2_u64.pow(attempt.min(5)));
+
+        select! {
+            _ = time::sleep(delay.min(MAX_RECONNECT_DELAY)) => {},
+            Some(action) = terminal_in_rx.recv() => {
+                // There is no server we could forward these actions to, but users should
+                // still be able to leave the client.
+                if let ClientInputAction::Quit | ClientInputAction::Detach = action {
+                    return None;
+                }
+            }
+        }
+
+        if let Ok(connection) = LocalSocketStream::connect(socket_name).await {
+            return Some(connection);
+        }
+    }
+
+    None
+}
+
 /// Continously handle the connection to the server by reacting to incoming
 /// [ServerToClientMsg] and terminal input events.
+/// This only ever returns if an error occurs (e.g. because the connection has been lost).
 async fn handle_connection(
     connection: LocalSocketStream,
     terminal_out_tx: UnboundedSender<TerminalEvent>,
-    mut terminal_in_rx: UnboundedReceiver<ClientInputAction>,
-) -> anyhow::Result<()> {
+    terminal_in_rx: &mut UnboundedReceiver<ClientInputAction>,
+    skip_confirmation: &mut SkipConfirmation,
+) -> anyhow::Result<Infallible> {
     // This consumes our connection and splits it into two halves,
     // so that we could concurrently act on both.
     let (reader, mut writer) = connection.into_split();
@@ -82,11 +206,11 @@ async fn handle_connection(
         select! {
             msg = InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader) => {
                 let msg = msg.context("Could not receive message from socket")?;
-                handle_server_to_client_msg(msg, &terminal_out_tx).context("Could not handle server to client message")?;
+                handle_server_to_client_msg(msg, &terminal_out_tx, &mut writer, skip_confirmation).await.context("Could not handle server to client message")?;
             },
             value = terminal_in_rx.recv() => {
                 if let Some(action) = value {
-                    handle_client_input_action(action, &terminal_out_tx, &mut writer).await.context("Could not handle input action")?;
+                    handle_client_input_action(action, &terminal_out_tx, &mut writer, skip_confirmation).await.context("Could not handle input action")?;
                 }
             }
         };
@@ -100,6 +224,7 @@ async fn handle_client_input_action(
     action: ClientInputAction,
     terminal_out_tx: &UnboundedSender<TerminalEvent>,
     writer: &mut OwnedWriteHalf,
+    skip_confirmation: &mut SkipConfirmation,
 ) -> anyhow::Result<()> {
     match action {
         // Command server to shutdown and quit the current client
@@ -143,8 +268,22 @@ async fn handle_client_input_action(
                 .context("Could not send IPC message")?;
         }
 
-        // NoOp
-        ClientInputAction::None => return Ok(()),
+        // NoOp (typing a label is started by the input task itself)
+        ClientInputAction::None | ClientInputAction::Label => return Ok(()),
+
+        // Only the view is affected, so there is nothing to tell the server
+        ClientInputAction::Redraw => {
+            terminal_out_tx
+                .send(TerminalEvent::Redraw)
+                .context("Could not send to terminal out")?;
+        }
+
+        // Purely presentational as well
+        ClientInputAction::Zen => {
+            terminal_out_tx
+                .send(TerminalEvent::ToggleZen)
+                .context("Could not send to terminal out")?;
+        }
 
         // Command the server to pause or play the timer
         ClientInputAction::PlayPause => {
@@ -154,8 +293,21 @@ async fn handle_client_input_action(
                 .context("Could not send IPC message")?;
         }
 
-        // Command the server to skip to the next interval
+        // Command the server to skip to the next interval (if the skip does not need to be
+        // confirmed first)
         ClientInputAction::Skip => {
+            let is_skipping = skip_confirmation.request();
+
+            terminal_out_tx
+                .send(TerminalEvent::SkipConfirmation {
+                    is_pending: !is_skipping,
+                })
+                .context("Could not send to terminal out")?;
+
+            if !is_skipping {
+                return Ok(());
+            }
+
             let msg = ClientToServerMsg::Skip;
             InterProcessCommunication::send_ipc_message(msg, writer)
                 .await
@@ -168,6 +320,40 @@ async fn handle_client_input_action(
                 .await
                 .context("Could not send IPC message")?;
         }
+
+        // Command the server to move back to the previous interval
+        ClientInputAction::Previous => {
+            let msg = ClientToServerMsg::Previous;
+            InterProcessCommunication::send_ipc_message(msg, writer)
+                .await
+                .context("Could not send IPC message")?;
+        }
+
+        // Count a distraction during the current focus interval
+        ClientInputAction::Distraction => {
+            let msg = ClientToServerMsg::Distraction;
+            InterProcessCommunication::send_ipc_message(msg, writer)
+                .await
+                .context("Could not send IPC message")?;
+        }
+
+        // The label is only sent to the server once it has been confirmed
+        ClientInputAction::EditLabel(label) => {
+            terminal_out_tx
+                .send(TerminalEvent::LabelInput(label))
+                .context("Could not send to terminal out")?;
+        }
+
+        ClientInputAction::SetLabel(label) => {
+            terminal_out_tx
+                .send(TerminalEvent::LabelInput(None))
+                .context("Could not send to terminal out")?;
+
+            let msg = ClientToServerMsg::SetLabel(label);
+            InterProcessCommunication::send_ipc_message(msg, writer)
+                .await
+                .context("Could not send IPC message")?;
+        }
     }
 
     Ok(())
@@ -175,16 +361,40 @@ async fn handle_client_input_action(
 
 /// Handle incoming [ServerToClientMsg]s (e.g. by sending incoming timer state to the
 /// [TerminalOutputTask]).
-fn handle_server_to_client_msg(
+async fn handle_server_to_client_msg(
     msg: ServerToClientMsg,
     terminal_out_tx: &UnboundedSender<TerminalEvent>,
+    writer: &mut OwnedWriteHalf,
+    skip_confirmation: &mut SkipConfirmation,
 ) -> anyhow::Result<()> {
     match msg {
         ServerToClientMsg::Timer(state) => {
+            // NOTE: Postponed breaks are skipped right away, just like regular breaks
+            if skip_confirmation.update(state.kind == TimerKind::Interval) {
+                terminal_out_tx
+                    .send(TerminalEvent::SkipConfirmation { is_pending: false })
+                    .context("Could not send to terminal out")?;
+            }
+
             terminal_out_tx
                 .send(TerminalEvent::View(state))
                 .context("Could not send to terminal out")?;
         }
+
+        // The interactive client does not subscribe to any events or request any clients
+        ServerToClientMsg::Event(_)
+        | ServerToClientMsg::Clients(_)
+        | ServerToClientMsg::ResumeAt(_)
+        | ServerToClientMsg::HandshakeAck(_)
+        | ServerToClientMsg::ServerInfo(_)
+        | ServerToClientMsg::Reloaded(_) => {}
+
+        // Let the server know, that we are still alive
+        ServerToClientMsg::Ping => {
+            InterProcessCommunication::send_ipc_message(ClientToServerMsg::Pong, writer)
+                .await
+                .context("Could not send IPC message")?;
+        }
     }
 
     Ok(())
@@ -1,14 +1,36 @@
 //! Creates a connection for single reads/writes from/to the server
-use crate::ipc::get_socket_name;
 use crate::server::status::server_status;
 use crate::server::status::ServerStatus;
 use interprocess::local_socket::tokio::LocalSocketStream;
 use interprocess::local_socket::tokio::OwnedReadHalf;
 use interprocess::local_socket::tokio::OwnedWriteHalf;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::time::sleep;
 
-/// Creates a connection to the zentime server (if one is running) and returns
+/// Number of attempts to connect to the server, before giving up
+const CONNECTION_ATTEMPTS: u32 = 4;
+
+/// Delay before the first retry, which is doubled on every further attempt
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// The connection to the zentime server could not be established
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum ConnectionError {
+    /// There is no server running on the socket
+    #[error("No zentime server running")]
+    NoServerRunning,
+
+    /// A server is running, but did not accept any connection
+    #[error("Could not connect to server after {0} attempts")]
+    Unreachable(u32),
+}
+
+/// Creates a connection to the zentime server listening on `socket_name` (if one is running) and returns
 /// a tuple of [OwnedReadHalf] and an [OwnedWriteHalf].
-/// If no server is running the current process is terminated.
+/// If the first attempt fails while a server is running, it is retried with an exponential backoff
+/// (e.g. because the server is currently starting or restarting).
+/// Otherwise [ConnectionError::NoServerRunning] is returned right away.
 ///
 /// NOTE:
 /// If you just want to read from the server, you still need to write [ClientToServerMsg::Sync]
@@ -17,16 +39,25 @@ use interprocess::local_socket::tokio::OwnedWriteHalf;
 ///
 /// NOTE:
 /// Also make sure to send a detach message to the server as well
-pub async fn one_shot_connection() -> anyhow::Result<(OwnedReadHalf, OwnedWriteHalf)> {
-    // check if server is running -> if not, quit
-    if server_status() == ServerStatus::Stopped {
-        println!("No zentime server running");
-        std::process::exit(0);
-    }
+pub async fn one_shot_connection(
+    socket_name: &str,
+) -> Result<(OwnedReadHalf, OwnedWriteHalf), ConnectionError> {
+    let mut delay = INITIAL_RETRY_DELAY;
+
+    for attempt in 1..=CONNECTION_ATTEMPTS {
+        if let Ok(connection) = LocalSocketStream::connect(socket_name).await {
+            return Ok(connection.into_split());
+        }
 
-    // connect to server
-    let socket_name = get_socket_name();
-    let connection = LocalSocketStream::connect(socket_name).await?;
+        if attempt == 1 && server_status(socket_name) == ServerStatus::Stopped {
+            return Err(ConnectionError::NoServerRunning);
+        }
+
+        if attempt < CONNECTION_ATTEMPTS {
+            sleep(delay).await;
+            delay *= 2;
+        }
+    }
 
-    Ok(connection.into_split())
+    Err(ConnectionError::Unreachable(CONNECTION_ATTEMPTS))
 }
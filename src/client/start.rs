@@ -4,17 +4,33 @@
 use crate::client::terminal_io::output::TerminalOutputTask;
 use std::sync::Arc;
 
-use crate::client::terminal_io::input::TerminalInputTask;
+use crate::client::terminal_io::input::{ClientInputAction, InterruptInputTask, TerminalInputTask};
+use crate::client::terminal_io::keymap::Keymap;
 use crate::client::terminal_io::output::TerminalOut;
 use crate::config::Config;
+use crossterm::cursor::Show;
+use crossterm::event::{DisableBracketedPaste, DisableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, LeaveAlternateScreen};
 use futures::future::FutureExt;
 use futures::lock::Mutex;
-use tokio::sync::mpsc::unbounded_channel;
+#[cfg(feature = "tray")]
+use log::warn;
+use std::future::Future;
+use std::io::stdout;
+use std::panic;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::task::JoinHandle;
 use tokio::try_join;
 
 use super::connection::ClientConnectionTask;
+use crate::client::terminal_io::output::BigClockInterface;
 use crate::client::terminal_io::output::DefaultInterface;
+use crate::client::terminal_io::output::JsonInterface;
 use crate::client::terminal_io::output::MinimalInterface;
+use crate::client::terminal_io::output::TmuxInterface;
+#[cfg(feature = "tray")]
+use crate::client::terminal_io::tray::{TrayInputTask, TrayInterface};
 
 /// Start a single zentime client and connect it to the zentime server.
 /// This makes sure we have tokio tasks in place to:
@@ -28,28 +44,164 @@ use crate::client::terminal_io::output::MinimalInterface;
 /// use zentime_rs::client::start;
 /// use zentime_rs::config::create_base_config;
 /// use zentime_rs::config::Config;
+/// use std::path::Path;
 ///
 /// #[tokio::main]
 /// async fn main() {
-///     let config: Config = create_base_config("./some/path/config.toml")
+///     let config_path = Path::new("./some/path/config.toml");
+///     let config: Config = create_base_config(Some(config_path), false, None)
+///        .expect("Could not select profile")
 ///        .extract()
 ///        .expect("Could not create config");
 ///     start(config).await;
 /// }
 /// ```
 pub async fn start(config: Config) {
-    let (terminal_in_tx, terminal_in_rx) = unbounded_channel();
-    let (terminal_out_tx, terminal_out_rx) = unbounded_channel();
+    #[cfg(feature = "tray")]
+    if config.view.interface == "tray" {
+        match TrayInterface::new().await {
+            Ok((tray, action_rx)) => {
+                return run(config, Box::new(tray), move |input_worker_tx| {
+                    TrayInputTask::spawn(action_rx, input_worker_tx)
+                })
+                .await;
+            }
+            Err(error) => {
+                warn!("{:#} - falling back to the default interface", error);
+            }
+        }
+    }
+
+    // Installed before the interface, which might already panic during its initialization
+    install_panic_hook(uses_alternate_screen(&config));
+    let terminal_out = init_interface(&config);
+
+    // NOTE: An invalid keymap has already been reported by Config::validate()
+    let keymap = Keymap::new(&config.keybindings).unwrap_or_default();
+
+    run(config, terminal_out, move |input_worker_tx| {
+        TerminalInputTask::spawn(input_worker_tx, keymap)
+    })
+    .await
+}
+
+/// Installs a panic hook, which restores the terminal (raw mode, cursor and optionally the
+/// alternate screen) before the previous hook prints the panic message.
+///
+/// WHY:
+/// A panic inside any task (e.g. because of an `expect()` during rendering) would otherwise leave
+/// the terminal in raw mode, which makes the shell of the user unusable.
+fn install_panic_hook(leave_alternate_screen: bool) {
+    let previous_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        disable_raw_mode().ok();
+        execute!(stdout(), Show, DisableMouseCapture, DisableBracketedPaste).ok();
+
+        // NOTE: Leaving the alternate screen without having entered it might move the cursor
+        if leave_alternate_screen {
+            execute!(stdout(), LeaveAlternateScreen).ok();
+        }
+
+        previous_hook(info);
+    }));
+}
+
+/// Denotes if the configured interface renders inside the alternate screen
+fn uses_alternate_screen(config: &Config) -> bool {
+    match config.view.interface.as_str() {
+        "minimal" | "tmux" => false,
+        "bigclock" => true,
+        _ => config.view.use_alternate_screen,
+    }
+}
+
+/// Start a read-only zentime client, which renders the timer just like [start], but can't
+/// control it. The only keys left are those which leave the client (all of them detach).
+///
+/// # Example
+///
+/// ```no_run
+/// use zentime_rs::client::start::observe;
+/// use zentime_rs::config::create_base_config;
+/// use zentime_rs::config::Config;
+/// use std::path::Path;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config_path = Path::new("./some/path/config.toml");
+///     let config: Config = create_base_config(Some(config_path), false, None)
+///        .expect("Could not select profile")
+///        .extract()
+///        .expect("Could not create config");
+///     observe(config).await;
+/// }
+/// ```
+pub async fn observe(config: Config) {
+    install_panic_hook(uses_alternate_screen(&config));
+    let mut terminal_out = init_interface(&config);
+    terminal_out.set_readonly(true);
 
-    let interface_type = config.view.interface.clone();
+    let keymap = Keymap::new(&config.keybindings)
+        .unwrap_or_default()
+        .read_only();
 
-    let terminal_out: Box<dyn TerminalOut + Send> = init_interface(interface_type);
+    run(config, terminal_out, move |input_worker_tx| {
+        TerminalInputTask::spawn(input_worker_tx, keymap)
+    })
+    .await
+}
+
+/// Start a zentime client which does not render anything, but prints each timer state as a
+/// line of JSON to stdout instead. Terminal input is ignored and the client detaches on SIGINT.
+///
+/// # Example
+///
+/// ```no_run
+/// use zentime_rs::client::start::watch;
+/// use zentime_rs::config::create_base_config;
+/// use zentime_rs::config::Config;
+/// use std::path::Path;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config_path = Path::new("./some/path/config.toml");
+///     let config: Config = create_base_config(Some(config_path), false, None)
+///        .expect("Could not select profile")
+///        .extract()
+///        .expect("Could not create config");
+///     watch(config).await;
+/// }
+/// ```
+pub async fn watch(config: Config) {
+    run(
+        config,
+        Box::new(JsonInterface::default()),
+        InterruptInputTask::spawn,
+    )
+    .await
+}
+
+/// Spawns the input, output and connection tasks of a client and waits for them to finish
+async fn run<I, F>(config: Config, terminal_out: Box<dyn TerminalOut + Send>, spawn_input: I)
+where
+    I: FnOnce(UnboundedSender<ClientInputAction>) -> F,
+    F: Future<Output = JoinHandle<()>>,
+{
+    let (terminal_in_tx, terminal_in_rx) = unbounded_channel();
+    let (terminal_out_tx, terminal_out_rx) = unbounded_channel();
 
     let thread_safe_terminal_out = Arc::new(Mutex::new(terminal_out));
 
-    let input_handler = TerminalInputTask::spawn(terminal_in_tx);
+    let input_handler = spawn_input(terminal_in_tx);
     let view_handler = TerminalOutputTask::spawn(thread_safe_terminal_out.clone(), terminal_out_rx);
-    let connection_handler = ClientConnectionTask::spawn(terminal_in_rx, terminal_out_tx);
+    let connection_handler = ClientConnectionTask::spawn(
+        config.server.socket_name(),
+        config.view.reconnect_attempts,
+        config.view.confirm_skip,
+        terminal_in_rx,
+        terminal_out_tx,
+    );
 
     let join_result = try_join! {
         connection_handler.flatten(),
@@ -66,20 +218,37 @@ pub async fn start(config: Config) {
 }
 
 /// Determine which terminal interface should be used.
-fn init_interface(interface_type: String) -> Box<dyn TerminalOut + Send> {
-    match interface_type.as_str() {
-        "minimal" => match MinimalInterface::new() {
-            Ok(interface) => {
-                // We move up one line to replace the initial prompt ending with our timer
-                let ansi_move_line_up_escape = "\x1B[A";
-                print!("{}", ansi_move_line_up_escape);
-                Box::new(interface)
-            },
+fn init_interface(config: &Config) -> Box<dyn TerminalOut + Send> {
+    match config.view.interface.as_str() {
+        "minimal" => {
+            match MinimalInterface::new(config.view.show_progress_bar, config.view.use_colors()) {
+                Ok(interface) => {
+                    // We move up one line to replace the initial prompt ending with our timer
+                    let ansi_move_line_up_escape = "\x1B[A";
+                    print!("{}", ansi_move_line_up_escape);
+                    Box::new(interface)
+                }
+                Err(error) => {
+                    panic!("Could not initialize interface: {}", error);
+                }
+            }
+        }
+        "bigclock" => match BigClockInterface::new(&config.theme) {
+            Ok(interface) => Box::new(interface),
             Err(error) => {
                 panic!("Could not initialize interface: {}", error);
             }
         },
-        _ => match DefaultInterface::new() {
+        "tmux" => Box::new(TmuxInterface::new(
+            config.view.symbols.clone(),
+            config.timers.intervals,
+        )),
+        _ => match DefaultInterface::new(
+            &config.theme,
+            config.view.show_session_times,
+            config.view.use_alternate_screen,
+            config.timers.strict_breaks,
+        ) {
             Ok(interface) => Box::new(interface),
             Err(error) => {
                 panic!("Could not initialize interface: {}", error);
@@ -1,6 +1,11 @@
 //! Zentime client terminal io
 
+mod big_clock;
 mod default_interface;
 pub mod input;
+pub mod keymap;
 pub mod output;
 pub mod terminal_event;
+mod theme;
+#[cfg(feature = "tray")]
+pub mod tray;
@@ -0,0 +1,177 @@
+use super::theme::Theme;
+use anyhow::Context;
+use zentime_rs_timer::pomodoro_timer::{TimerKind, ViewState};
+
+use std::io::Stdout;
+use tui::{
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Span, Spans},
+    widgets::Paragraph,
+    Terminal as TuiTerminal,
+};
+
+/// Height of every glyph of the [FONT] in lines
+const GLYPH_HEIGHT: usize = 5;
+
+/// Columns between two glyphs (before scaling)
+const GLYPH_SPACING: usize = 1;
+
+/// Multi-line glyphs of all characters which can be part of a formatted timer.
+/// Characters without a glyph are rendered as blank space.
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 11] = [
+    ('0', ["█████", "█   █", "█   █", "█   █", "█████"]),
+    ('1', ["  █  ", " ██  ", "  █  ", "  █  ", " ███ "]),
+    ('2', ["█████", "    █", "█████", "█    ", "█████"]),
+    ('3', ["█████", "    █", " ████", "    █", "█████"]),
+    ('4', ["█   █", "█   █", "█████", "    █", "    █"]),
+    ('5', ["█████", "█    ", "█████", "    █", "█████"]),
+    ('6', ["█████", "█    ", "█████", "█   █", "█████"]),
+    ('7', ["█████", "    █", "   █ ", "  █  ", "  █  "]),
+    ('8', ["█████", "█   █", "█████", "█   █", "█████"]),
+    ('9', ["█████", "█   █", "█████", "    █", "█████"]),
+    (':', [" ", "█", " ", "█", " "]),
+];
+
+/// Big clock interface, which renders the timer in large block digits centered on the screen
+/// and (if `show_status_line` is set) a small status line beneath it.
+/// Without the status line, this is also the zen display of the default interface.
+pub fn render(
+    terminal: &mut TuiTerminal<CrosstermBackend<Stdout>>,
+    timer_state: ViewState,
+    theme: &Theme,
+    show_status_line: bool,
+) -> anyhow::Result<()> {
+    terminal
+        .draw(|frame| {
+            // WHY:
+            // The layout is computed from the current frame size on every draw, so that the
+            // clock adapts to a resized terminal with the next timer update.
+            let rect = frame.size();
+
+            // The status line is part of the text, so that it is centered along with the clock
+            let status_height = if show_status_line { 2 } else { 0 };
+            let max_height = (rect.height as usize).saturating_sub(status_height);
+            let scale = scale(&timer_state.time, rect.width as usize, max_height);
+
+            let lines = if scale == 0 {
+                vec![timer_state.time.clone()]
+            } else {
+                big_text(&timer_state.time, scale)
+            };
+            let height = (lines.len() + status_height).min(rect.height as usize) as u16;
+
+            let clock_style = Style::default().fg(if timer_state.is_paused {
+                Color::DarkGray
+            } else {
+                kind_color(timer_state.kind, theme)
+            });
+
+            let mut text: Vec<Spans> = lines
+                .into_iter()
+                .map(|line| Spans::from(Span::styled(line, clock_style)))
+                .collect();
+            if show_status_line {
+                text.push(Spans::default());
+                text.push(Spans::from(Span::styled(
+                    status_line(&timer_state),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+
+            let clock = Paragraph::new(text).alignment(Alignment::Center);
+            frame.render_widget(clock, centered(rect, height));
+        })
+        .context("Could not render to terminal")?;
+    Ok(())
+}
+
+/// Vertically centers an area of the given height inside the given rect
+fn centered(rect: Rect, height: u16) -> Rect {
+    let padding = rect.height.saturating_sub(height) / 2;
+
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(padding),
+                Constraint::Length(height),
+                Constraint::Min(0),
+            ]
+            .as_ref(),
+        )
+        .split(rect)[1]
+}
+
+/// Largest factor by which the glyphs of the given text can be scaled to fit into the given
+/// size. Returns 0 if not even the unscaled glyphs would fit.
+fn scale(text: &str, max_width: usize, max_height: usize) -> usize {
+    let width = text
+        .chars()
+        .map(|character| glyph(character)[0].chars().count() + GLYPH_SPACING)
+        .sum::<usize>()
+        .saturating_sub(GLYPH_SPACING)
+        .max(1);
+
+    (max_width / width).min(max_height / GLYPH_HEIGHT)
+}
+
+/// Renders the text with the glyphs of the [FONT], scaled horizontally and vertically by the
+/// given factor
+fn big_text(text: &str, scale: usize) -> Vec<String> {
+    let spacing = " ".repeat(GLYPH_SPACING * scale);
+
+    (0..GLYPH_HEIGHT)
+        .flat_map(|row| {
+            let line = text
+                .chars()
+                .map(|character| {
+                    glyph(character)[row]
+                        .chars()
+                        .map(|cell| cell.to_string().repeat(scale))
+                        .collect::<String>()
+                })
+                .collect::<Vec<String>>()
+                .join(&spacing);
+
+            std::iter::repeat_n(line, scale)
+        })
+        .collect()
+}
+
+/// Glyph of the given character (blank, if the [FONT] does not contain it)
+fn glyph(character: char) -> [&'static str; GLYPH_HEIGHT] {
+    FONT.iter()
+        .find(|(glyph_character, _)| *glyph_character == character)
+        .map(|(_, glyph)| *glyph)
+        .unwrap_or(["   "; GLYPH_HEIGHT])
+}
+
+/// Color of the clock, depending on the kind of the current timer
+fn kind_color(kind: TimerKind, theme: &Theme) -> Color {
+    match kind {
+        TimerKind::Interval => theme.focus,
+        TimerKind::Break | TimerKind::LongBreak => theme.break_color,
+        TimerKind::PostponedBreak | TimerKind::PostponedLongBreak => theme.postponed,
+        TimerKind::SessionComplete => Color::Green,
+    }
+}
+
+/// Short description of the current timer, e.g. `Focus - Round 2 (paused)`
+fn status_line(state: &ViewState) -> String {
+    let kind = match state.kind {
+        TimerKind::Interval => "Focus",
+        TimerKind::Break => "Break",
+        TimerKind::LongBreak => "Long break",
+        TimerKind::PostponedBreak | TimerKind::PostponedLongBreak => "Postponed",
+        TimerKind::SessionComplete => "Session complete",
+    };
+
+    format!(
+        "{} - Round {}{}",
+        kind,
+        state.round,
+        if state.is_paused { " (paused)" } else { "" }
+    )
+}
@@ -1,5 +1,7 @@
+use super::theme::Theme;
 use anyhow::Context;
-use zentime_rs_timer::pomodoro_timer::ViewState;
+use zentime_rs_timer::pomodoro_timer::{TimerKind, ViewState};
+use zentime_rs_timer::util::seconds_to_time;
 
 use std::io::Stdout;
 use tui::{
@@ -7,40 +9,107 @@ use tui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Paragraph, Tabs},
+    widgets::{Block, Borders, Gauge, Paragraph, Tabs},
     Terminal as TuiTerminal,
 };
 
+/// Maximum number of segments of the daily goal progress bar
+const MAX_GOAL_BAR_WIDTH: u64 = 10;
+
+/// Input state of the client, which determines what is shown at the bottom of the default
+/// interface
+#[derive(Debug, Clone, Copy)]
+pub struct InputState<'a> {
+    /// Denotes if a skip waits for confirmation
+    pub is_skip_pending: bool,
+
+    /// Denotes if the client can't control the timer
+    pub is_readonly: bool,
+
+    /// Label which is currently being typed (if any)
+    pub label_input: Option<&'a str>,
+}
+
 /// Default interface
 pub fn render(
     terminal: &mut TuiTerminal<CrosstermBackend<Stdout>>,
     timer_state: ViewState,
+    theme: &Theme,
+    show_session_times: bool,
+    strict_breaks: bool,
+    input: InputState,
 ) -> anyhow::Result<()> {
+    // The session times are shown in an additional line of the info panel
+    let info_height = if show_session_times { 5 } else { 4 };
+
     terminal
         .draw(|frame| {
             let rect = frame.size();
-            let layout = layout(rect);
+            let layout = layout(rect, info_height);
 
-            // Rendered at the bottom
-            let key_tabs = key_binding_info(timer_state.is_break);
-            frame.render_widget(key_tabs, layout[1]);
+            // Rendered at the bottom (replaced by the label while it is being typed)
+            if let Some(label_input) = input.label_input {
+                frame.render_widget(label_input_field(label_input, theme), layout[1]);
+            } else {
+                // Breaks can't be skipped in strict mode
+                let can_skip = !(strict_breaks && timer_state.kind.is_break());
+                let key_tabs = key_binding_info(
+                    timer_state.kind,
+                    input.is_skip_pending,
+                    can_skip,
+                    timer_state.can_postpone,
+                    input.is_readonly,
+                    theme,
+                );
+                frame.render_widget(key_tabs, layout[1]);
+            }
 
             // Top layout
             let inner_layout = inner_layout(layout[0]);
 
             // Rendered to the left
-            let timer_info = timer_info(&timer_state);
+            let timer_info = timer_info(&timer_state, theme, show_session_times);
             frame.render_widget(timer_info, inner_layout[0]);
 
             // Rendered to the right
-            let timer = timer(&timer_state.time);
-            frame.render_widget(timer, inner_layout[1])
+            let timer_layout = timer_layout(inner_layout[1]);
+            let timer = timer(&timer_state.time, &timer_state.history, theme);
+            frame.render_widget(timer, timer_layout[0]);
+
+            // Rendered beneath the timer
+            let progress = progress_gauge(&timer_state, theme);
+            frame.render_widget(progress, timer_layout[1])
         })
         .context("Could not render to terminal")?;
     Ok(())
 }
 
-/// Base layout of the default interface
+/// Shown instead of the timer while the client is trying to reconnect to the server
+pub fn render_reconnecting(
+    terminal: &mut TuiTerminal<CrosstermBackend<Stdout>>,
+    attempt: u32,
+    max_attempts: u32,
+) -> anyhow::Result<()> {
+    terminal
+        .draw(|frame| {
+            let layout = layout(frame.size(), 4);
+
+            let text = format!(
+                "Connection to server lost - reconnecting ({}/{})...",
+                attempt, max_attempts
+            );
+            let info = Paragraph::new(text)
+                .block(Block::default().title("zentime").borders(Borders::ALL))
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center);
+
+            frame.render_widget(info, layout[0]);
+        })
+        .context("Could not render to terminal")?;
+    Ok(())
+}
+
+/// Base layout of the default interface, where part A is at most `top_height` lines high
 /// ┌───────────────────────────────────────────────┐
 /// │                                               │
 /// │                                               │
@@ -50,13 +119,13 @@ pub fn render(
 /// ┌───────────────────────────────────────────────┐
 /// │                    B                          │
 /// └───────────────────────────────────────────────┘
-fn layout(rect: Rect) -> Vec<Rect> {
+fn layout(rect: Rect, top_height: u16) -> Vec<Rect> {
     Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
         .constraints(
             [
-                Constraint::Max(4),
+                Constraint::Max(top_height),
                 Constraint::Max(3),
                 Constraint::Length(1),
             ]
@@ -79,25 +148,63 @@ fn inner_layout(rect: Rect) -> Vec<Rect> {
         .split(rect)
 }
 
+/// Layout of the timer rendered into the inner layout part B
+/// ┌────────────────────────────────┐
+/// │                A               │
+/// └────────────────────────────────┘
+///                 B
+fn timer_layout(rect: Rect) -> Vec<Rect> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)].as_ref())
+        .split(rect)
+}
+
 /// Keyboard shortcuts of the default interface
 /// ┌─────────────────────────────────────────────────────────┐
 /// │ [Q]uit │ [D]etach │ [S]kip │ Space: Play/Pause          │
 /// └─────────────────────────────────────────────────────────┘
-fn key_binding_info(is_break: bool) -> Tabs<'static> {
-    let keybindings = vec![
-        "[Q]uit",
-        "[D]etach",
-        "[S]kip",
-        if is_break { "[P]ostpone" } else { "" },
-        "Space: Play/Pause",
-    ];
+/// Read-only clients can only detach (and toggle the zen display).
+/// The postpone hint is hidden once the postpone limit of the break has been reached.
+fn key_binding_info(
+    kind: TimerKind,
+    is_skip_pending: bool,
+    can_skip: bool,
+    can_postpone: bool,
+    is_readonly: bool,
+    theme: &Theme,
+) -> Tabs<'static> {
+    let keybindings: Vec<&str> = if is_readonly {
+        vec!["Read-only", "[D]etach", "[Z]en"]
+    } else {
+        [
+            Some("[Q]uit"),
+            Some("[D]etach"),
+            can_skip.then_some(if is_skip_pending {
+                "[S] again to confirm skip"
+            } else {
+                "[S]kip"
+            }),
+            if kind.is_break() {
+                can_postpone.then_some("[P]ostpone")
+            } else {
+                Some("[X] Distracted")
+            },
+            Some("[L]abel"),
+            Some("[Z]en"),
+            Some("Space: Play/Pause"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    };
 
     let keybinding_spans = keybindings
         .iter()
         .map(|key| {
             Spans::from(vec![Span::styled(
                 *key,
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.keybindings),
             )])
         })
         .collect();
@@ -105,22 +212,51 @@ fn key_binding_info(is_break: bool) -> Tabs<'static> {
     Tabs::new(keybinding_spans).block(
         Block::default()
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::DarkGray)),
+            .style(Style::default().fg(theme.keybindings)),
+    )
+}
+
+/// Single line input field for the label of the current timer
+/// ┌ Label (Enter: confirm, Esc: cancel) ────────────────────┐
+/// │ Writing docs_                                           │
+/// └─────────────────────────────────────────────────────────┘
+fn label_input_field<'a>(label_input: &'a str, theme: &Theme) -> Paragraph<'a> {
+    Paragraph::new(Spans::from(vec![
+        Span::styled(label_input, Style::default().fg(Color::White)),
+        Span::styled("_", Style::default().fg(theme.keybindings)),
+    ]))
+    .block(
+        Block::default()
+            .title("Label (Enter: confirm, Esc: cancel)")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(theme.keybindings)),
     )
 }
 
-/// Timer information of the default interface (interval/round number, break/focus)
-fn timer_info(state: &ViewState) -> Paragraph {
+/// Timer information of the default interface (interval/round number, break/focus and
+/// optionally the session times)
+fn timer_info<'a>(state: &'a ViewState, theme: &Theme, show_session_times: bool) -> Paragraph<'a> {
     let rounds = format!("Round: {}", state.round);
-    let timer_kind = if state.is_break {
-        Span::styled("Break", Style::default().fg(Color::Yellow))
-    } else if state.is_postponed {
-        Span::styled("Postponed", Style::default().fg(Color::Red))
-    } else {
-        Span::styled("Focus", Style::default().fg(Color::Blue))
+    let timer_kind = match state.kind {
+        TimerKind::SessionComplete => {
+            Span::styled("Session complete", Style::default().fg(Color::Green))
+        }
+        TimerKind::Break | TimerKind::LongBreak => {
+            Span::styled("Break", Style::default().fg(theme.break_color))
+        }
+        TimerKind::PostponedBreak | TimerKind::PostponedLongBreak => {
+            Span::styled("Postponed", Style::default().fg(theme.postponed))
+        }
+        TimerKind::Interval if state.is_stopwatch => {
+            Span::styled("Stopwatch", Style::default().fg(theme.focus))
+        }
+        TimerKind::Interval if state.is_overtime => {
+            Span::styled("Overtime", Style::default().fg(theme.focus))
+        }
+        TimerKind::Interval => Span::styled("Focus", Style::default().fg(theme.focus)),
     };
 
-    let postponed_count = if state.is_postponed {
+    let postponed_count = if state.kind.is_postponed() {
         Span::styled(
             format!(" ({})", state.postpone_count),
             Style::default().fg(Color::DarkGray),
@@ -129,21 +265,125 @@ fn timer_info(state: &ViewState) -> Paragraph {
         Span::styled("", Style::default())
     };
 
-    let info_text = vec![
-        Spans::from(vec![timer_kind, postponed_count]),
-        Spans::from(vec![Span::styled(rounds, Style::default().fg(Color::Gray))]),
+    let mut round_info = vec![
+        Span::styled(rounds, Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!(" ({} until long break)", state.intervals_until_long_break),
+            Style::default().fg(Color::DarkGray),
+        ),
     ];
 
+    if let Some((completed, goal)) = state.goal_progress {
+        round_info.push(Span::styled(
+            format!("  {}", goal_progress_bar(completed, goal)),
+            Style::default().fg(if completed >= goal {
+                Color::Green
+            } else {
+                Color::DarkGray
+            }),
+        ));
+    }
+
+    let muted = if state.is_muted {
+        Span::styled(" [muted]", Style::default().fg(Color::DarkGray))
+    } else {
+        Span::styled("", Style::default())
+    };
+
+    let distractions = if !state.kind.is_break() && state.distractions > 0 {
+        Span::styled(
+            format!(" {} distracted", state.distractions),
+            Style::default().fg(Color::DarkGray),
+        )
+    } else {
+        Span::styled("", Style::default())
+    };
+
+    let label = match &state.label {
+        Some(label) => Span::styled(format!(" {}", label), Style::default().fg(Color::White)),
+        None => Span::styled("", Style::default()),
+    };
+
+    let mut info_text = vec![
+        Spans::from(vec![
+            timer_kind,
+            label,
+            postponed_count,
+            distractions,
+            muted,
+        ]),
+        Spans::from(round_info),
+    ];
+
+    if show_session_times {
+        info_text.push(Spans::from(Span::styled(
+            format!(
+                "Session: {}  Long break in: {}",
+                seconds_to_time(state.session_elapsed_secs),
+                seconds_to_time(state.cycle_remaining_secs)
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
     Paragraph::new(info_text)
         .block(Block::default().title("zentime").borders(Borders::ALL))
         .style(Style::default().fg(Color::White))
         .alignment(Alignment::Left)
 }
 
-/// Timer of the default interface
-fn timer(time: &str) -> Paragraph {
+/// Small progress bar for the daily goal, e.g. `▰▰▰▱▱ 3/5`.
+/// Goals above [MAX_GOAL_BAR_WIDTH] are scaled down to keep the bar short.
+fn goal_progress_bar(completed: u64, goal: u64) -> String {
+    let width = goal.clamp(1, MAX_GOAL_BAR_WIDTH);
+    let filled = (completed.min(goal) * width)
+        .checked_div(goal)
+        .unwrap_or(width);
+
+    format!(
+        "{}{} {}/{}",
+        "▰".repeat(filled as usize),
+        "▱".repeat((width - filled) as usize),
+        completed,
+        goal
+    )
+}
+
+/// Timer of the default interface.
+/// The timers completed during the current session are shown as a row of squares inside its
+/// title, e.g. `■■■■▬`.
+fn timer<'a>(time: &'a str, history: &[TimerKind], theme: &Theme) -> Paragraph<'a> {
+    // The history only ever contains ended focus intervals and breaks
+    let history_spans: Vec<Span> = history
+        .iter()
+        .filter_map(|kind| match kind {
+            TimerKind::Interval => Some(Span::styled("■", Style::default().fg(theme.focus))),
+            TimerKind::Break => Some(Span::styled("■", Style::default().fg(theme.break_color))),
+            TimerKind::LongBreak => Some(Span::styled("▬", Style::default().fg(theme.break_color))),
+            _ => None,
+        })
+        .collect();
+
     Paragraph::new(time)
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::Cyan))
+        .block(
+            Block::default()
+                .title(Spans::from(history_spans))
+                .borders(Borders::ALL),
+        )
+        .style(Style::default().fg(theme.timer))
         .alignment(Alignment::Center)
 }
+
+/// Progress of the current timer, e.g. `█████     50%`
+fn progress_gauge(state: &ViewState, theme: &Theme) -> Gauge<'static> {
+    let color = match state.kind {
+        TimerKind::Break | TimerKind::LongBreak => theme.break_color,
+        TimerKind::PostponedBreak | TimerKind::PostponedLongBreak => theme.postponed,
+        TimerKind::Interval | TimerKind::SessionComplete => theme.focus,
+    };
+
+    Gauge::default()
+        .gauge_style(Style::default().fg(color))
+        .ratio(f64::from(state.progress.clamp(0.0, 1.0)))
+        .use_unicode(true)
+}
@@ -1,6 +1,9 @@
 //! Code related to async client terminal input handling
 
+use crate::client::terminal_io::keymap::Keymap;
 use crossterm::event::{EventStream, KeyCode, KeyEvent, KeyModifiers};
+use std::io::IsTerminal;
+use tokio::signal::ctrl_c;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::yield_now;
 use tokio::{spawn, task::JoinHandle};
@@ -9,7 +12,7 @@ use tokio_stream::StreamExt;
 use crossterm::event::Event;
 
 /// Actions triggered by user terminal input on a client
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ClientInputAction {
     /// Quit Timer and terminate server
     Quit,
@@ -29,8 +32,31 @@ pub enum ClientInputAction {
     /// Resets the timer back to the first interval
     Reset,
 
+    /// Moves back to the previous timer (break or focus)
+    Previous,
+
     /// Postpones the current break, if possible (see [PomodoroTimerConfig])
     PostPone,
+
+    /// Logs a distraction during the current focus interval
+    Distraction,
+
+    /// Renders the last timer state again right away (e.g. because the terminal has been resized)
+    Redraw,
+
+    /// Starts typing a label for the current timer. This is only ever handled by the
+    /// [TerminalInputTask] itself, which forwards the typed label as [Self::EditLabel].
+    Label,
+
+    /// The label which is currently being typed (`None` once typing has been cancelled)
+    EditLabel(Option<String>),
+
+    /// Labels the current timer with the typed label (or removes the label, if it is empty)
+    SetLabel(Option<String>),
+
+    /// Toggles the zen display, which only shows the time (purely presentational, the server
+    /// is not involved)
+    Zen,
 }
 
 /// Tokio task handling terminal input events
@@ -38,16 +64,50 @@ pub enum ClientInputAction {
 pub struct TerminalInputTask {}
 
 impl TerminalInputTask {
-    /// Spanws the task and converts incoming terminal input events into [ClientInputAction]s and
-    /// sends them to the client.
-    pub async fn spawn(input_worker_tx: UnboundedSender<ClientInputAction>) -> JoinHandle<()> {
+    /// Spanws the task and converts incoming terminal input events into [ClientInputAction]s
+    /// (according to the given [Keymap]) and sends them to the client.
+    pub async fn spawn(
+        input_worker_tx: UnboundedSender<ClientInputAction>,
+        keymap: Keymap,
+    ) -> JoinHandle<()> {
         spawn(async move {
+            // WHY:
+            // Interfaces like the tmux interface are usually run by status bars, which do not
+            // attach a terminal. There is no input to read in that case (and crossterm would
+            // panic), so we simply keep the sender alive without ever sending anything.
+            if !std::io::stdin().is_terminal() {
+                let _input_worker_tx = input_worker_tx;
+                return futures::future::pending().await;
+            }
+
             let mut stream = EventStream::new();
 
+            // Label which is currently being typed (see [ClientInputAction::Label])
+            let mut label_input: Option<String> = None;
+
             loop {
                 let result = stream.next().await;
                 if let Some(Ok(event)) = result {
-                    if let Err(error) = input_worker_tx.send(handle_input(event)) {
+                    // Typing a label is modal, i.e. keybindings are ignored until the label
+                    // has been confirmed or cancelled
+                    let action = match label_input.as_mut() {
+                        Some(label) => handle_label_input(event, label),
+                        None => match handle_input(event, &keymap) {
+                            ClientInputAction::Label => {
+                                label_input = Some(String::new());
+                                ClientInputAction::EditLabel(Some(String::new()))
+                            }
+                            action => action,
+                        },
+                    };
+
+                    if let ClientInputAction::EditLabel(None) | ClientInputAction::SetLabel(_) =
+                        action
+                    {
+                        label_input = None;
+                    }
+
+                    if let Err(error) = input_worker_tx.send(action) {
                         // TODO: handle this more gracefully
                         panic!("Could not send ClientInputAction: {}", error)
                     };
@@ -59,60 +119,82 @@ impl TerminalInputTask {
     }
 }
 
-/// Keymap from terminal input events to [ClientInputAction]
-fn handle_input(event: Event) -> ClientInputAction {
-    if let Event::Key(key_event) = event {
-        match key_event {
-            KeyEvent {
-                code: KeyCode::Char('q'),
-                ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('c'),
-                modifiers: KeyModifiers::CONTROL,
-                ..
-            } => {
-                return ClientInputAction::Quit;
-            }
+/// Tokio task for clients without any terminal input (e.g. `zentime watch`).
+/// It only listens for SIGINT (Ctrl-C) and detaches the client once it has been received.
+#[derive(Copy, Clone, Debug)]
+pub struct InterruptInputTask {}
 
-            KeyEvent {
-                code: KeyCode::Char('d'),
-                ..
-            } => {
-                return ClientInputAction::Detach;
+impl InterruptInputTask {
+    /// Spawns the task, which sends a [ClientInputAction::Detach] on SIGINT
+    pub async fn spawn(input_worker_tx: UnboundedSender<ClientInputAction>) -> JoinHandle<()> {
+        spawn(async move {
+            if let Err(error) = ctrl_c().await {
+                panic!("Could not listen for SIGINT: {}", error)
             }
 
-            KeyEvent {
-                code: KeyCode::Char(' '),
-                ..
-            } => {
-                return ClientInputAction::PlayPause;
-            }
+            input_worker_tx.send(ClientInputAction::Detach).ok();
 
-            KeyEvent {
-                code: KeyCode::Char('s'),
-                ..
-            } => {
-                return ClientInputAction::Skip;
-            }
+            // The connection task shuts down the client
+            futures::future::pending().await
+        })
+    }
+}
 
-            KeyEvent {
-                code: KeyCode::Char('p'),
-                ..
-            } => {
-                return ClientInputAction::PostPone
-            }
+/// Converts terminal input events into [ClientInputAction]s
+fn handle_input(event: Event, keymap: &Keymap) -> ClientInputAction {
+    match event {
+        Event::Key(key_event) => keymap.action(&key_event),
+
+        // Resize events might occur in batches, but rendering is cheap enough to simply redraw on
+        // each of them
+        Event::Resize(_, _) => ClientInputAction::Redraw,
+
+        // WHY:
+        // With bracketed paste enabled (see [super::output::DefaultInterface]), pasted text
+        // arrives as a single event instead of one key event per character, which could
+        // otherwise trigger arbitrary keybindings.
+        Event::Paste(_) | Event::Mouse(_) | Event::FocusGained | Event::FocusLost => {
+            ClientInputAction::None
+        }
+    }
+}
 
-            KeyEvent {
-                code: KeyCode::Char('r'),
-                ..
-            } => {
-                return ClientInputAction::Reset;
+/// Converts terminal input events into edits of the label which is currently being typed.
+/// Enter confirms the label and Esc (or Ctrl-C) cancels typing.
+fn handle_label_input(event: Event, label: &mut String) -> ClientInputAction {
+    match event {
+        Event::Key(KeyEvent {
+            code, modifiers, ..
+        }) => match code {
+            KeyCode::Enter => {
+                let label = std::mem::take(label);
+                ClientInputAction::SetLabel(Some(label).filter(|label| !label.trim().is_empty()))
+            }
+            KeyCode::Esc => ClientInputAction::EditLabel(None),
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                ClientInputAction::EditLabel(None)
+            }
+            KeyCode::Backspace => {
+                label.pop();
+                ClientInputAction::EditLabel(Some(label.clone()))
             }
+            KeyCode::Char(char)
+                if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                label.push(char);
+                ClientInputAction::EditLabel(Some(label.clone()))
+            }
+            _ => ClientInputAction::None,
+        },
 
-            _ => {}
+        // Only the first line of pasted text is used, because a label is a single line
+        Event::Paste(text) => {
+            label.push_str(text.lines().next().unwrap_or_default());
+            ClientInputAction::EditLabel(Some(label.clone()))
         }
-    }
 
-    ClientInputAction::None
+        Event::Resize(_, _) => ClientInputAction::Redraw,
+
+        Event::Mouse(_) | Event::FocusGained | Event::FocusLost => ClientInputAction::None,
+    }
 }
@@ -0,0 +1,186 @@
+//! Mapping of terminal key events to [ClientInputAction]s (see [KeyBindingConfig])
+
+use crate::client::terminal_io::input::ClientInputAction;
+use crate::config::{ConfigProblem, KeyBindingConfig};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::str::FromStr;
+
+/// A single key together with its modifiers, e.g. `ctrl-c`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Key {
+    code: KeyCode,
+
+    /// Only `CONTROL` and `ALT` are taken into account. Shifted characters are configured as
+    /// uppercase characters instead (e.g. `S`).
+    modifiers: KeyModifiers,
+}
+
+/// Error returned when parsing an unknown [Key]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UnknownKeyError;
+
+impl FromStr for Key {
+    type Err = UnknownKeyError;
+
+    /// Parses keys like `q`, `S`, `space`, `left`, `f5` or `ctrl-c` and `alt-x`
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut name = key.trim();
+
+        loop {
+            if let Some(rest) = strip_modifier(name, "ctrl-") {
+                modifiers |= KeyModifiers::CONTROL;
+                name = rest;
+            } else if let Some(rest) = strip_modifier(name, "alt-") {
+                modifiers |= KeyModifiers::ALT;
+                name = rest;
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(char), None) => KeyCode::Char(char),
+            _ => match name.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                function_key => function_key
+                    .strip_prefix('f')
+                    .and_then(|number| number.parse::<u8>().ok())
+                    .filter(|number| (1..=12).contains(number))
+                    .map(KeyCode::F)
+                    .ok_or(UnknownKeyError)?,
+            },
+        };
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+/// Strips the given (case insensitive) modifier prefix, unless nothing would be left of the key
+fn strip_modifier<'a>(name: &'a str, prefix: &str) -> Option<&'a str> {
+    name.get(..prefix.len())
+        .filter(|start| start.eq_ignore_ascii_case(prefix))
+        .and_then(|_| name.get(prefix.len()..))
+        .filter(|rest| !rest.is_empty())
+}
+
+impl Key {
+    fn matches(&self, event: &KeyEvent) -> bool {
+        self.code == event.code
+            && self.modifiers == event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+/// Keymap from terminal key events to [ClientInputAction]s
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Key, ClientInputAction)>,
+}
+
+impl Keymap {
+    /// Creates the keymap of the given config.
+    /// Fails with every unknown key and every key which is bound to more than one action.
+    pub fn new(config: &KeyBindingConfig) -> Result<Self, Vec<ConfigProblem>> {
+        let actions = [
+            ("quit", &config.quit, ClientInputAction::Quit),
+            ("detach", &config.detach, ClientInputAction::Detach),
+            (
+                "play_pause",
+                &config.play_pause,
+                ClientInputAction::PlayPause,
+            ),
+            ("skip", &config.skip, ClientInputAction::Skip),
+            ("reset", &config.reset, ClientInputAction::Reset),
+            ("previous", &config.previous, ClientInputAction::Previous),
+            ("postpone", &config.postpone, ClientInputAction::PostPone),
+            (
+                "distraction",
+                &config.distraction,
+                ClientInputAction::Distraction,
+            ),
+            ("label", &config.label, ClientInputAction::Label),
+            ("zen", &config.zen, ClientInputAction::Zen),
+        ];
+
+        let mut bindings: Vec<(Key, ClientInputAction)> = vec![];
+        let mut bound_by: Vec<&'static str> = vec![];
+        let mut problems = vec![];
+
+        for (name, keys, action) in actions {
+            for key_name in keys {
+                let Ok(key) = key_name.parse::<Key>() else {
+                    problems.push(ConfigProblem::UnknownKey(name, key_name.clone()));
+                    continue;
+                };
+
+                match bindings.iter().position(|(bound_key, _)| *bound_key == key) {
+                    // NOTE: Listing the same key twice for a single action is harmless
+                    Some(index) if bound_by[index] == name => {}
+                    Some(index) => problems.push(ConfigProblem::ConflictingKeyBinding(
+                        key_name.clone(),
+                        bound_by[index],
+                        name,
+                    )),
+                    None => {
+                        bindings.push((key, action.clone()));
+                        bound_by.push(name);
+                    }
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(Self { bindings })
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Keeps only the keys which leave the client and lets all of them detach, so that the
+    /// client can't control the timer (see [crate::client::start::observe]).
+    /// The zen display is kept, because it only affects the client itself.
+    pub fn read_only(self) -> Self {
+        let bindings = self
+            .bindings
+            .into_iter()
+            .filter_map(|(key, action)| match action {
+                ClientInputAction::Quit | ClientInputAction::Detach => {
+                    Some((key, ClientInputAction::Detach))
+                }
+                ClientInputAction::Zen => Some((key, ClientInputAction::Zen)),
+                _ => None,
+            })
+            .collect();
+
+        Self { bindings }
+    }
+
+    /// Returns the action which is bound to the given key event (if any)
+    pub fn action(&self, event: &KeyEvent) -> ClientInputAction {
+        self.bindings
+            .iter()
+            .find(|(key, _)| key.matches(event))
+            .map_or(ClientInputAction::None, |(_, action)| action.clone())
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&KeyBindingConfig::default()).expect("Default keybindings should be valid")
+    }
+}
@@ -1,19 +1,25 @@
 //! Code related to client async terminal output handling
 
-use crate::client::terminal_io::default_interface::render;
+use crate::client::terminal_io::big_clock;
+use crate::client::terminal_io::default_interface::{render, render_reconnecting, InputState};
+use crate::client::terminal_io::theme::Theme;
+use crate::config::{SymbolConfig, ThemeConfig};
 use anyhow::Context;
 use crossterm::cursor::Hide;
-use crossterm::style::Stylize;
+use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
+use crossterm::style::{ContentStyle, StyledContent, Stylize};
 use crossterm::terminal::{enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::{cursor::Show, event::DisableMouseCapture, execute, terminal::disable_raw_mode};
 use futures::lock::Mutex;
-use zentime_rs_timer::pomodoro_timer::ViewState;
+use std::fmt::Display;
 use std::io::Write;
 use std::sync::Arc;
 use std::{io::Stdout, process};
 use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::task::{spawn, JoinHandle};
 use tui::{backend::CrosstermBackend, Terminal as TuiTerminal};
+use unicode_width::UnicodeWidthStr;
+use zentime_rs_timer::pomodoro_timer::{TimerKind, ViewState};
 
 use super::terminal_event::TerminalEvent;
 
@@ -28,10 +34,85 @@ impl TerminalOutputTask {
         mut out_rx: UnboundedReceiver<TerminalEvent>,
     ) -> JoinHandle<()> {
         spawn(async move {
+            // Cached, so that a resized terminal can be redrawn without waiting for the server
+            let mut last_state: Option<ViewState> = None;
+
             loop {
                 match out_rx.recv().await {
                     Some(TerminalEvent::View(state)) => {
-                        if let Err(error) = terminal_out.lock().await.render(state) {
+                        last_state = Some(state.clone());
+
+                        // WHY:
+                        // The lock has to be released before quitting, because a guard inside
+                        // the `if let` condition would live until the end of its block.
+                        let result = terminal_out.lock().await.render(state);
+                        if let Err(error) = result {
+                            return terminal_out
+                                .lock()
+                                .await
+                                .quit(Some(format!("ERROR: {}", error)), true);
+                        }
+                    }
+                    Some(TerminalEvent::Redraw) => {
+                        let Some(state) = last_state.clone() else {
+                            continue;
+                        };
+
+                        let result = terminal_out.lock().await.render(state);
+                        if let Err(error) = result {
+                            return terminal_out
+                                .lock()
+                                .await
+                                .quit(Some(format!("ERROR: {}", error)), true);
+                        }
+                    }
+                    Some(TerminalEvent::Reconnecting {
+                        attempt,
+                        max_attempts,
+                    }) => {
+                        // The state of the lost server is outdated
+                        last_state = None;
+
+                        let result = terminal_out
+                            .lock()
+                            .await
+                            .render_reconnecting(attempt, max_attempts);
+                        if let Err(error) = result {
+                            return terminal_out
+                                .lock()
+                                .await
+                                .quit(Some(format!("ERROR: {}", error)), true);
+                        }
+                    }
+                    Some(TerminalEvent::SkipConfirmation { is_pending }) => {
+                        terminal_out.lock().await.set_skip_pending(is_pending);
+                    }
+                    // The typed label is shown right away instead of waiting for the next tick
+                    Some(TerminalEvent::LabelInput(label)) => {
+                        terminal_out.lock().await.set_label_input(label);
+
+                        let Some(state) = last_state.clone() else {
+                            continue;
+                        };
+
+                        let result = terminal_out.lock().await.render(state);
+                        if let Err(error) = result {
+                            return terminal_out
+                                .lock()
+                                .await
+                                .quit(Some(format!("ERROR: {}", error)), true);
+                        }
+                    }
+                    // Switched right away instead of waiting for the next tick
+                    Some(TerminalEvent::ToggleZen) => {
+                        terminal_out.lock().await.toggle_zen();
+
+                        let Some(state) = last_state.clone() else {
+                            continue;
+                        };
+
+                        let result = terminal_out.lock().await.render(state);
+                        if let Err(error) = result {
                             return terminal_out
                                 .lock()
                                 .await
@@ -53,6 +134,26 @@ pub trait TerminalOut {
     /// Renders the current [ViewState]
     fn render(&mut self, state: ViewState) -> anyhow::Result<()>;
 
+    /// Renders a hint, that the client is currently trying to reconnect to the server
+    fn render_reconnecting(&mut self, attempt: u32, max_attempts: u32) -> anyhow::Result<()>;
+
+    /// Denotes if a skip waits for confirmation, so that it can be hinted at on the next render.
+    /// Interfaces without any keyboard input ignore this by default.
+    fn set_skip_pending(&mut self, _is_pending: bool) {}
+
+    /// Sets the label which is currently being typed (`None` once typing has ended), so that it
+    /// can be shown on the next render.
+    /// Interfaces without any keyboard input ignore this by default.
+    fn set_label_input(&mut self, _label: Option<String>) {}
+
+    /// Denotes that the client can't control the timer, so that keybinding hints can be hidden.
+    /// Interfaces without any keybinding hints ignore this by default.
+    fn set_readonly(&mut self, _is_readonly: bool) {}
+
+    /// Switches between the regular layout and the zen display, which only shows the time.
+    /// Interfaces without a zen display ignore this by default.
+    fn toggle_zen(&mut self) {}
+
     /// Gracefully quits the [Self] so that raw-mode, alternate screens etc.
     /// are restored to their default.
     fn quit(&mut self, msg: Option<String>, is_error: bool);
@@ -64,11 +165,132 @@ pub trait TerminalOut {
 #[derive()]
 pub struct DefaultInterface {
     tui_terminal: TuiTerminal<CrosstermBackend<Stdout>>,
+    theme: Theme,
+    show_session_times: bool,
+    is_alternate_screen: bool,
+    is_skip_pending: bool,
+    strict_breaks: bool,
+    is_readonly: bool,
+    label_input: Option<String>,
+    is_zen: bool,
 }
 
 impl DefaultInterface {
-    /// Creates a new default interface
-    pub fn new() -> anyhow::Result<Self> {
+    /// Creates a new default interface, which is colored according to the given theme.
+    /// If `show_session_times` is set, the session times are shown inside the info panel.
+    /// If `use_alternate_screen` is not set, the interface is rendered inside the main buffer.
+    /// If `strict_breaks` is set, the skip keybinding is hidden during breaks.
+    pub fn new(
+        theme: &ThemeConfig,
+        show_session_times: bool,
+        use_alternate_screen: bool,
+        strict_breaks: bool,
+    ) -> anyhow::Result<Self> {
+        let backend = CrosstermBackend::new(std::io::stdout());
+        if use_alternate_screen {
+            execute!(std::io::stdout(), EnterAlternateScreen)
+                .context("Can't execute crossterm macros")?;
+        }
+        let mut terminal =
+            TuiTerminal::new(backend).context("Tui-Terminal could not be created")?;
+        enable_raw_mode().context("Can't run in raw mode")?;
+        execute!(std::io::stdout(), EnableBracketedPaste)
+            .context("Can't execute crossterm macros")?;
+        terminal.clear().context("Terminal could not be cleared")?;
+        terminal.hide_cursor().context("Could not hide cursor")?;
+
+        Ok(Self {
+            tui_terminal: terminal,
+            theme: Theme::from_config(theme),
+            show_session_times,
+            is_alternate_screen: use_alternate_screen,
+            is_skip_pending: false,
+            strict_breaks,
+            is_readonly: false,
+            label_input: None,
+            is_zen: false,
+        })
+    }
+}
+
+impl TerminalOut for DefaultInterface {
+    fn render(&mut self, state: ViewState) -> anyhow::Result<()> {
+        // NOTE: A label which is being typed is still shown, so that typing does not happen
+        // blindly
+        if self.is_zen && self.label_input.is_none() {
+            return big_clock::render(&mut self.tui_terminal, state, &self.theme, false);
+        }
+
+        render(
+            &mut self.tui_terminal,
+            state,
+            &self.theme,
+            self.show_session_times,
+            self.strict_breaks,
+            InputState {
+                is_skip_pending: self.is_skip_pending,
+                is_readonly: self.is_readonly,
+                label_input: self.label_input.as_deref(),
+            },
+        )
+    }
+
+    fn render_reconnecting(&mut self, attempt: u32, max_attempts: u32) -> anyhow::Result<()> {
+        render_reconnecting(&mut self.tui_terminal, attempt, max_attempts)
+    }
+
+    fn set_skip_pending(&mut self, is_pending: bool) {
+        self.is_skip_pending = is_pending;
+    }
+
+    fn set_readonly(&mut self, is_readonly: bool) {
+        self.is_readonly = is_readonly;
+    }
+
+    fn set_label_input(&mut self, label: Option<String>) {
+        self.label_input = label;
+    }
+
+    fn toggle_zen(&mut self) {
+        self.is_zen = !self.is_zen;
+    }
+
+    fn quit(&mut self, msg: Option<String>, is_error: bool) {
+        disable_raw_mode().expect("Could not disable raw mode");
+        self.tui_terminal
+            .show_cursor()
+            .expect("Could not show cursor");
+        self.tui_terminal.clear().expect("Could not clear terminal");
+        execute!(
+            std::io::stdout(),
+            DisableMouseCapture,
+            DisableBracketedPaste
+        )
+        .expect("Could not execute crossterm macros");
+
+        if self.is_alternate_screen {
+            execute!(std::io::stdout(), LeaveAlternateScreen)
+                .expect("Could not execute crossterm macros");
+        }
+
+        println!("\n{}", msg.unwrap_or_else(|| String::from("")));
+
+        process::exit(i32::from(is_error))
+    }
+}
+
+/// Kiosk-like interface, which renders the timer in large block digits filling the terminal.
+/// Falls back to the regular text size, if the terminal is too small.
+#[allow(missing_debug_implementations)]
+pub struct BigClockInterface {
+    tui_terminal: TuiTerminal<CrosstermBackend<Stdout>>,
+    theme: Theme,
+}
+
+impl BigClockInterface {
+    /// Creates a new big clock interface inside the alternate screen, which is colored according
+    /// to the given theme
+    pub fn new(theme: &ThemeConfig) -> anyhow::Result<Self> {
         let backend = CrosstermBackend::new(std::io::stdout());
         execute!(std::io::stdout(), EnterAlternateScreen)
             .context("Can't execute crossterm macros")?;
@@ -80,13 +302,18 @@ impl DefaultInterface {
 
         Ok(Self {
             tui_terminal: terminal,
+            theme: Theme::from_config(theme),
         })
     }
 }
 
-impl TerminalOut for DefaultInterface {
+impl TerminalOut for BigClockInterface {
     fn render(&mut self, state: ViewState) -> anyhow::Result<()> {
-        render(&mut self.tui_terminal, state)
+        big_clock::render(&mut self.tui_terminal, state, &self.theme, true)
+    }
+
+    fn render_reconnecting(&mut self, attempt: u32, max_attempts: u32) -> anyhow::Result<()> {
+        render_reconnecting(&mut self.tui_terminal, attempt, max_attempts)
     }
 
     fn quit(&mut self, msg: Option<String>, is_error: bool) {
@@ -95,7 +322,7 @@ impl TerminalOut for DefaultInterface {
             .show_cursor()
             .expect("Could not show cursor");
         self.tui_terminal.clear().expect("Could not clear terminal");
-        execute!(std::io::stdout(), DisableMouseCapture, LeaveAlternateScreen)
+        execute!(std::io::stdout(), LeaveAlternateScreen)
             .expect("Could not execute crossterm macros");
 
         println!("\n{}", msg.unwrap_or_else(|| String::from("")));
@@ -106,53 +333,144 @@ impl TerminalOut for DefaultInterface {
 
 /// Minimal interface which uses a [Crossterm] to display colors, hide the cursor and enable raw mode.
 /// The actual rendering happens with simple `print!`-macro-calls.
-#[derive(Debug, Copy, Clone)]
-pub struct MinimalInterface {}
+#[derive(Debug, Clone)]
+pub struct MinimalInterface {
+    show_progress_bar: bool,
+    use_colors: bool,
+    is_skip_pending: bool,
+    label_input: Option<String>,
+}
+
+/// Number of segments of the progress bar of the [MinimalInterface]
+const PROGRESS_BAR_WIDTH: usize = 8;
 
 impl MinimalInterface {
     /// Creates a new minimal interface and also enables raw mode and hides the cursor.
-    pub fn new() -> anyhow::Result<Self> {
+    /// If `show_progress_bar` is set, a short progress bar (e.g. `[####----]`) is rendered
+    /// after the timer.
+    /// If `use_colors` is not set, the interface is rendered as plain text.
+    pub fn new(show_progress_bar: bool, use_colors: bool) -> anyhow::Result<Self> {
         enable_raw_mode().context("Can't run in raw mode")?;
 
         execute!(std::io::stdout(), Hide).context("Could not execute crossterm macros")?;
-        Ok(Self {})
+        Ok(Self {
+            show_progress_bar,
+            use_colors,
+            is_skip_pending: false,
+            label_input: None,
+        })
     }
+
+    /// Removes all styling from the given content, if colors are disabled
+    fn paint<D: Display>(&self, mut content: StyledContent<D>) -> StyledContent<D> {
+        if !self.use_colors {
+            *content.style_mut() = ContentStyle::new();
+        }
+
+        content
+    }
+}
+
+/// Text progress bar like `[####----]`
+fn progress_bar(progress: f32) -> String {
+    let filled = ((progress.clamp(0.0, 1.0) * PROGRESS_BAR_WIDTH as f32).round() as usize)
+        .min(PROGRESS_BAR_WIDTH);
+
+    format!(
+        "[{}{}]",
+        "#".repeat(filled),
+        "-".repeat(PROGRESS_BAR_WIDTH - filled)
+    )
 }
 
 impl TerminalOut for MinimalInterface {
     fn render(&mut self, state: ViewState) -> anyhow::Result<()> {
-        let timer = format!(" {} ", state.time.white());
+        let timer = format!(" {} ", self.paint(state.time.white()));
         let round = format!("Round: {}", state.round);
-        let timer_kind = if state.is_break {
-                "Break".yellow()
-            } else if state.is_postponed {
-                "Postpone".red()
-            } else {
-                "Focus".blue()
-            };
+        let timer_kind = match state.kind {
+            TimerKind::SessionComplete => "Complete".green(),
+            TimerKind::Break | TimerKind::LongBreak => "Break".yellow(),
+            TimerKind::PostponedBreak | TimerKind::PostponedLongBreak => "Postpone".red(),
+            TimerKind::Interval if state.is_overtime => "Overtime".blue(),
+            TimerKind::Interval => "Focus".blue(),
+        };
 
-        let postponed_count = if state.is_postponed {
+        let postponed_count = if state.kind.is_postponed() {
             format!(" ({})", state.postpone_count).dark_grey()
         } else {
             "".to_string().white()
         };
 
+        let muted = if state.is_muted {
+            " [muted]".dark_grey()
+        } else {
+            "".white()
+        };
+
+        let label = match &state.label {
+            Some(label) => format!(" \"{}\"", label).white(),
+            None => "".to_string().white(),
+        };
+
+        // The label which is being typed replaces the skip hint
+        let hint = match &self.label_input {
+            Some(label_input) => format!(" Label: {}_ (Enter/Esc)", label_input).yellow(),
+            None if self.is_skip_pending => " Press [s] again to skip".to_string().yellow(),
+            None => "".to_string().white(),
+        };
+
+        let progress = if self.show_progress_bar {
+            format!(" {}", progress_bar(state.progress)).dark_grey()
+        } else {
+            "".to_string().white()
+        };
+
         let ansi_erase_line_escape = "\x1B[2K";
         let ansi_move_cursor_to_start_of_line_escape = "\r";
 
         print!(
-            "{}{}{} {} {}{}",
+            "{}{}{}{} {} {}{}{}{}{}",
             ansi_move_cursor_to_start_of_line_escape,
             ansi_erase_line_escape,
-            if state.is_paused { timer.on_dark_green() } else { timer.on_dark_red() },
-            round.green(),
-            timer_kind,
-            postponed_count
+            self.paint(if state.is_paused {
+                timer.on_dark_green()
+            } else {
+                timer.on_dark_red()
+            }),
+            self.paint(progress),
+            self.paint(round.green()),
+            self.paint(timer_kind),
+            self.paint(label),
+            self.paint(postponed_count),
+            self.paint(muted),
+            self.paint(hint)
         );
 
         Ok(std::io::stdout().flush()?)
     }
 
+    fn render_reconnecting(&mut self, attempt: u32, max_attempts: u32) -> anyhow::Result<()> {
+        let ansi_erase_line_escape = "\x1B[2K";
+        let ansi_move_cursor_to_start_of_line_escape = "\r";
+
+        print!(
+            "{}{}{}",
+            ansi_move_cursor_to_start_of_line_escape,
+            ansi_erase_line_escape,
+            self.paint(format!("Reconnecting ({}/{})...", attempt, max_attempts).dark_grey())
+        );
+
+        Ok(std::io::stdout().flush()?)
+    }
+
+    fn set_skip_pending(&mut self, is_pending: bool) {
+        self.is_skip_pending = is_pending;
+    }
+
+    fn set_label_input(&mut self, label: Option<String>) {
+        self.label_input = label;
+    }
+
     fn quit(&mut self, msg: Option<String>, is_error: bool) {
         disable_raw_mode().expect("Could not disable raw mode");
         execute!(std::io::stdout(), Show, DisableMouseCapture)
@@ -163,3 +481,136 @@ impl TerminalOut for MinimalInterface {
         process::exit(i32::from(is_error))
     }
 }
+
+/// Interface which prints each [ViewState] as a single line of JSON and never enters raw mode.
+/// Messages (e.g. while reconnecting) are written to stderr, so that stdout only contains
+/// JSON lines.
+#[derive(Debug, Clone, Default)]
+pub struct JsonInterface {
+    last_line: String,
+}
+
+impl TerminalOut for JsonInterface {
+    fn render(&mut self, state: ViewState) -> anyhow::Result<()> {
+        let line = serde_json::to_string(&state).context("Could not serialize view state")?;
+
+        // The server sends its state multiple times per second
+        if line == self.last_line {
+            return Ok(());
+        }
+
+        // WHY:
+        // Unlike `println!`, this does not panic if the reading end of a pipe has been closed
+        let mut stdout = std::io::stdout();
+        writeln!(stdout, "{}", line).context("Could not write to stdout")?;
+        self.last_line = line;
+
+        Ok(stdout.flush()?)
+    }
+
+    fn render_reconnecting(&mut self, attempt: u32, max_attempts: u32) -> anyhow::Result<()> {
+        eprintln!("Reconnecting ({}/{})...", attempt, max_attempts);
+
+        Ok(())
+    }
+
+    fn quit(&mut self, msg: Option<String>, is_error: bool) {
+        if let Some(msg) = msg {
+            eprintln!("{}", msg.trim());
+        }
+
+        process::exit(i32::from(is_error))
+    }
+}
+
+/// Interface which prints a single fixed-width line per render, without any colors or cursor
+/// manipulation. This is meant to be read by status bars (e.g. `#(zentime -i tmux)` inside tmux).
+#[derive(Debug, Clone)]
+pub struct TmuxInterface {
+    symbols: SymbolConfig,
+    intervals: u64,
+    last_line: String,
+}
+
+impl TmuxInterface {
+    /// Creates a new tmux interface.
+    /// `intervals` is required to show the position inside the current pomodoro cycle.
+    pub fn new(symbols: SymbolConfig, intervals: u64) -> Self {
+        Self {
+            symbols,
+            intervals,
+            last_line: String::new(),
+        }
+    }
+
+    /// Formats the state like `🍅 24:59 (2/4)`
+    fn line(&self, state: &ViewState) -> String {
+        let symbol = if state.is_paused {
+            &self.symbols.paused
+        } else if state.kind.is_break() || state.kind == TimerKind::SessionComplete {
+            &self.symbols.break_symbol
+        } else {
+            &self.symbols.focus
+        };
+
+        // WHY:
+        // Every part is padded to a stable width, so that the status bar does not jitter
+        // whenever the timer changes its state.
+        let symbol_width = [
+            &self.symbols.focus,
+            &self.symbols.break_symbol,
+            &self.symbols.paused,
+        ]
+        .iter()
+        .map(|symbol| symbol.width())
+        .max()
+        .unwrap_or(0);
+        let symbol_padding = " ".repeat(symbol_width - symbol.width());
+
+        let cycle_position = state
+            .round
+            .saturating_sub(1)
+            .checked_rem(self.intervals)
+            .map_or(state.round, |rest| rest + 1);
+
+        format!(
+            "{}{} {:>5} ({:>width$}/{})",
+            symbol,
+            symbol_padding,
+            state.time,
+            cycle_position,
+            self.intervals,
+            width = self.intervals.to_string().len()
+        )
+    }
+}
+
+impl TerminalOut for TmuxInterface {
+    fn render(&mut self, state: ViewState) -> anyhow::Result<()> {
+        let line = self.line(&state);
+
+        // The server sends its state multiple times per second
+        if line == self.last_line {
+            return Ok(());
+        }
+
+        println!("{}", line);
+        self.last_line = line;
+
+        Ok(std::io::stdout().flush()?)
+    }
+
+    fn render_reconnecting(&mut self, attempt: u32, max_attempts: u32) -> anyhow::Result<()> {
+        println!("Reconnecting ({}/{})...", attempt, max_attempts);
+
+        Ok(std::io::stdout().flush()?)
+    }
+
+    fn quit(&mut self, msg: Option<String>, is_error: bool) {
+        if let Some(msg) = msg {
+            println!("{}", msg);
+        }
+
+        process::exit(i32::from(is_error))
+    }
+}
@@ -8,8 +8,33 @@ pub enum TerminalEvent {
     /// Rendering information with a [ViewState]
     View(ViewState),
 
-    /// The timer received an [AppAction::Quit] and forwards
-    /// this information to the view
+    /// The connection to the server has been lost and the client is trying to reconnect
+    Reconnecting {
+        /// Current reconnect attempt (starting at 1)
+        attempt: u32,
+
+        /// Maximum number of attempts before the client quits
+        max_attempts: u32,
+    },
+
+    /// The last [ViewState] should be rendered again right away (e.g. after a terminal resize)
+    Redraw,
+
+    /// A skip of the current focus interval has been requested and waits for confirmation
+    /// (or the confirmation is not pending anymore)
+    SkipConfirmation {
+        /// Denotes if the skip is waiting for confirmation
+        is_pending: bool,
+    },
+
+    /// A label for the current timer is being typed (`None` once typing has ended)
+    LabelInput(Option<String>),
+
+    /// The zen display (only the time is shown) should be toggled
+    ToggleZen,
+
+    /// The client quits (e.g. on a [super::input::ClientInputAction::Quit] or because the
+    /// server could not be reached) and forwards this information to the view
     Quit {
         /// Optinal message to display on quit
         msg: Option<String>,
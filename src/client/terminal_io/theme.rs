@@ -0,0 +1,175 @@
+//! Color themes of the default interface
+
+use crate::config::ThemeConfig;
+use log::info;
+use tui::style::Color;
+
+/// Colors of the semantic roles inside the default interface
+#[derive(Debug, Copy, Clone)]
+pub struct Theme {
+    pub focus: Color,
+    pub break_color: Color,
+    pub postponed: Color,
+    pub timer: Color,
+    pub keybindings: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            focus: Color::Blue,
+            break_color: Color::Yellow,
+            postponed: Color::Red,
+            timer: Color::Cyan,
+            keybindings: Color::DarkGray,
+        }
+    }
+}
+
+impl Theme {
+    /// Theme without any colors besides shades of gray
+    fn monochrome() -> Self {
+        Self {
+            focus: Color::White,
+            break_color: Color::Gray,
+            postponed: Color::White,
+            timer: Color::White,
+            keybindings: Color::DarkGray,
+        }
+    }
+
+    /// Creates the theme from the built-in theme named in the config and overrides its colors
+    /// with all configured colors. Unknown theme or color names fall back to the built-in values.
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let base = match config.name.as_str() {
+            "monochrome" => Self::monochrome(),
+            "default" => Self::default(),
+            name => {
+                info!("Unknown theme \"{}\" - falling back to default theme", name);
+                Self::default()
+            }
+        };
+
+        Self {
+            focus: color_or(&config.focus, base.focus),
+            break_color: color_or(&config.break_color, base.break_color),
+            postponed: color_or(&config.postponed, base.postponed),
+            timer: color_or(&config.timer, base.timer),
+            keybindings: color_or(&config.keybindings, base.keybindings),
+        }
+    }
+}
+
+/// Parses the configured color or returns the fallback, if none is configured or the color is
+/// unknown
+fn color_or(color: &Option<String>, fallback: Color) -> Color {
+    match color {
+        Some(color) => parse_color(color).unwrap_or_else(|| {
+            info!(
+                "Unknown color \"{}\" - falling back to {:?}",
+                color, fallback
+            );
+            fallback
+        }),
+        None => fallback,
+    }
+}
+
+/// Parses a color name (e.g. "blue", "light-red" or "dark_gray") or a hex string (e.g. "#5f87ff")
+fn parse_color(color: &str) -> Option<Color> {
+    let color = color.trim().to_lowercase().replace(['-', '_', ' '], "");
+
+    if let Some(hex) = color.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+
+        let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+        return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+
+    let color = match color.as_str() {
+        "reset" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return None,
+    };
+
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(parse_color("blue"), Some(Color::Blue));
+        assert_eq!(parse_color(" Light-Red "), Some(Color::LightRed));
+        assert_eq!(parse_color("dark_grey"), Some(Color::DarkGray));
+        assert_eq!(parse_color("light magenta"), Some(Color::LightMagenta));
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(parse_color("#5f87ff"), Some(Color::Rgb(0x5f, 0x87, 0xff)));
+        assert_eq!(parse_color("#FFFFFF"), Some(Color::Rgb(255, 255, 255)));
+        assert_eq!(parse_color("#fff"), None);
+        assert_eq!(parse_color("#gg0000"), None);
+        assert_eq!(parse_color("5f87ff"), None);
+    }
+
+    #[test]
+    fn invalid_colors_fall_back() {
+        assert_eq!(parse_color("sparkly"), None);
+        assert_eq!(
+            color_or(&Some("sparkly".to_string()), Color::Blue),
+            Color::Blue
+        );
+        assert_eq!(color_or(&None, Color::Blue), Color::Blue);
+    }
+
+    #[test]
+    fn partial_overrides_keep_the_remaining_colors_of_the_base_theme() {
+        let theme = Theme::from_config(&ThemeConfig {
+            name: "monochrome".to_string(),
+            focus: Some("green".to_string()),
+            timer: Some("#010203".to_string()),
+            postponed: Some("sparkly".to_string()),
+            ..ThemeConfig::default()
+        });
+        let monochrome = Theme::monochrome();
+
+        assert_eq!(theme.focus, Color::Green);
+        assert_eq!(theme.timer, Color::Rgb(1, 2, 3));
+        assert_eq!(theme.postponed, monochrome.postponed);
+        assert_eq!(theme.break_color, monochrome.break_color);
+        assert_eq!(theme.keybindings, monochrome.keybindings);
+    }
+
+    #[test]
+    fn unknown_themes_fall_back_to_the_default_theme() {
+        let theme = Theme::from_config(&ThemeConfig {
+            name: "solarized".to_string(),
+            ..ThemeConfig::default()
+        });
+        let default = Theme::default();
+
+        assert_eq!(theme.focus, default.focus);
+        assert_eq!(theme.break_color, default.break_color);
+    }
+}
@@ -0,0 +1,212 @@
+//! System tray interface (only available with the `tray` feature).
+//! The tray is registered as a StatusNotifierItem via D-Bus, which is supported by most
+//! Linux desktop environments.
+
+use crate::client::terminal_io::input::ClientInputAction;
+use crate::client::terminal_io::output::TerminalOut;
+use anyhow::Context;
+use ksni::menu::StandardItem;
+use ksni::{MenuItem, ToolTip, TrayMethods};
+use std::process;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::task::{spawn, JoinHandle};
+use zentime_rs_timer::pomodoro_timer::{TimerKind, ViewState};
+
+/// Text which is currently shown by the tray
+#[derive(Debug, Clone, Default)]
+struct TrayContent {
+    /// Short description of the current timer, e.g. `Focus 24:59`
+    status: String,
+
+    /// Denotes if the current break can still be postponed
+    can_postpone: bool,
+}
+
+/// StatusNotifierItem, which shows the current timer inside its tooltip and menu
+#[derive(Debug)]
+struct ZentimeTray {
+    content: Arc<Mutex<TrayContent>>,
+    action_tx: UnboundedSender<ClientInputAction>,
+}
+
+impl ZentimeTray {
+    fn content(&self) -> TrayContent {
+        self.content
+            .lock()
+            .map(|content| content.clone())
+            .unwrap_or_default()
+    }
+
+    /// Menu entry which sends the given action to the client
+    fn action_item(label: &str, action: ClientInputAction) -> MenuItem<Self> {
+        StandardItem {
+            label: label.to_string(),
+            activate: Box::new(move |tray: &mut Self| {
+                tray.action_tx.send(action.clone()).ok();
+            }),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl ksni::Tray for ZentimeTray {
+    fn id(&self) -> String {
+        String::from("zentime")
+    }
+
+    fn title(&self) -> String {
+        format!("zentime - {}", self.content().status)
+    }
+
+    fn icon_name(&self) -> String {
+        String::from("appointment-soon")
+    }
+
+    fn tool_tip(&self) -> ToolTip {
+        ToolTip {
+            title: String::from("zentime"),
+            description: self.content().status,
+            ..Default::default()
+        }
+    }
+
+    fn menu(&self) -> Vec<MenuItem<Self>> {
+        let content = self.content();
+
+        let mut menu = vec![
+            StandardItem {
+                label: content.status,
+                enabled: false,
+                ..Default::default()
+            }
+            .into(),
+            MenuItem::Separator,
+            Self::action_item("Play/Pause", ClientInputAction::PlayPause),
+            Self::action_item("Skip", ClientInputAction::Skip),
+        ];
+
+        if content.can_postpone {
+            menu.push(Self::action_item("Postpone", ClientInputAction::PostPone));
+        }
+
+        menu.extend([
+            MenuItem::Separator,
+            Self::action_item("Detach", ClientInputAction::Detach),
+            Self::action_item("Quit", ClientInputAction::Quit),
+        ]);
+
+        menu
+    }
+}
+
+/// Implementation of a [TerminalOut], which renders into the system tray instead of the
+/// terminal. Menu entries are sent as [ClientInputAction]s (see [TrayInputTask]).
+#[allow(missing_debug_implementations)]
+pub struct TrayInterface {
+    handle: ksni::Handle<ZentimeTray>,
+    content: Arc<Mutex<TrayContent>>,
+}
+
+impl TrayInterface {
+    /// Registers the tray and returns it together with the receiver of its menu actions.
+    /// This fails if no StatusNotifierItem-host is available (e.g. outside of a desktop session).
+    pub async fn new() -> anyhow::Result<(Self, UnboundedReceiver<ClientInputAction>)> {
+        let (action_tx, action_rx) = unbounded_channel();
+        let content = Arc::new(Mutex::new(TrayContent {
+            status: String::from("Connecting..."),
+            can_postpone: false,
+        }));
+
+        let handle = ZentimeTray {
+            content: content.clone(),
+            action_tx,
+        }
+        .spawn()
+        .await
+        .context("Could not register system tray")?;
+
+        Ok((Self { handle, content }, action_rx))
+    }
+
+    /// Replaces the content of the tray and notifies the tray host about the change
+    fn update(&mut self, content: TrayContent) {
+        if let Ok(mut current_content) = self.content.lock() {
+            *current_content = content;
+        }
+
+        // WHY:
+        // The tray reads its content on every update, so that updates which are handled out of
+        // order never show an outdated timer.
+        let handle = self.handle.clone();
+        spawn(async move { handle.update(|_| {}).await });
+    }
+}
+
+impl TerminalOut for TrayInterface {
+    fn render(&mut self, state: ViewState) -> anyhow::Result<()> {
+        let kind = match state.kind {
+            TimerKind::Interval if state.is_overtime => "Overtime",
+            TimerKind::Interval => "Focus",
+            TimerKind::Break | TimerKind::LongBreak => "Break",
+            TimerKind::PostponedBreak | TimerKind::PostponedLongBreak => "Postponed",
+            TimerKind::SessionComplete => "Session complete",
+        };
+
+        self.update(TrayContent {
+            status: format!(
+                "{} {}{}",
+                kind,
+                state.time,
+                if state.is_paused { " (paused)" } else { "" }
+            ),
+            can_postpone: state.can_postpone,
+        });
+
+        Ok(())
+    }
+
+    fn render_reconnecting(&mut self, attempt: u32, max_attempts: u32) -> anyhow::Result<()> {
+        self.update(TrayContent {
+            status: format!("Reconnecting ({}/{})...", attempt, max_attempts),
+            can_postpone: false,
+        });
+
+        Ok(())
+    }
+
+    fn quit(&mut self, msg: Option<String>, is_error: bool) {
+        // The tray disappears once the process exits
+        drop(self.handle.shutdown());
+
+        if let Some(msg) = msg {
+            println!("{}", msg);
+        }
+
+        process::exit(i32::from(is_error))
+    }
+}
+
+/// Tokio task which forwards the menu actions of a [TrayInterface] to the client
+#[derive(Copy, Clone, Debug)]
+pub struct TrayInputTask {}
+
+impl TrayInputTask {
+    /// Spawns the task, which sends every action received from the tray to the client
+    pub async fn spawn(
+        mut action_rx: UnboundedReceiver<ClientInputAction>,
+        input_worker_tx: UnboundedSender<ClientInputAction>,
+    ) -> JoinHandle<()> {
+        spawn(async move {
+            while let Some(action) = action_rx.recv().await {
+                if input_worker_tx.send(action).is_err() {
+                    break;
+                }
+            }
+
+            // The connection task shuts down the client
+            futures::future::pending().await
+        })
+    }
+}
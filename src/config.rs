@@ -1,11 +1,18 @@
 //! Code related to the runtime configuration of zentime
 
+use crate::client::terminal_io::keymap::Keymap;
+use crate::ipc::get_socket_name;
+use chrono::{NaiveTime, Weekday};
+use log::LevelFilter;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
 use zentime_rs_timer::config::PomodoroTimerConfig;
 
 use figment::{
-    providers::{Format, Serialized, Toml},
+    providers::{Env, Format, Serialized, Toml},
     Figment,
 };
 
@@ -20,29 +27,241 @@ pub struct NotificationConfig {
     /// Will default to a bell sound, if `None`
     pub sound_file: Option<String>,
 
+    /// Soundfile to be played back when a focus interval ends and a short break starts.
+    /// Falls back to `sound_file`, if `None`
+    pub focus_end_sound: Option<String>,
+
+    /// Soundfile to be played back when a break ends.
+    /// Falls back to `sound_file`, if `None`
+    pub break_end_sound: Option<String>,
+
+    /// Soundfile to be played back when a focus interval ends and a long break starts.
+    /// Falls back to `sound_file`, if `None`
+    pub long_break_sound: Option<String>,
+
+    /// Soundfile (e.g. white noise), which is looped while a focus interval is running.
+    /// Requires the `audio` feature.
+    pub ambient_sound: Option<String>,
+
     /// Notification bell volume
     pub volume: f32,
 
+    /// Duration in milliseconds over which the bell fades in to its volume.
+    /// A value of 0 disables fading.
+    pub fade_in_ms: u64,
+
+    /// Number of times the bell is played back on each notification
+    pub bell_repeat: u8,
+
+    /// Silence in milliseconds between two repetitions of the bell
+    pub bell_repeat_gap_ms: u64,
+
+    /// Handling of a bell, which is started while the previous one is still playing
+    /// (e.g. because intervals have been skipped rapidly)
+    pub sound_overlap: SoundOverlap,
+
+    /// Notifications (bell and OS-notification) are suppressed, if the previous notification of
+    /// the same [NotificationEvent] has been dispatched less than the given number of
+    /// milliseconds ago.
+    /// A value of 0 disables debouncing.
+    pub notification_debounce_ms: u64,
+
     /// Show OS-notification
     pub show_notification: bool,
 
+    /// Summary (title) of OS-notifications.
+    /// Will default to "◯ zentime", if `None`
+    pub notification_summary: Option<String>,
+
+    /// Icon of OS-notifications, either a path to an image or a freedesktop icon name
+    /// (e.g. "appointment-soon"). No icon is shown, if `None`
+    pub notification_icon: Option<String>,
+
+    /// Urgency of all OS-notifications. If `None`, notifications which start a break are
+    /// critical (so that they persist until dismissed) and all others are normal.
+    /// Only supported by XDG notification servers.
+    pub notification_urgency: Option<NotificationUrgency>,
+
+    /// Duration in milliseconds after which OS-notifications are closed.
+    /// A value of 0 keeps them open until they are dismissed.
+    /// Falls back to the default of the notification server, if `None`
+    pub notification_timeout_ms: Option<u32>,
+
     /// A random suggestion will be picked on each break and shown inside the
     /// notification text.
     pub break_suggestions: Option<Vec<String>>,
+
+    /// Overrides of `enable_bell` and `show_notification` for the end of focus intervals
+    /// (followed by a short break)
+    pub focus_end: EventNotificationConfig,
+
+    /// Overrides of `enable_bell` and `show_notification` for the end of focus intervals
+    /// (followed by a long break)
+    pub long_break: EventNotificationConfig,
+
+    /// Overrides of `enable_bell` and `show_notification` for the end of breaks
+    pub break_end: EventNotificationConfig,
+
+    /// URL to which a JSON body (`event`, `round` and `label`) is posted on every notification
+    /// (e.g. `http://homeassistant.local:8123/api/webhook/zentime`), even while notifications are
+    /// muted or debounced.
+    /// Only available if zentime has been built with the `http` feature.
+    pub webhook_url: Option<String>,
+}
+
+/// Notification toggles of a single [NotificationEvent].
+/// Unset toggles fall back to the global toggles of the [NotificationConfig].
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default)]
+pub struct EventNotificationConfig {
+    /// Enable/Disable bell
+    pub bell: Option<bool>,
+
+    /// Show OS-notification
+    pub notification: Option<bool>,
+
+    /// Urgency of the OS-notification (falls back to `notification_urgency`)
+    pub urgency: Option<NotificationUrgency>,
+}
+
+impl NotificationConfig {
+    /// Returns the toggles configured for the given event
+    fn event_config(&self, event: NotificationEvent) -> Option<&EventNotificationConfig> {
+        match event {
+            NotificationEvent::FocusEnd => Some(&self.focus_end),
+            NotificationEvent::LongBreak => Some(&self.long_break),
+            NotificationEvent::BreakEnd => Some(&self.break_end),
+            NotificationEvent::GoalReached
+            | NotificationEvent::ScheduledStart
+            | NotificationEvent::OneShotEnd => None,
+        }
+    }
+
+    /// Denotes if the bell should be played on the given event (falls back to `enable_bell`)
+    pub fn bell_for(&self, event: NotificationEvent) -> bool {
+        self.event_config(event)
+            .and_then(|event_config| event_config.bell)
+            .unwrap_or(self.enable_bell)
+    }
+
+    /// Denotes if an OS-notification should be shown on the given event
+    /// (falls back to `show_notification`)
+    pub fn notification_for(&self, event: NotificationEvent) -> bool {
+        self.event_config(event)
+            .and_then(|event_config| event_config.notification)
+            .unwrap_or(self.show_notification)
+    }
+
+    /// Returns the urgency of the OS-notification of the given event.
+    /// Falls back to `notification_urgency` and then to a default, which makes notifications
+    /// starting a break critical.
+    pub fn urgency_for(&self, event: NotificationEvent) -> NotificationUrgency {
+        self.event_config(event)
+            .and_then(|event_config| event_config.urgency)
+            .or(self.notification_urgency)
+            .unwrap_or(match event {
+                NotificationEvent::FocusEnd | NotificationEvent::LongBreak => {
+                    NotificationUrgency::Critical
+                }
+                NotificationEvent::BreakEnd
+                | NotificationEvent::GoalReached
+                | NotificationEvent::ScheduledStart
+                | NotificationEvent::OneShotEnd => NotificationUrgency::Normal,
+            })
+    }
+
+    /// Returns the soundfile configured for the given event or falls back to `sound_file`
+    pub fn sound_for(&self, event: NotificationEvent) -> Option<String> {
+        let event_sound = match event {
+            NotificationEvent::FocusEnd => &self.focus_end_sound,
+            NotificationEvent::LongBreak => &self.long_break_sound,
+            NotificationEvent::BreakEnd => &self.break_end_sound,
+            NotificationEvent::GoalReached
+            | NotificationEvent::ScheduledStart
+            | NotificationEvent::OneShotEnd => &None,
+        };
+
+        event_sound.clone().or_else(|| self.sound_file.clone())
+    }
+}
+
+/// Events on which the server dispatches a notification
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// A focus interval has ended and a short break begins
+    FocusEnd,
+
+    /// A focus interval has ended and a long break begins
+    LongBreak,
+
+    /// A break has ended
+    BreakEnd,
+
+    /// The daily goal of focus intervals has been reached
+    GoalReached,
+
+    /// A focus session has been started by the [ScheduleConfig]
+    ScheduledStart,
+
+    /// A single focus session (see `zentime focus`) has ended, which is not followed by a break
+    OneShotEnd,
 }
 
 impl Default for NotificationConfig {
     fn default() -> Self {
         NotificationConfig {
             volume: 0.5,
+            fade_in_ms: 0,
+            bell_repeat: 1,
+            bell_repeat_gap_ms: 500,
+            sound_overlap: SoundOverlap::default(),
+            notification_debounce_ms: 0,
             sound_file: None,
+            focus_end_sound: None,
+            break_end_sound: None,
+            long_break_sound: None,
+            ambient_sound: None,
             enable_bell: true,
             show_notification: true,
+            notification_summary: None,
+            notification_icon: None,
+            notification_urgency: None,
+            notification_timeout_ms: None,
             break_suggestions: None,
+            focus_end: EventNotificationConfig::default(),
+            long_break: EventNotificationConfig::default(),
+            break_end: EventNotificationConfig::default(),
+            webhook_url: None,
         }
     }
 }
 
+/// Handling of a sound, which is started while another one is still playing
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SoundOverlap {
+    /// Stops the current sound in favour of the new one
+    #[default]
+    Replace,
+
+    /// Plays the new sound once the current one has ended
+    Queue,
+}
+
+/// Urgency of an OS-notification
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationUrgency {
+    /// Might be shown less prominently, depending on the notification server
+    Low,
+
+    /// Regular notification
+    Normal,
+
+    /// Persists until it has been dismissed
+    Critical,
+}
+
 /// Configuration of the interface
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct ViewConfig {
@@ -52,6 +271,34 @@ pub struct ViewConfig {
     /// Suppresses the output of one-shot commands
     /// (e.g. `zentime skip` or `zentime toggle-timer`)
     pub silent: bool,
+
+    /// Number of attempts a client makes to reconnect to the server after the connection
+    /// has been lost (e.g. because the server has been restarted).
+    /// A value of 0 disables reconnecting, so that the client quits immediately.
+    pub reconnect_attempts: u32,
+
+    /// Shows the elapsed time of the current session and the time until the next long break
+    /// inside the default interface
+    pub show_session_times: bool,
+
+    /// Renders the default interface inside the alternate screen, so that the terminal content
+    /// (and its scrollback) is restored once the client quits. If disabled, the interface is
+    /// rendered in-place inside the main buffer.
+    pub use_alternate_screen: bool,
+
+    /// Renders a short progress bar of the current timer inside the minimal interface
+    pub show_progress_bar: bool,
+
+    /// Colors the minimal interface.
+    /// If unset, colors are only used if stdout is a terminal and `NO_COLOR` is not set.
+    pub use_colors: Option<bool>,
+
+    /// Requires skipping a focus interval inside an interactive client to be confirmed by
+    /// pressing `s` a second time. Breaks are always skipped right away.
+    pub confirm_skip: bool,
+
+    /// Symbols of the tmux interface
+    pub symbols: SymbolConfig,
 }
 
 impl Default for ViewConfig {
@@ -59,13 +306,147 @@ impl Default for ViewConfig {
         Self {
             interface: "default".to_string(),
             silent: false,
+            reconnect_attempts: 5,
+            show_session_times: false,
+            use_alternate_screen: true,
+            show_progress_bar: false,
+            use_colors: None,
+            confirm_skip: false,
+            symbols: SymbolConfig::default(),
         }
     }
 }
 
+impl ViewConfig {
+    /// Returns the configured `use_colors` or detects, if colors are supported
+    /// (see <https://no-color.org>)
+    pub fn use_colors(&self) -> bool {
+        self.use_colors.unwrap_or_else(|| {
+            let is_no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+            !is_no_color && std::io::stdout().is_terminal()
+        })
+    }
+}
+
+/// Symbols which are prefixed to the timer inside the tmux interface
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct SymbolConfig {
+    /// Shown during focus intervals (and postponed breaks)
+    pub focus: String,
+
+    /// Shown during breaks
+    #[serde(rename = "break")]
+    pub break_symbol: String,
+
+    /// Shown while the timer is paused
+    pub paused: String,
+}
+
+impl Default for SymbolConfig {
+    fn default() -> Self {
+        Self {
+            focus: "🍅".to_string(),
+            break_symbol: "☕".to_string(),
+            paused: "⏸".to_string(),
+        }
+    }
+}
+
+/// Seconds after which a paused timer backs off (see [ServerConfig::paused_backoff_secs])
+const DEFAULT_PAUSED_BACKOFF_SECS: u64 = 10;
+
+/// Seconds between two unchanged view states of a paused timer, which has backed off
+const DEFAULT_PAUSED_UPDATE_INTERVAL_SECS: u64 = 5;
+
+/// Configuration of the zentime server process
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+pub struct ServerConfig {
+    /// Path (or namespaced name) of the socket over which server and clients communicate.
+    /// Will default to the zentime socket (see [get_socket_name]), if `None`.
+    pub socket_path: Option<String>,
+
+    /// Port of the HTTP status endpoint (`GET /status`) on localhost.
+    /// Only available if zentime has been built with the `http` feature.
+    pub http_port: Option<u16>,
+
+    /// Log level of the server daemon (error, warn, info, debug or trace).
+    /// Defaults to `warn`. Note that `RUST_LOG` still takes precedence.
+    pub log_level: Option<String>,
+
+    /// Number of focus intervals which should be completed per day.
+    /// If set, the progress is shown inside the default interface and a notification is sent once
+    /// the goal has been reached.
+    /// If a `stats_file` is configured, the progress survives server restarts, because the focus
+    /// intervals completed today are read from it on startup.
+    pub daily_goal: Option<u64>,
+
+    /// Automatically pauses a running focus interval, if no client has sent any input (e.g.
+    /// play/pause or skip) for the given number of seconds. The timer resumes on the next input.
+    /// Breaks are never paused.
+    pub pause_on_idle_secs: Option<u64>,
+
+    /// Seconds after which a paused timer only sends an unchanged view state every
+    /// `paused_update_interval_secs`, which reduces the traffic during long pauses.
+    /// Defaults to 10 and 0 disables the backoff.
+    pub paused_backoff_secs: Option<u64>,
+
+    /// Seconds between two unchanged view states of a timer, which has been paused for longer
+    /// than `paused_backoff_secs`. Defaults to 5.
+    pub paused_update_interval_secs: Option<u64>,
+
+    /// Starts counting down the first focus interval as soon as the server has been started,
+    /// instead of waiting for the first play/pause
+    #[serde(default)]
+    pub start_running: bool,
+
+    /// File to which every completed timer is appended as a line of JSON (e.g.
+    /// `~/.local/share/zentime/stats.jsonl`). Nothing is recorded, if `None`.
+    /// The records can be exported via `zentime export`.
+    pub stats_file: Option<String>,
+}
+
+impl ServerConfig {
+    /// Returns the configured socket name or falls back to the default zentime socket name
+    pub fn socket_name(&self) -> String {
+        match &self.socket_path {
+            Some(path) => shellexpand::tilde(path.trim()).to_string(),
+            None => get_socket_name(),
+        }
+    }
+
+    /// Returns the expanded path of the configured stats file (if any)
+    pub fn stats_file(&self) -> Option<PathBuf> {
+        self.stats_file
+            .as_ref()
+            .map(|path| PathBuf::from(shellexpand::tilde(path.trim()).as_ref()))
+    }
+
+    /// Returns the configured log level or falls back to `warn`
+    pub fn log_level(&self) -> &str {
+        self.log_level.as_deref().unwrap_or("warn")
+    }
+
+    /// Returns the configured backoff of paused timers as `(after, interval)` or falls back to
+    /// its defaults. Returns `None`, if the backoff has been disabled.
+    pub fn paused_backoff(&self) -> Option<(Duration, Duration)> {
+        let after = self
+            .paused_backoff_secs
+            .unwrap_or(DEFAULT_PAUSED_BACKOFF_SECS);
+        let interval = self
+            .paused_update_interval_secs
+            .unwrap_or(DEFAULT_PAUSED_UPDATE_INTERVAL_SECS);
+
+        (after > 0).then(|| (Duration::from_secs(after), Duration::from_secs(interval)))
+    }
+}
+
 /// Zentime configuration
 #[derive(Deserialize, Serialize, Clone, Default, Debug)]
 pub struct Config {
+    /// Server configuration
+    pub server: ServerConfig,
+
     /// Interface configuration
     pub view: ViewConfig,
 
@@ -74,13 +455,519 @@ pub struct Config {
 
     /// Configuration for OS notifications
     pub notifications: NotificationConfig,
+
+    /// Colors of the default interface
+    pub theme: ThemeConfig,
+
+    /// Times at which the server starts a focus session on its own
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+
+    /// Keys of the terminal interfaces
+    #[serde(default)]
+    pub keybindings: KeyBindingConfig,
+}
+
+/// Keys which trigger each action inside the terminal interfaces.
+/// Keys are given like `"q"`, `"S"` (shifted), `"space"`, `"left"`, `"f5"` or `"ctrl-c"` and
+/// every key may only be bound to a single action.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(default)]
+pub struct KeyBindingConfig {
+    /// Quit the timer and terminate the server
+    pub quit: Vec<String>,
+
+    /// Detach the client without terminating the server
+    pub detach: Vec<String>,
+
+    /// Start or pause the current timer
+    pub play_pause: Vec<String>,
+
+    /// Skip to the next timer
+    pub skip: Vec<String>,
+
+    /// Reset the timer back to the first interval
+    pub reset: Vec<String>,
+
+    /// Move back to the previous timer
+    pub previous: Vec<String>,
+
+    /// Postpone the current break
+    pub postpone: Vec<String>,
+
+    /// Log a distraction during the current focus interval
+    pub distraction: Vec<String>,
+
+    /// Type a label for the current timer (confirmed with Enter, cancelled with Esc)
+    pub label: Vec<String>,
+
+    /// Toggle the zen display of the default interface, which only shows the time
+    pub zen: Vec<String>,
+}
+
+impl Default for KeyBindingConfig {
+    fn default() -> Self {
+        let keys = |keys: &[&str]| keys.iter().map(|key| key.to_string()).collect();
+
+        Self {
+            quit: keys(&["q"]),
+            // WHY:
+            // Ctrl-C is commonly expected to only leave the current program, which is why it
+            // keeps the server (and therefore the timer) running.
+            detach: keys(&["d", "ctrl-c"]),
+            play_pause: keys(&["space"]),
+            skip: keys(&["s"]),
+            reset: keys(&["r"]),
+            previous: keys(&["b"]),
+            postpone: keys(&["p"]),
+            distraction: keys(&["x"]),
+            label: keys(&["l"]),
+            zen: keys(&["z"]),
+        }
+    }
+}
+
+/// Times at which the server resets the timer and starts a new focus session on its own, e.g.
+/// every weekday at 09:00. Manual control is still possible at any time.
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+pub struct ScheduleConfig {
+    /// Scheduled starts of a focus session (`[[schedule.entries]]`)
+    #[serde(default)]
+    pub entries: Vec<ScheduleEntry>,
+}
+
+/// A single scheduled start of a focus session
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ScheduleEntry {
+    /// Local time of day as `HH:MM`, e.g. `"09:00"`
+    pub time: String,
+
+    /// Days on which the session is started, e.g. `["mon", "tue"]` (or full names like `"monday"`).
+    /// The session is started every day, if this is empty.
+    #[serde(default)]
+    pub weekdays: Vec<String>,
+}
+
+impl ScheduleEntry {
+    /// Returns the configured time of day or `None`, if it is not a valid `HH:MM` time
+    pub fn time_of_day(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(self.time.trim(), "%H:%M").ok()
+    }
+
+    /// Returns the configured weekdays or the first name which is not a valid weekday
+    pub fn days(&self) -> Result<Vec<Weekday>, String> {
+        self.weekdays
+            .iter()
+            .map(|day| day.trim().parse::<Weekday>().map_err(|_| day.clone()))
+            .collect()
+    }
+}
+
+/// Colors of the default interface.
+/// Each color may be a color name (e.g. "blue", "light-red" or "dark-gray") or a hex string
+/// (e.g. "#5f87ff"). Unset or unknown colors fall back to the colors of the built-in theme.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ThemeConfig {
+    /// Built-in theme which is used as base ("default" or "monochrome")
+    pub name: String,
+
+    /// Color of the focus label
+    pub focus: Option<String>,
+
+    /// Color of the break label
+    #[serde(rename = "break")]
+    pub break_color: Option<String>,
+
+    /// Color of the postponed label
+    pub postponed: Option<String>,
+
+    /// Color of the timer
+    pub timer: Option<String>,
+
+    /// Color of the keybinding bar
+    pub keybindings: Option<String>,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            focus: None,
+            break_color: None,
+            postponed: None,
+            timer: None,
+            keybindings: None,
+        }
+    }
+}
+
+/// A single problem found during [Config::validate]
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ConfigProblem {
+    /// At least a single interval is required before a major break
+    #[error("timers.intervals has to be at least 1 (got {0})")]
+    IntervalsTooLow(u64),
+
+    /// A session without any rounds would be complete right away
+    #[error("timers.max_rounds has to be at least 1 (got 0)")]
+    MaxRoundsTooLow,
+
+    /// The focus timer has to run for at least a second
+    #[error("timers.timer has to be at least 1 second (got {0})")]
+    TimerTooShort(u64),
+
+    /// Breaks can't be computed from a negative (or invalid) ratio
+    #[error("timers.break_ratio has to be greater than 0.0 (got {0})")]
+    BreakRatioTooLow(f32),
+
+    /// The bell volume is outside of the supported range
+    #[error("notifications.volume has to be between 0.0 and 1.0 (got {0})")]
+    VolumeOutOfRange(f32),
+
+    /// The bell has to be played back at least once (use `enable_bell` to disable it instead)
+    #[error("notifications.bell_repeat has to be at least 1 (got 0)")]
+    BellRepeatTooLow,
+
+    /// Webhooks can only be sent via HTTP(S)
+    #[error("notifications.webhook_url has to start with http:// or https:// (got {0})")]
+    UnsupportedWebhookUrl(String),
+
+    /// A configured sound file could not be found (config key, path)
+    #[error("notifications.{0} does not exist: {1}")]
+    SoundFileNotFound(&'static str, String),
+
+    /// A daily goal of zero intervals can't be reached
+    #[error("server.daily_goal has to be at least 1 (got 0)")]
+    DailyGoalTooLow,
+
+    /// Pausing right away would make it impossible to run a focus interval
+    #[error("server.pause_on_idle_secs has to be at least 1 (got 0)")]
+    IdleTimeoutTooLow,
+
+    /// Unchanged view states of paused timers have to be sent eventually
+    #[error("server.paused_update_interval_secs has to be at least 1 (got 0)")]
+    PausedUpdateIntervalTooLow,
+
+    /// The configured log level is not a known level
+    #[error("server.log_level has to be one of error, warn, info, debug or trace (got {0})")]
+    InvalidLogLevel(String),
+
+    /// A scheduled start does not have a valid time of day
+    #[error("schedule.entries.time has to be a time like \"09:00\" (got {0})")]
+    InvalidScheduleTime(String),
+
+    /// A scheduled start contains an unknown weekday
+    #[error("schedule.entries.weekdays has to contain weekdays like \"mon\" (got {0})")]
+    InvalidScheduleWeekday(String),
+
+    /// A keybinding contains an unknown key (action, key)
+    #[error("keybindings.{0} contains an unknown key (got {1})")]
+    UnknownKey(&'static str, String),
+
+    /// A key is bound to more than one action (key, first action, second action)
+    #[error("keybindings: \"{0}\" is bound to both {1} and {2}")]
+    ConflictingKeyBinding(String, &'static str, &'static str),
+}
+
+/// Error returned by [Config::validate], listing all problems of a configuration
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Invalid configuration:\n{}", format_problems(.0))]
+pub struct InvalidConfigError(pub Vec<ConfigProblem>);
+
+fn format_problems(problems: &[ConfigProblem]) -> String {
+    problems
+        .iter()
+        .map(|problem| format!("  - {}", problem))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+impl Config {
+    /// Returns the keys of all settings, which differ between `self` (the config the server has
+    /// been started with) and the `reloaded` config, but are only read on startup and therefore
+    /// require a restart of the server to apply.
+    pub fn restart_required_changes(&self, reloaded: &Config) -> Vec<&'static str> {
+        fn differs<T: Serialize>(current: &T, reloaded: &T) -> bool {
+            serde_json::to_value(current).ok() != serde_json::to_value(reloaded).ok()
+        }
+
+        let (server, reloaded_server) = (&self.server, &reloaded.server);
+        let (notifications, reloaded_notifications) =
+            (&self.notifications, &reloaded.notifications);
+
+        [
+            (
+                "server.socket_path",
+                differs(&server.socket_path, &reloaded_server.socket_path),
+            ),
+            (
+                "server.http_port",
+                differs(&server.http_port, &reloaded_server.http_port),
+            ),
+            (
+                "server.log_level",
+                differs(&server.log_level, &reloaded_server.log_level),
+            ),
+            (
+                "server.paused_backoff_secs",
+                differs(
+                    &server.paused_backoff_secs,
+                    &reloaded_server.paused_backoff_secs,
+                ),
+            ),
+            (
+                "server.paused_update_interval_secs",
+                differs(
+                    &server.paused_update_interval_secs,
+                    &reloaded_server.paused_update_interval_secs,
+                ),
+            ),
+            (
+                "server.start_running",
+                differs(&server.start_running, &reloaded_server.start_running),
+            ),
+            (
+                "server.stats_file",
+                differs(&server.stats_file, &reloaded_server.stats_file),
+            ),
+            (
+                "notifications.sound_overlap",
+                differs(
+                    &notifications.sound_overlap,
+                    &reloaded_notifications.sound_overlap,
+                ),
+            ),
+            (
+                "notifications.ambient_sound",
+                differs(
+                    &notifications.ambient_sound,
+                    &reloaded_notifications.ambient_sound,
+                ),
+            ),
+            ("schedule", differs(&self.schedule, &reloaded.schedule)),
+        ]
+        .into_iter()
+        .filter_map(|(key, changed)| changed.then_some(key))
+        .collect()
+    }
+
+    /// Checks the configuration for values which would lead to errors at runtime (e.g. a division
+    /// by zero for `intervals = 0`) and returns all problems at once.
+    pub fn validate(&self) -> Result<(), InvalidConfigError> {
+        let mut problems = vec![];
+
+        if self.timers.intervals < 1 {
+            problems.push(ConfigProblem::IntervalsTooLow(self.timers.intervals));
+        }
+
+        if self.timers.max_rounds == Some(0) {
+            problems.push(ConfigProblem::MaxRoundsTooLow);
+        }
+
+        if self.timers.timer < 1 {
+            problems.push(ConfigProblem::TimerTooShort(self.timers.timer));
+        }
+
+        if let Some(break_ratio) = self
+            .timers
+            .break_ratio
+            .filter(|ratio| !ratio.is_finite() || *ratio <= 0.0)
+        {
+            problems.push(ConfigProblem::BreakRatioTooLow(break_ratio));
+        }
+
+        if !(0.0..=1.0).contains(&self.notifications.volume) {
+            problems.push(ConfigProblem::VolumeOutOfRange(self.notifications.volume));
+        }
+
+        if self.notifications.bell_repeat < 1 {
+            problems.push(ConfigProblem::BellRepeatTooLow);
+        }
+
+        if let Some(webhook_url) = &self.notifications.webhook_url {
+            if !webhook_url.starts_with("http://") && !webhook_url.starts_with("https://") {
+                problems.push(ConfigProblem::UnsupportedWebhookUrl(webhook_url.clone()));
+            }
+        }
+
+        let sound_files = [
+            ("sound_file", &self.notifications.sound_file),
+            ("focus_end_sound", &self.notifications.focus_end_sound),
+            ("break_end_sound", &self.notifications.break_end_sound),
+            ("long_break_sound", &self.notifications.long_break_sound),
+            ("ambient_sound", &self.notifications.ambient_sound),
+        ];
+
+        for (key, sound_file) in sound_files {
+            if let Some(sound_file) = sound_file {
+                if !Path::new(sound_file).exists() {
+                    problems.push(ConfigProblem::SoundFileNotFound(
+                        key,
+                        sound_file.to_string(),
+                    ));
+                }
+            }
+        }
+
+        if self.server.pause_on_idle_secs == Some(0) {
+            problems.push(ConfigProblem::IdleTimeoutTooLow);
+        }
+
+        if self.server.paused_update_interval_secs == Some(0) {
+            problems.push(ConfigProblem::PausedUpdateIntervalTooLow);
+        }
+
+        if self.server.daily_goal == Some(0) {
+            problems.push(ConfigProblem::DailyGoalTooLow);
+        }
+
+        if self.server.log_level().parse::<LevelFilter>().is_err() {
+            problems.push(ConfigProblem::InvalidLogLevel(
+                self.server.log_level().to_string(),
+            ));
+        }
+
+        for entry in &self.schedule.entries {
+            if entry.time_of_day().is_none() {
+                problems.push(ConfigProblem::InvalidScheduleTime(entry.time.clone()));
+            }
+
+            if let Err(day) = entry.days() {
+                problems.push(ConfigProblem::InvalidScheduleWeekday(day));
+            }
+        }
+
+        if let Err(keymap_problems) = Keymap::new(&self.keybindings) {
+            problems.extend(keymap_problems);
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidConfigError(problems))
+        }
+    }
+}
+
+/// Location of the global configuration file, which is used unless another file has been passed
+/// explicitly (see [create_base_config])
+pub const DEFAULT_CONFIG_PATH: &str = "~/.config/zentime/zentime.toml";
+
+/// Name of project local configuration files (see [find_local_config])
+pub const LOCAL_CONFIG_FILE_NAME: &str = ".zentime.toml";
+
+/// Prefix of environment variables which override configuration values (see [create_base_config])
+pub const ENV_PREFIX: &str = "ZENTIME_";
+
+/// Key of the table containing all named configuration profiles (e.g. `[profiles.work]`)
+pub const PROFILES_KEY: &str = "profiles";
+
+/// Error returned by [create_base_config], if the selected profile has not been configured
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Unknown profile \"{name}\" (available profiles: {})", format_profile_names(.available))]
+pub struct UnknownProfileError {
+    /// Name of the selected profile
+    pub name: String,
+
+    /// Names of all configured profiles
+    pub available: Vec<String>,
+}
+
+fn format_profile_names(names: &[String]) -> String {
+    if names.is_empty() {
+        return "none".to_string();
+    }
+
+    names.join(", ")
 }
 
 /// Creates a base configuration [Figment] by trying to open a configuration file
-/// from a given path and merging its configuration with the zentime default configuration.
-pub fn create_base_config(config_path: &str) -> Figment {
-    let mut path_buffer = PathBuf::new();
-    path_buffer.push(shellexpand::tilde(config_path.trim()).as_ref());
+/// and merging its configuration with the zentime default configuration.
+///
+/// If `use_local_config` is set, a project local `.zentime.toml` (see [find_local_config]) is
+/// merged as well. It overrides the global configuration file at [DEFAULT_CONFIG_PATH], but is
+/// itself overridden by an explicitly given `config_path`.
+///
+/// If a `profile` is given, its table (e.g. `[profiles.work]`) is merged over all files.
+/// An [UnknownProfileError] is returned, if no such profile has been configured.
+///
+/// Environment variables prefixed with [ENV_PREFIX] are merged last and therefore override
+/// all files. The first `_` after the prefix separates the table from the key,
+/// e.g. `ZENTIME_TIMERS_TIMER=1500` or `ZENTIME_NOTIFICATIONS_SOUND_FILE=/path/to/sound.mp3`.
+pub fn create_base_config(
+    config_path: Option<&Path>,
+    use_local_config: bool,
+    profile: Option<&str>,
+) -> Result<Figment, UnknownProfileError> {
+    let mut figment = Figment::from(Serialized::defaults(Config::default()));
+    let local_config_path = use_local_config.then(find_local_config).flatten();
+
+    // WHY:
+    // A project local config should win over the global config, but an explicitly passed config
+    // is the most specific choice of the user.
+    match config_path {
+        Some(config_path) => {
+            if let Some(local_config_path) = local_config_path {
+                figment = figment.merge(Toml::file(local_config_path));
+            }
+
+            figment = figment.merge(Toml::file(config_file_path(Some(config_path))));
+        }
+        None => {
+            figment = figment.merge(Toml::file(config_file_path(None)));
+
+            if let Some(local_config_path) = local_config_path {
+                figment = figment.merge(Toml::file(local_config_path));
+            }
+        }
+    }
+
+    if let Some(profile) = profile {
+        figment = merge_profile(figment, profile)?;
+    }
+
+    Ok(figment.merge(
+        // WHY:
+        // Keys like `sound_file` contain underscores themselves, which is why only the first
+        // underscore denotes nesting.
+        Env::prefixed(ENV_PREFIX).map(|key| key.as_str().replacen('_', ".", 1).into()),
+    ))
+}
+
+/// Returns the (tilde expanded) path of the given configuration file or falls back to
+/// [DEFAULT_CONFIG_PATH]
+pub fn config_file_path(config_path: Option<&Path>) -> PathBuf {
+    let config_path = config_path
+        .map(|path| path.to_string_lossy())
+        .unwrap_or(DEFAULT_CONFIG_PATH.into());
+
+    PathBuf::from(shellexpand::tilde(config_path.trim()).as_ref())
+}
+
+/// Merges the table of the given profile over the configuration
+fn merge_profile(figment: Figment, profile: &str) -> Result<Figment, UnknownProfileError> {
+    let profiles = figment
+        .find_value(PROFILES_KEY)
+        .ok()
+        .and_then(|value| value.into_dict())
+        .unwrap_or_default();
+
+    match profiles.get(profile) {
+        Some(values) => Ok(figment.merge(Serialized::defaults(values.clone()))),
+        None => Err(UnknownProfileError {
+            name: profile.to_string(),
+            available: profiles.into_keys().collect(),
+        }),
+    }
+}
+
+/// Walks up from the current working directory and returns the path of the first
+/// `.zentime.toml` found (if any).
+pub fn find_local_config() -> Option<PathBuf> {
+    let current_dir = std::env::current_dir().ok()?;
 
-    Figment::from(Serialized::defaults(Config::default())).merge(Toml::file(path_buffer))
+    current_dir
+        .ancestors()
+        .map(|dir| dir.join(LOCAL_CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
 }
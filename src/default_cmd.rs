@@ -1,61 +1,138 @@
-use std::env::current_dir;
 use std::process;
-use sysinfo::Pid;
 use zentime_rs::client::start;
+use zentime_rs::client::start::observe;
 use zentime_rs::config::Config;
 use zentime_rs::server::status::server_status;
 use zentime_rs::server::status::ServerStatus;
 
-use sysinfo::ProcessExt;
-use sysinfo::System;
-use sysinfo::SystemExt;
-use tokio::process::Command;
-
+use crate::subcommands::server::spawn_server;
 use crate::CommonArgs;
 
-#[tokio::main]
-pub async fn default_cmd(common_args: &CommonArgs, config: Config) {
-    let system = System::new_all();
+/// Starts the server (if necessary) and attaches a client to it.
+///
+/// NOTE: The server is spawned before entering the async context, because spawning it blocks
+/// until the daemonization has finished.
+pub fn default_cmd(common_args: &CommonArgs, config: Config, attach_only: bool, readonly: bool) {
+    let is_server_stopped = server_status(&config.server.socket_name()) == ServerStatus::Stopped;
+
+    if is_server_stopped && attach_only {
+        eprintln!("No zentime server is running - not attaching because of --attach-only");
+        process::exit(1);
+    }
 
     // We need to spawn a server process before we can attach our client
-    if server_status() == ServerStatus::Stopped {
-        // WHY:
-        // We want to get information about the current zentime process, e.g.
-        // the path to its executable. That way this does also work in ci or during
-        // development, where one might not have added a specific zentime binary to their path.
-        let current_process = system
-            .process(Pid::from(process::id() as i32))
-            .expect("Could not retrieve information for current zentime process");
-
-        let current_dir = current_dir()
-            .expect("Could not get current directory")
-            .into_os_string();
-
-        let server_args = get_server_args(common_args);
-
-        if let Err(error) = Command::new(current_process.exe())
-            .arg("server")
-            .arg("start")
-            .args(server_args)
-            .current_dir(current_dir)
-            .spawn()
-            .expect("Could not start server daemon")
-            .wait()
-            .await
-        {
-            panic!("Server exited unexpectedly: {}", error)
-        };
-    }
-
-    start(config).await;
+    if is_server_stopped {
+        spawn_server(common_args, &config, &[]);
+    } else {
+        warn_about_ignored_session_flags(common_args);
+    }
+
+    attach(config, readonly);
+}
+
+#[tokio::main]
+async fn attach(config: Config, readonly: bool) {
+    if readonly {
+        observe(config).await;
+    } else {
+        start(config).await;
+    }
 }
 
-fn get_server_args(common_args: &CommonArgs) -> Vec<String> {
-    let mut args: Vec<String> = vec![
-        // Config path
-        "-c".to_string(),
-        common_args.config.to_string(),
-    ];
+/// Warns that `--no-bell` and `--no-notification` have no effect, because they only apply to a
+/// newly spawned server (see [get_server_args]).
+pub fn warn_about_ignored_session_flags(common_args: &CommonArgs) {
+    let flags: Vec<&str> = [
+        (common_args.no_bell, "--no-bell"),
+        (common_args.no_notification, "--no-notification"),
+    ]
+    .into_iter()
+    .filter_map(|(is_set, flag)| is_set.then_some(flag))
+    .collect();
+
+    if !flags.is_empty() {
+        eprintln!(
+            "Ignoring {}, because a zentime server is already running (use `zentime mute` to silence it)",
+            flags.join(" and ")
+        );
+    }
+}
+
+/// Converts the given [CommonArgs] into arguments for a `zentime server start` call
+pub fn get_server_args(common_args: &CommonArgs, config: &Config) -> Vec<String> {
+    let mut args: Vec<String> = vec![];
+
+    // NOTE:
+    // The config path is only forwarded if it has been passed explicitly, because it would
+    // otherwise take precedence over a project local config.
+    if let Some(config_path) = &common_args.config {
+        args.push("-c".to_string());
+        args.push(config_path.to_string_lossy().into_owned());
+    }
+
+    if let Some(profile) = &common_args.profile {
+        args.push("--profile".to_string());
+        args.push(profile.to_string());
+    }
+
+    if common_args.no_local_config {
+        args.push("--no-local-config".to_string());
+    }
+
+    // WHY:
+    // We always pass the resolved socket explicitly (even if it has been configured inside the
+    // config file), so that the server process can be identified by its socket (see [server_status]).
+    // This is the only value taken from the resolved config - a running server can't move to
+    // another socket anyway.
+    args.push("--socket".to_string());
+    args.push(config.server.socket_name());
+
+    // NOTE:
+    // All other values are only forwarded if they have been passed on the command line, because
+    // the server reads its config file again on reload and flags would take precedence over it.
+    if let Some(log_level) = &common_args.log_level {
+        args.push("--log-level".to_string());
+        args.push(log_level.to_string());
+    }
+
+    if let Some(daily_goal) = &common_args.daily_goal {
+        args.push("--daily-goal".to_string());
+        args.push(daily_goal.to_string());
+    }
+
+    if let Some(pause_on_idle_secs) = &common_args.pause_on_idle_secs {
+        args.push("--pause-on-idle-secs".to_string());
+        args.push(pause_on_idle_secs.to_string());
+    }
+
+    if let Some(paused_backoff_secs) = &common_args.paused_backoff_secs {
+        args.push("--paused-backoff-secs".to_string());
+        args.push(paused_backoff_secs.to_string());
+    }
+
+    if let Some(paused_update_interval_secs) = &common_args.paused_update_interval_secs {
+        args.push("--paused-update-interval-secs".to_string());
+        args.push(paused_update_interval_secs.to_string());
+    }
+
+    if common_args.start_running {
+        args.push("--start-running".to_string());
+    }
+
+    // NOTE: These only apply to the spawned server and are not part of the config
+    if common_args.no_bell {
+        args.push("--no-bell".to_string());
+    }
+
+    if common_args.no_notification {
+        args.push("--no-notification".to_string());
+    }
+
+    #[cfg(feature = "http")]
+    if let Some(http_port) = &common_args.http_port {
+        args.push("--http-port".to_string());
+        args.push(http_port.to_string());
+    }
 
     if let Some(postpone_limit) = &common_args.server_config.timers.postpone_limit {
         args.push("--postpone-limit".to_string());
@@ -77,16 +154,81 @@ fn get_server_args(common_args: &CommonArgs) -> Vec<String> {
         args.push(sound_file.to_string());
     }
 
+    if let Some(focus_end_sound) = &common_args.server_config.notifications.focus_end_sound {
+        args.push("--focus-end-sound".to_string());
+        args.push(focus_end_sound.to_string());
+    }
+
+    if let Some(break_end_sound) = &common_args.server_config.notifications.break_end_sound {
+        args.push("--break-end-sound".to_string());
+        args.push(break_end_sound.to_string());
+    }
+
+    if let Some(long_break_sound) = &common_args.server_config.notifications.long_break_sound {
+        args.push("--long-break-sound".to_string());
+        args.push(long_break_sound.to_string());
+    }
+
+    if let Some(ambient_sound) = &common_args.server_config.notifications.ambient_sound {
+        args.push("--ambient-sound".to_string());
+        args.push(ambient_sound.to_string());
+    }
+
     if let Some(volume) = &common_args.server_config.notifications.volume {
         args.push("--volume".to_string());
         args.push(volume.to_string());
     }
 
+    if let Some(fade_in_ms) = &common_args.server_config.notifications.fade_in_ms {
+        args.push("--fade-in-ms".to_string());
+        args.push(fade_in_ms.to_string());
+    }
+
+    if let Some(bell_repeat) = &common_args.server_config.notifications.bell_repeat {
+        args.push("--bell-repeat".to_string());
+        args.push(bell_repeat.to_string());
+    }
+
+    if let Some(bell_repeat_gap_ms) = &common_args.server_config.notifications.bell_repeat_gap_ms {
+        args.push("--bell-repeat-gap-ms".to_string());
+        args.push(bell_repeat_gap_ms.to_string());
+    }
+
+    if let Some(notification_debounce_ms) = &common_args
+        .server_config
+        .notifications
+        .notification_debounce_ms
+    {
+        args.push("--notification-debounce-ms".to_string());
+        args.push(notification_debounce_ms.to_string());
+    }
+
     if let Some(show_notification) = &common_args.server_config.notifications.show_notification {
         args.push("--show-notification".to_string());
         args.push(show_notification.to_string());
     }
 
+    if let Some(notification_summary) =
+        &common_args.server_config.notifications.notification_summary
+    {
+        args.push("--notification-summary".to_string());
+        args.push(notification_summary.to_string());
+    }
+
+    if let Some(notification_icon) = &common_args.server_config.notifications.notification_icon {
+        args.push("--notification-icon".to_string());
+        args.push(notification_icon.to_string());
+    }
+
+    if let Some(notification_timeout_ms) = &common_args
+        .server_config
+        .notifications
+        .notification_timeout_ms
+    {
+        args.push("--notification-timeout-ms".to_string());
+        args.push(notification_timeout_ms.to_string());
+    }
+
     if let Some(timer) = &common_args.server_config.timers.timer {
         args.push("--timer".to_string());
         args.push(timer.to_string());
@@ -102,6 +244,36 @@ fn get_server_args(common_args: &CommonArgs) -> Vec<String> {
         args.push(major_break.to_string());
     }
 
+    if let Some(max_rounds) = &common_args.server_config.timers.max_rounds {
+        args.push("--max-rounds".to_string());
+        args.push(max_rounds.to_string());
+    }
+
+    if let Some(strict_breaks) = &common_args.server_config.timers.strict_breaks {
+        args.push("--strict-breaks".to_string());
+        args.push(strict_breaks.to_string());
+    }
+
+    if let Some(auto_start_focus) = &common_args.server_config.timers.auto_start_focus {
+        args.push("--auto-start-focus".to_string());
+        args.push(auto_start_focus.to_string());
+    }
+
+    if let Some(pause_after_long_break) = &common_args.server_config.timers.pause_after_long_break {
+        args.push("--pause-after-long-break".to_string());
+        args.push(pause_after_long_break.to_string());
+    }
+
+    if let Some(break_ratio) = &common_args.server_config.timers.break_ratio {
+        args.push("--break-ratio".to_string());
+        args.push(break_ratio.to_string());
+    }
+
+    if let Some(overtime_secs) = &common_args.server_config.timers.overtime_secs {
+        args.push("--overtime-secs".to_string());
+        args.push(overtime_secs.to_string());
+    }
+
     if let Some(intervals) = &common_args.server_config.timers.intervals {
         args.push("--intervals".to_string());
         args.push(intervals.to_string())
@@ -1,21 +1,57 @@
 //! Utilities to handle zentime inter-process-communication
 
 use anyhow::{bail, Context};
+use chrono::{DateTime, Local};
 use futures::io::BufReader;
 use futures::{AsyncReadExt, AsyncWriteExt};
 use interprocess::local_socket::tokio::{OwnedReadHalf, OwnedWriteHalf};
 use interprocess::local_socket::NameTypeSupport;
 use serde::{Deserialize, Serialize};
-use zentime_rs_timer::pomodoro_timer::ViewState;
+use std::env;
 use std::fmt::Debug;
+use std::path::PathBuf;
+use std::time::Duration;
+use zentime_rs_timer::pomodoro_timer::ViewState;
+
+/// Interval in which the server sends a [ServerToClientMsg::Ping] to each client connection
+pub const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of consecutive pings a client may leave unanswered, before its connection is closed
+pub const MAX_MISSED_PONGS: u32 = 3;
+
+/// Maximum length of a single message (1 MiB), which is far more than any message needs.
+/// Longer frames are rejected before reading them, so that a misbehaving peer can't make the
+/// other side allocate arbitrary amounts of memory.
+pub const MAX_FRAME_LEN: usize = 1024 * 1024;
 
 const DEFAULT_SOCKET_PATH: &str = "/tmp/zentime.sock";
 const DEFAULT_SOCKET_NAMESPACE: &str = "@zentime.sock";
 const DEBUG_SOCKET_PATH: &str = "/tmp/zentime_debug.sock";
 const DEBUG_SOCKET_NAMESPACE: &str = "@zentime_debug.sock";
+const SOCKET_FILE_NAME: &str = "zentime.sock";
+const DEBUG_SOCKET_FILE_NAME: &str = "zentime_debug.sock";
+
+/// Get zentime socket name over which server and clients may connect.
+/// If `XDG_RUNTIME_DIR` is set, the socket is placed inside of it (e.g.
+/// `/run/user/1000/zentime.sock`). Otherwise a namespaced socket is used, if supported, or a
+/// socket inside `/tmp`.
+pub fn get_socket_name() -> String {
+    let file_name = if cfg!(debug_assertions) {
+        DEBUG_SOCKET_FILE_NAME
+    } else {
+        SOCKET_FILE_NAME
+    };
+
+    // WHY:
+    // Sockets inside `/tmp` (or the abstract namespace) can be reached by every user on the
+    // machine, whereas the runtime dir is only accessible by the current user.
+    if let Some(runtime_dir) = env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .filter(|runtime_dir| runtime_dir.is_absolute())
+    {
+        return runtime_dir.join(file_name).to_string_lossy().into_owned();
+    }
 
-/// Get zentime socket name over which server and clients may connect
-pub fn get_socket_name() -> &'static str {
     // This scoping trick allows us to nicely contain the import inside the `match`, so that if
     // any imports of variants named `Both` happen down the line, they won't collide with the
     // enum we're working with here. Maybe someone should make a macro for this.
@@ -32,6 +68,7 @@ pub fn get_socket_name() -> &'static str {
             OnlyNamespaced | Both => DEFAULT_SOCKET_NAMESPACE,
         }
     }
+    .to_string()
 }
 
 /// A message from the zentime server to the client
@@ -39,10 +76,147 @@ pub fn get_socket_name() -> &'static str {
 pub enum ServerToClientMsg {
     /// Aggregated state of the timer which a client can display
     Timer(ViewState),
+
+    /// A timer event the client has subscribed to (see [ClientToServerMsg::Subscribe])
+    Event(TimerEvent),
+
+    /// Keepalive, which is sent every [PING_INTERVAL] and has to be answered with
+    /// [ClientToServerMsg::Pong] (any other message counts as an answer as well).
+    ///
+    /// NOTE: Every client which stays connected for longer than [MAX_MISSED_PONGS] intervals
+    /// (i.e. about 30 seconds) has to answer these - otherwise the server closes its connection.
+    Ping,
+
+    /// Answer to [ClientToServerMsg::ListClients]
+    Clients(Vec<ClientInfo>),
+
+    /// Answer to [ClientToServerMsg::PauseFor] with the time at which the timer will be resumed
+    ResumeAt(DateTime<Local>),
+
+    /// Answer to a [Handshake], after which all messages are sent in the given [Encoding]
+    HandshakeAck(Encoding),
+
+    /// Answer to [ClientToServerMsg::GetServerInfo]
+    ServerInfo(ServerInfo),
+
+    /// Answer to [ClientToServerMsg::Reload] with all changed settings, which only apply after a
+    /// restart of the server (see [crate::config::Config::restart_required_changes]), or the
+    /// reason, why the config could not be reloaded
+    Reloaded(Result<Vec<String>, String>),
+}
+
+/// Runtime information about the server itself (see `zentime server status --verbose`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerInfo {
+    /// Time at which the server has been started
+    pub started_at: DateTime<Local>,
+
+    /// Time at which the current timer (focus interval or break) has first been running.
+    /// `None` if it hasn't been started yet.
+    pub timer_started_at: Option<DateTime<Local>>,
+}
+
+/// A client, which is currently connected to the server
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientInfo {
+    /// Id the server has assigned to the connection (counting up from 1)
+    pub id: u64,
+
+    /// Time at which the client has connected
+    pub connected_at: DateTime<Local>,
+}
+
+/// Typed event, which is emitted whenever the timer transitions into another state
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimerEvent {
+    /// A focus interval of the given round has started
+    IntervalStarted {
+        /// Current pomodoro round
+        round: u64,
+    },
+
+    /// A break after the given round has started
+    BreakStarted {
+        /// Current pomodoro round
+        round: u64,
+
+        /// Denotes if this is a long break
+        is_long: bool,
+    },
+
+    /// The break of the given round has been postponed
+    BreakPostponed {
+        /// Current pomodoro round
+        round: u64,
+
+        /// Times the break has been postponed
+        postpone_count: u16,
+    },
+
+    /// The running timer has been paused
+    TimerPaused,
+
+    /// The paused timer has been resumed
+    TimerResumed,
+
+    /// The daily goal of focus intervals has been reached
+    GoalReached {
+        /// Completed focus intervals of the current day
+        completed: u64,
+    },
+
+    /// All rounds of the session have been completed (see `timers.max_rounds`)
+    SessionComplete {
+        /// Number of completed rounds
+        rounds: u64,
+    },
+}
+
+impl TimerEvent {
+    /// Returns the kind of the event, which clients may subscribe to
+    pub fn kind(&self) -> EventKind {
+        match self {
+            TimerEvent::IntervalStarted { .. } => EventKind::IntervalStarted,
+            TimerEvent::BreakStarted { .. } => EventKind::BreakStarted,
+            TimerEvent::BreakPostponed { .. } => EventKind::BreakPostponed,
+            TimerEvent::TimerPaused => EventKind::TimerPaused,
+            TimerEvent::TimerResumed => EventKind::TimerResumed,
+            TimerEvent::GoalReached { .. } => EventKind::GoalReached,
+            TimerEvent::SessionComplete { .. } => EventKind::SessionComplete,
+        }
+    }
+}
+
+/// Kinds of [TimerEvent]s a client can subscribe to
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventKind {
+    /// Subscribe to all events
+    All,
+
+    /// See [TimerEvent::IntervalStarted]
+    IntervalStarted,
+
+    /// See [TimerEvent::BreakStarted]
+    BreakStarted,
+
+    /// See [TimerEvent::BreakPostponed]
+    BreakPostponed,
+
+    /// See [TimerEvent::TimerPaused]
+    TimerPaused,
+
+    /// See [TimerEvent::TimerResumed]
+    TimerResumed,
+
+    /// See [TimerEvent::GoalReached]
+    GoalReached,
+
+    /// See [TimerEvent::SessionComplete]
+    SessionComplete,
 }
 
 /// A message from a client to the zentime server
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ClientToServerMsg {
     /// Command the server to shutdown and close all connections
     Quit,
@@ -59,6 +233,10 @@ pub enum ClientToServerMsg {
     /// Command the server to reset the timer back to interval 1
     Reset,
 
+    /// Command the server to move back to the previous interval (see
+    /// [zentime_rs_timer::pomodoro_timer_action::PomodoroTimerAction::PreviousInterval])
+    Previous,
+
     /// Currently it's necessary for a client to write at least once to a socket
     /// connection to synchronize with the server.
     /// For one-shot zentime commands we therefore use this sync msg to synchronize
@@ -69,7 +247,101 @@ pub enum ClientToServerMsg {
     PostPone,
 
     /// Sets current timer to a specific time (in seconds)
-    SetTimer(u64)
+    SetTimer(u64),
+
+    /// Mutes (`true`) or unmutes (`false`) all notifications, while the timer keeps running
+    SetMuted(bool),
+
+    /// Counts a distraction during the current focus interval (ignored during breaks)
+    Distraction,
+
+    /// Overrides the duration (in seconds) of the next focus interval and/or the next break.
+    /// Each override is only applied once and the configured durations are used afterwards.
+    SetNextInterval {
+        /// Duration of the next focus interval
+        focus: Option<u64>,
+
+        /// Duration of the next (short or long) break
+        break_: Option<u64>,
+    },
+
+    /// Subscribes the connection to [TimerEvent]s of the given kind, which will be sent
+    /// as [ServerToClientMsg::Event] in addition to the regular timer state.
+    /// May be sent multiple times to subscribe to several kinds.
+    Subscribe(EventKind),
+
+    /// Answer to a [ServerToClientMsg::Ping]
+    Pong,
+
+    /// Requests all other clients, which are currently connected to the server (answered with
+    /// [ServerToClientMsg::Clients])
+    ListClients,
+
+    /// Stops the stopwatch, which shuts the server down (ignored if the server doesn't run a
+    /// stopwatch)
+    StopStopwatch,
+
+    /// Labels the current timer (e.g. with the task one is focusing on), which is shown by all
+    /// clients until the next timer starts. `None` removes the label.
+    SetLabel(Option<String>),
+
+    /// Requests runtime information about the server (answered with
+    /// [ServerToClientMsg::ServerInfo])
+    GetServerInfo,
+
+    /// Pauses the timer (if it is running) and resumes it after the given number of seconds,
+    /// unless it has been resumed manually in the meantime (answered with
+    /// [ServerToClientMsg::ResumeAt])
+    PauseFor(u64),
+
+    /// Reads the config again and applies it without restarting the server (answered with
+    /// [ServerToClientMsg::Reloaded]). Runtime settings (e.g. notifications) are applied right
+    /// away, changed durations only apply from the next timer on and settings like the socket
+    /// path only apply after a restart.
+    Reload,
+}
+
+/// Encoding of all messages sent over a single connection (see [Handshake])
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Encoding {
+    /// [Messagepack](https://msgpack.org/) via [rmp_serde]
+    #[default]
+    Msgpack,
+
+    /// JSON via [serde_json], which is easier to use for clients written in other languages
+    Json,
+}
+
+/// Optional first message of a client, which declares the [Encoding] of all following messages
+/// in both directions. Clients which don't send a handshake use [Encoding::Msgpack].
+///
+/// The handshake itself is always encoded as JSON (e.g. `{"encoding":"Json"}`), so that clients
+/// without messagepack support are able to send it. The server acknowledges it with
+/// [ServerToClientMsg::HandshakeAck], which is already sent in the requested encoding.
+///
+/// NOTE:
+/// Regardless of the encoding, long-lived clients have to answer every [ServerToClientMsg::Ping]
+/// with a [ClientToServerMsg::Pong], because the server closes connections which miss
+/// [MAX_MISSED_PONGS] pings in a row.
+///
+/// NOTE:
+/// The server might send timer states before it has received the handshake. These are still
+/// encoded as messagepack and should be skipped (which is possible because every message is
+/// prefixed with its length, regardless of its encoding).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Handshake {
+    /// Encoding of all following messages
+    pub encoding: Encoding,
+}
+
+/// The first message a server receives on a connection (see [InterProcessCommunication::recv_first_ipc_message])
+#[derive(Debug, Clone)]
+pub enum FirstMessage<M> {
+    /// The client has requested an [Encoding] for all following messages
+    Handshake(Handshake),
+
+    /// The client didn't send a handshake, which is why this message is encoded as messagepack
+    Message(M),
 }
 
 /// Service handling communication between processes over the zentime socket.
@@ -84,13 +356,61 @@ impl InterProcessCommunication {
     where
         M: Serialize + for<'a> Deserialize<'a> + Debug,
     {
-        let encoded_msg =
-            rmp_serde::encode::to_vec::<M>(&msg).context(format!("Could not encode {:?}", msg))?;
+        Self::send_ipc_message_as(msg, Encoding::Msgpack, writer).await
+    }
+
+    /// Writes a message to the zentime socket, which is encoded with the given [Encoding]
+    pub async fn send_ipc_message_as<M>(
+        msg: M,
+        encoding: Encoding,
+        writer: &mut OwnedWriteHalf,
+    ) -> anyhow::Result<()>
+    where
+        M: Serialize + for<'a> Deserialize<'a> + Debug,
+    {
+        let encoded_msg = match encoding {
+            Encoding::Msgpack => rmp_serde::encode::to_vec::<M>(&msg)
+                .context(format!("Could not encode {:?}", msg))?,
+            Encoding::Json => {
+                serde_json::to_vec(&msg).context(format!("Could not encode {:?}", msg))?
+            }
+        };
+
+        Self::send_frame(&encoded_msg, writer)
+            .await
+            .context(format!("Could not write {:?} to stream", msg))
+    }
+
+    /// Requests the given [Encoding] for all following messages of the connection.
+    /// This has to be the very first message sent over the connection.
+    pub async fn send_handshake(
+        encoding: Encoding,
+        writer: &mut OwnedWriteHalf,
+    ) -> anyhow::Result<()> {
+        let handshake = Handshake { encoding };
+        let encoded_handshake =
+            serde_json::to_vec(&handshake).context("Could not encode handshake")?;
+
+        Self::send_frame(&encoded_handshake, writer)
+            .await
+            .context("Could not write handshake to stream")
+    }
+
+    /// Writes an already encoded message with its length to the stream (see
+    /// [Self::recv_ipc_message] for why this is needed)
+    async fn send_frame(encoded_msg: &[u8], writer: &mut OwnedWriteHalf) -> anyhow::Result<()> {
+        if encoded_msg.len() > MAX_FRAME_LEN {
+            bail!(
+                "Message of {} bytes exceeds the maximum of {} bytes",
+                encoded_msg.len(),
+                MAX_FRAME_LEN
+            );
+        }
+
         let msg_length =
             u32::try_from(encoded_msg.len()).context("Could not cast msg length to u32")?;
         let msg_length = msg_length.to_le_bytes();
 
-        // Write msg with length to the stream (see [Self::recv_ipc_message] for why this is needed)
         writer
             .write_all(&msg_length)
             .await
@@ -98,9 +418,9 @@ impl InterProcessCommunication {
 
         // Write actual msg to the stream
         writer
-            .write_all(&encoded_msg)
+            .write_all(encoded_msg)
             .await
-            .context(format!("Could not write {:?} to stream", msg))?;
+            .context("Could not write message to stream")?;
 
         Ok(())
     }
@@ -111,6 +431,57 @@ impl InterProcessCommunication {
     where
         M: Serialize + for<'a> Deserialize<'a> + Debug,
     {
+        Self::recv_ipc_message_as(Encoding::Msgpack, reader).await
+    }
+
+    /// Reads a message from the zentime socket, which is decoded with the given [Encoding]
+    pub async fn recv_ipc_message_as<M>(
+        encoding: Encoding,
+        reader: &mut BufReader<OwnedReadHalf>,
+    ) -> anyhow::Result<M>
+    where
+        M: Serialize + for<'a> Deserialize<'a> + Debug,
+    {
+        let buffer = Self::recv_frame(reader).await?;
+
+        match encoding {
+            Encoding::Msgpack => match rmp_serde::from_slice::<M>(&buffer) {
+                Ok(msg) => Ok(msg),
+                Err(error) => bail!("Could not decode msg: {:?}", error),
+            },
+            Encoding::Json => match serde_json::from_slice::<M>(&buffer) {
+                Ok(msg) => Ok(msg),
+                Err(error) => bail!("Could not decode msg: {:?}", error),
+            },
+        }
+    }
+
+    /// Reads the first message of a connection, which is either a [Handshake] or a regular
+    /// message encoded as messagepack (sent by clients which don't negotiate an encoding)
+    pub async fn recv_first_ipc_message<M>(
+        reader: &mut BufReader<OwnedReadHalf>,
+    ) -> anyhow::Result<FirstMessage<M>>
+    where
+        M: Serialize + for<'a> Deserialize<'a> + Debug,
+    {
+        let buffer = Self::recv_frame(reader).await?;
+
+        // NOTE: A JSON object starts with `{`, which is never the start of an encoded message
+        if let Ok(handshake) = serde_json::from_slice::<Handshake>(&buffer) {
+            return Ok(FirstMessage::Handshake(handshake));
+        }
+
+        match rmp_serde::from_slice::<M>(&buffer) {
+            Ok(msg) => Ok(FirstMessage::Message(msg)),
+            Err(error) => bail!("Could not decode msg: {:?}", error),
+        }
+    }
+
+    /// Reads a single message (without decoding it).
+    ///
+    /// NOTE: A frame longer than [MAX_FRAME_LEN] results in an error. Its content is left unread,
+    /// which is why the connection should be closed afterwards.
+    async fn recv_frame(reader: &mut BufReader<OwnedReadHalf>) -> anyhow::Result<Vec<u8>> {
         // Read message length, so that we can make an exact read of the actual message afterwards
         let mut buffer = [0_u8; 4];
 
@@ -118,18 +489,21 @@ impl InterProcessCommunication {
             .read_exact(&mut buffer)
             .await
             .context("Could not read msg length")?;
-        let msg_length = u32::from_le_bytes(buffer);
+        let msg_length = usize::try_from(u32::from_le_bytes(buffer))
+            .context("Could not convert msg length to usize")?;
 
-        let mut buffer = [0_u8; 1024];
+        if msg_length > MAX_FRAME_LEN {
+            bail!(
+                "Message of {} bytes exceeds the maximum of {} bytes",
+                msg_length,
+                MAX_FRAME_LEN
+            );
+        }
 
-        // Read message of previously determined length, decode and return it
-        if let Err(error) = reader
-            .read_exact(
-                &mut buffer[0..usize::try_from(msg_length)
-                    .context("Could not convert msg length to usize")?],
-            )
-            .await
-        {
+        let mut buffer = vec![0_u8; msg_length];
+
+        // Read message of previously determined length
+        if let Err(error) = reader.read_exact(&mut buffer).await {
             match error.kind() {
                 std::io::ErrorKind::UnexpectedEof => {
                     bail!("Buffer slice has not been filled entirely: {:?}", error)
@@ -138,12 +512,351 @@ impl InterProcessCommunication {
             }
         };
 
-        match rmp_serde::from_slice::<M>(
-            &buffer[0..usize::try_from(msg_length)
-                .context("Could not convert msg length to usize")?],
-        ) {
-            Ok(msg) => Ok(msg),
-            Err(error) => bail!("Could not decode msg: {:?}", error),
+        Ok(buffer)
+    }
+}
+
+/// Checks whether the given error has been caused by the other side closing its half of the
+/// connection (e.g. a client which has exited without sending [ClientToServerMsg::Detach]).
+pub fn is_connection_closed(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause.downcast_ref::<std::io::Error>().is_some_and(|error| {
+            matches!(
+                error.kind(),
+                std::io::ErrorKind::UnexpectedEof
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::ConnectionReset
+            )
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use interprocess::local_socket::tokio::{LocalSocketListener, LocalSocketStream};
+
+    /// Both ends of a connection over a temporary socket
+    struct Connection {
+        client_reader: BufReader<OwnedReadHalf>,
+        client_writer: OwnedWriteHalf,
+        server_reader: BufReader<OwnedReadHalf>,
+        server_writer: OwnedWriteHalf,
+    }
+
+    /// Connects a client to a server, which listens on a temporary socket unique to the test
+    async fn connect(test_name: &str) -> Connection {
+        let socket_path = env::temp_dir().join(format!(
+            "zentime_ipc_test_{}_{}.sock",
+            std::process::id(),
+            test_name
+        ));
+        std::fs::remove_file(&socket_path).ok();
+
+        let listener = LocalSocketListener::bind(socket_path.as_path()).expect("Could not bind");
+        let (server_conn, client_conn) = tokio::join!(
+            listener.accept(),
+            LocalSocketStream::connect(socket_path.as_path())
+        );
+        std::fs::remove_file(&socket_path).ok();
+
+        let (server_reader, server_writer) = server_conn.expect("Could not accept").into_split();
+        let (client_reader, client_writer) = client_conn.expect("Could not connect").into_split();
+
+        Connection {
+            client_reader: BufReader::new(client_reader),
+            client_writer,
+            server_reader: BufReader::new(server_reader),
+            server_writer,
+        }
+    }
+
+    fn view_state() -> ViewState {
+        ViewState {
+            round: 2,
+            intervals_until_long_break: 2,
+            time: "25:00".to_string(),
+            remaining_secs: 1500,
+            is_paused: true,
+            goal_progress: Some((1, 8)),
+            distractions: 1,
+            session_elapsed_secs: 1500,
+            cycle_remaining_secs: 4500,
+            label: Some("Writing docs".to_string()),
+            ..ViewState::default()
         }
     }
+
+    fn client_msgs() -> Vec<ClientToServerMsg> {
+        vec![
+            ClientToServerMsg::Quit,
+            ClientToServerMsg::Detach,
+            ClientToServerMsg::PlayPause,
+            ClientToServerMsg::Skip,
+            ClientToServerMsg::Reset,
+            ClientToServerMsg::Previous,
+            ClientToServerMsg::Sync,
+            ClientToServerMsg::PostPone,
+            ClientToServerMsg::SetTimer(300),
+            ClientToServerMsg::SetMuted(true),
+            ClientToServerMsg::Distraction,
+            ClientToServerMsg::SetNextInterval {
+                focus: Some(3000),
+                break_: None,
+            },
+            ClientToServerMsg::Subscribe(EventKind::BreakStarted),
+            ClientToServerMsg::Pong,
+            ClientToServerMsg::ListClients,
+            ClientToServerMsg::StopStopwatch,
+            ClientToServerMsg::SetLabel(Some("Writing docs".to_string())),
+            ClientToServerMsg::SetLabel(None),
+            ClientToServerMsg::GetServerInfo,
+            ClientToServerMsg::PauseFor(300),
+            ClientToServerMsg::Reload,
+        ]
+    }
+
+    fn server_msgs() -> Vec<ServerToClientMsg> {
+        let time = Local.with_ymd_and_hms(2023, 1, 2, 9, 30, 0).unwrap();
+
+        vec![
+            ServerToClientMsg::Timer(view_state()),
+            ServerToClientMsg::Event(TimerEvent::BreakStarted {
+                round: 2,
+                is_long: false,
+            }),
+            ServerToClientMsg::Ping,
+            ServerToClientMsg::Clients(vec![ClientInfo {
+                id: 1,
+                connected_at: time,
+            }]),
+            ServerToClientMsg::ResumeAt(time),
+            ServerToClientMsg::HandshakeAck(Encoding::Json),
+            ServerToClientMsg::ServerInfo(ServerInfo {
+                started_at: time,
+                timer_started_at: None,
+            }),
+            ServerToClientMsg::Reloaded(Ok(vec![])),
+            ServerToClientMsg::Reloaded(Ok(vec!["schedule".to_string()])),
+            ServerToClientMsg::Reloaded(Err("Invalid config".to_string())),
+        ]
+    }
+
+    /// Sends all messages in order and receives them on the other end.
+    /// NOTE: The messages don't implement `PartialEq`, which is why their debug output is compared.
+    async fn assert_round_trip<M>(
+        msgs: Vec<M>,
+        encoding: Encoding,
+        writer: &mut OwnedWriteHalf,
+        reader: &mut BufReader<OwnedReadHalf>,
+    ) where
+        M: Serialize + for<'a> Deserialize<'a> + Debug + Clone,
+    {
+        let sent = msgs.clone();
+        let send = async {
+            for msg in sent {
+                InterProcessCommunication::send_ipc_message_as(msg, encoding, writer)
+                    .await
+                    .expect("Could not send msg");
+            }
+        };
+        let recv = async {
+            let mut received = vec![];
+            for _ in 0..msgs.len() {
+                received.push(
+                    InterProcessCommunication::recv_ipc_message_as::<M>(encoding, reader)
+                        .await
+                        .expect("Could not receive msg"),
+                );
+            }
+            received
+        };
+
+        let ((), received) = tokio::join!(send, recv);
+        assert_eq!(format!("{:?}", received), format!("{:?}", msgs));
+    }
+
+    #[tokio::test]
+    async fn all_messages_round_trip() {
+        let mut conn = connect("all_messages").await;
+
+        for encoding in [Encoding::Msgpack, Encoding::Json] {
+            assert_round_trip(
+                client_msgs(),
+                encoding,
+                &mut conn.client_writer,
+                &mut conn.server_reader,
+            )
+            .await;
+            assert_round_trip(
+                server_msgs(),
+                encoding,
+                &mut conn.server_writer,
+                &mut conn.client_reader,
+            )
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn frames_of_any_length_round_trip() {
+        let mut conn = connect("frame_lengths").await;
+
+        // Crosses the boundary of the former fixed 1024 byte buffer and the buffer of the reader
+        let lengths = [0, 1, 1023, 1024, 1025, 20_000];
+        let frames: Vec<Vec<u8>> = lengths
+            .iter()
+            .map(|length| (0..*length).map(|byte| (byte % 251) as u8).collect())
+            .collect();
+
+        let send = async {
+            for frame in &frames {
+                InterProcessCommunication::send_frame(frame, &mut conn.client_writer)
+                    .await
+                    .expect("Could not send frame");
+            }
+        };
+        let recv = async {
+            let mut received = vec![];
+            for _ in &frames {
+                received.push(
+                    InterProcessCommunication::recv_frame(&mut conn.server_reader)
+                        .await
+                        .expect("Could not receive frame"),
+                );
+            }
+            received
+        };
+
+        let ((), received) = tokio::join!(send, recv);
+        assert_eq!(received, frames);
+    }
+
+    #[tokio::test]
+    async fn large_messages_round_trip() {
+        let mut conn = connect("large_messages").await;
+        let time = Local.with_ymd_and_hms(2023, 1, 2, 9, 30, 0).unwrap();
+
+        let clients: Vec<ClientInfo> = (1..=200)
+            .map(|id| ClientInfo {
+                id,
+                connected_at: time,
+            })
+            .collect();
+
+        for encoding in [Encoding::Msgpack, Encoding::Json] {
+            assert_round_trip(
+                vec![
+                    ClientToServerMsg::SetLabel(Some("a".repeat(1025))),
+                    ClientToServerMsg::Sync,
+                ],
+                encoding,
+                &mut conn.client_writer,
+                &mut conn.server_reader,
+            )
+            .await;
+            assert_round_trip(
+                vec![
+                    ServerToClientMsg::Clients(clients.clone()),
+                    ServerToClientMsg::Ping,
+                ],
+                encoding,
+                &mut conn.server_writer,
+                &mut conn.client_reader,
+            )
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_frames_are_rejected() {
+        let mut conn = connect("oversized_frame").await;
+
+        // Only the length is sent - the receiver must not wait for (or allocate) the content
+        conn.client_writer
+            .write_all(&u32::MAX.to_le_bytes())
+            .await
+            .expect("Could not write msg length");
+
+        assert!(
+            InterProcessCommunication::recv_frame(&mut conn.server_reader)
+                .await
+                .is_err()
+        );
+
+        assert!(InterProcessCommunication::send_frame(
+            &vec![0; MAX_FRAME_LEN + 1],
+            &mut conn.client_writer
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn empty_frame_is_not_decoded() {
+        let mut conn = connect("empty_frame").await;
+
+        InterProcessCommunication::send_frame(&[], &mut conn.client_writer)
+            .await
+            .expect("Could not send frame");
+        InterProcessCommunication::send_ipc_message(
+            ClientToServerMsg::Sync,
+            &mut conn.client_writer,
+        )
+        .await
+        .expect("Could not send msg");
+
+        assert!(
+            InterProcessCommunication::recv_ipc_message::<ClientToServerMsg>(
+                &mut conn.server_reader
+            )
+            .await
+            .is_err()
+        );
+
+        // The connection is still in sync after the empty frame
+        let msg = InterProcessCommunication::recv_ipc_message::<ClientToServerMsg>(
+            &mut conn.server_reader,
+        )
+        .await
+        .expect("Could not receive msg");
+        assert!(matches!(msg, ClientToServerMsg::Sync));
+    }
+
+    #[tokio::test]
+    async fn first_message_is_either_a_handshake_or_a_message() {
+        let mut conn = connect("first_message").await;
+
+        InterProcessCommunication::send_handshake(Encoding::Json, &mut conn.client_writer)
+            .await
+            .expect("Could not send handshake");
+        let first = InterProcessCommunication::recv_first_ipc_message::<ClientToServerMsg>(
+            &mut conn.server_reader,
+        )
+        .await
+        .expect("Could not receive handshake");
+        assert!(matches!(
+            first,
+            FirstMessage::Handshake(Handshake {
+                encoding: Encoding::Json
+            })
+        ));
+
+        InterProcessCommunication::send_ipc_message(
+            ClientToServerMsg::Skip,
+            &mut conn.client_writer,
+        )
+        .await
+        .expect("Could not send msg");
+        let first = InterProcessCommunication::recv_first_ipc_message::<ClientToServerMsg>(
+            &mut conn.server_reader,
+        )
+        .await
+        .expect("Could not receive msg");
+        assert!(matches!(
+            first,
+            FirstMessage::Message(ClientToServerMsg::Skip)
+        ));
+    }
 }
@@ -3,6 +3,8 @@
     missing_copy_implementations,
     missing_debug_implementations
 )]
+// `is_multiple_of` would require Rust 1.87
+#![allow(unknown_lints, clippy::manual_is_multiple_of)]
 //! Zentime is a client/server based CLI pomodor/productivity timer written in Rust.
 //! This crate consists of a binary and a library crate.
 //! The library crate is a collection of tools to interact with zentime server, clients, configuration and to handle
@@ -14,3 +16,4 @@ pub mod client;
 pub mod config;
 pub mod ipc;
 pub mod server;
+pub mod stats;
@@ -1,27 +1,107 @@
 use crate::default_cmd::default_cmd;
-use clap::{Parser, Subcommand};
+use chrono::NaiveDate;
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
 use env_logger::Env;
 
 mod default_cmd;
 mod subcommands;
 use figment::providers::Serialized;
+use figment::Figment;
+use interprocess::local_socket::tokio::{OwnedReadHalf, OwnedWriteHalf};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+#[cfg(feature = "ws")]
+use subcommands::bridge::bridge;
 use subcommands::{
+    completions::completions,
+    config::{check, show},
+    export::{export, ExportFormat},
+    focus_now::focus_now,
+    mute::set_muted,
+    pause::pause_for,
     postpone::postpone,
+    previous_interval::previous_interval,
     query_server_once::query_server_once,
+    remaining::remaining,
     reset_timer::reset_timer,
-    server::{start_daemonized, status, stop},
+    server::{
+        clients, doctor, logs, ping, reload, restart, start_and_wait, start_daemonized,
+        start_foreground, start_one_shot, start_stopwatch, status, stop,
+    },
+    set_next_interval::set_next_interval,
     set_timer::set_timer,
     skip_timer::skip_timer,
+    sound::{extract, parse_volume, test},
+    stats::{parse_day_arg, stats, StatsFormat},
+    stopwatch::stop_stopwatch,
     toggle_timer::toggle_timer,
+    watch::{watch, WatchFormat},
 };
-use zentime_rs::config::{create_base_config, Config};
+use zentime_rs::client::one_shot_connection::{one_shot_connection, ConnectionError};
+use zentime_rs::config::{config_file_path, create_base_config, Config};
+use zentime_rs::server::TimerMode;
+use zentime_rs_timer::util::parse_duration;
 
-#[derive(clap::Args)]
+#[derive(clap::Args, Clone)]
 pub struct CommonArgs {
-    /// Sets a custom config file
-    #[arg(short, long, default_value = "~/.config/zentime/zentime.toml")]
-    config: String,
+    /// Sets a custom config file, which overrides a project local `.zentime.toml`
+    /// (defaults to `~/.config/zentime/zentime.toml`)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Sets a custom socket path over which server and clients communicate
+    #[arg(long)]
+    socket: Option<String>,
+
+    /// Log level of the server daemon (error, warn, info, debug or trace)
+    #[arg(long)]
+    log_level: Option<String>,
+
+    /// Number of focus intervals which should be completed per day
+    #[arg(long)]
+    daily_goal: Option<u64>,
+
+    /// Pauses a running focus interval after the given number of seconds without any client input
+    #[arg(long)]
+    pause_on_idle_secs: Option<u64>,
+
+    /// Seconds after which a paused timer sends fewer view updates (0 disables the backoff)
+    #[arg(long)]
+    paused_backoff_secs: Option<u64>,
+
+    /// Seconds between two view updates of a paused timer, which has backed off
+    #[arg(long)]
+    paused_update_interval_secs: Option<u64>,
+
+    /// Starts counting down the first focus interval as soon as the server has been started
+    #[arg(long)]
+    start_running: bool,
+
+    /// Disables the bell for this session only (including per event overrides of the config).
+    /// Only applies to a newly started server.
+    #[arg(long)]
+    no_bell: bool,
+
+    /// Disables OS-notifications for this session only (including per event overrides of the
+    /// config). Only applies to a newly started server.
+    #[arg(long)]
+    no_notification: bool,
+
+    /// Selects a configuration profile (e.g. `work` for a `[profiles.work]` table), which is
+    /// merged over the configuration file
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Disables the discovery of a project local `.zentime.toml` in the current directory
+    /// (or any of its parents)
+    #[arg(long)]
+    no_local_config: bool,
+
+    /// Serves the current timer state as JSON via `GET /status` on the given localhost port
+    #[cfg(feature = "http")]
+    #[arg(long)]
+    http_port: Option<u16>,
 
     #[command(flatten)]
     server_config: ServerConfig,
@@ -43,15 +123,72 @@ struct ClapNotificationConfig {
     #[arg(long)]
     pub sound_file: Option<String>,
 
+    /// Path to soundfile which is played back when a focus interval ends (falls back to
+    /// `--sound-file`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub focus_end_sound: Option<String>,
+
+    /// Path to soundfile which is played back when a break ends (falls back to `--sound-file`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub break_end_sound: Option<String>,
+
+    /// Path to soundfile which is played back when a long break starts (falls back to
+    /// `--sound-file`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub long_break_sound: Option<String>,
+
+    /// Path to soundfile which is looped while a focus interval is running (e.g. white noise)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub ambient_sound: Option<String>,
+
     /// Notification bell volume
     #[serde(skip_serializing_if = "Option::is_none")]
     #[arg(long)]
     pub volume: Option<f32>,
 
+    /// Duration in milliseconds over which the bell fades in (0 disables fading)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub fade_in_ms: Option<u64>,
+
+    /// Number of times the bell is played back on each notification
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub bell_repeat: Option<u8>,
+
+    /// Silence in milliseconds between two repetitions of the bell
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub bell_repeat_gap_ms: Option<u64>,
+
+    /// Suppress notifications within the given number of milliseconds after the previous one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub notification_debounce_ms: Option<u64>,
+
     /// Show OS-notification
     #[serde(skip_serializing_if = "Option::is_none")]
     #[arg(long)]
     pub show_notification: Option<bool>,
+
+    /// Summary (title) of OS-notifications
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub notification_summary: Option<String>,
+
+    /// Icon of OS-notifications (path to an image or freedesktop icon name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub notification_icon: Option<String>,
+
+    /// Close OS-notifications after the given number of milliseconds (0 keeps them open)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub notification_timeout_ms: Option<u32>,
 }
 
 /// This should match [zentime-rs-timer::config::TimerConfig], but makes fields optional, so that they are not
@@ -91,6 +228,38 @@ struct ClapTimerConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[arg(long)]
     pub postpone_timer: Option<u64>,
+
+    /// Number of rounds (focus interval + break) after which the session is complete
+    /// and the timer stops (runs indefinitely, if not set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub max_rounds: Option<u64>,
+
+    /// Breaks can not be skipped, if this is set (postponing them is still possible)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub strict_breaks: Option<bool>,
+
+    /// Focus intervals start right away once a break has ended
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub auto_start_focus: Option<bool>,
+
+    /// The focus interval after a long break waits for play/pause (even with auto-start-focus)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub pause_after_long_break: Option<bool>,
+
+    /// Short breaks last for the given fraction of the preceding focus interval (e.g. 0.2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub break_ratio: Option<f32>,
+
+    /// Seconds the timer keeps counting up after a focus interval has run out, before the break
+    /// starts (skip or play/pause to start the break right away)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub overtime_secs: Option<u64>,
 }
 
 #[derive(clap::Args, Serialize, Deserialize, Clone, Debug)]
@@ -117,6 +286,44 @@ struct ClapViewConfig {
     /// (e.g. `zentime skip` or `zentime toggle-timer`)
     #[arg(long, short = 's')]
     pub silent: bool,
+
+    /// Number of attempts to reconnect to the server after the connection has been lost
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub reconnect_attempts: Option<u32>,
+
+    /// Quit the client right away when the server connection has been lost, instead of trying
+    /// to reconnect
+    #[serde(skip)]
+    #[arg(long)]
+    pub no_reconnect: bool,
+
+    /// Shows the elapsed time of the current session and the time until the next long break
+    /// inside the default interface
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub show_session_times: Option<bool>,
+
+    /// Renders the default interface inside the alternate screen (otherwise it is rendered
+    /// in-place inside the main buffer)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub use_alternate_screen: Option<bool>,
+
+    /// Renders a short progress bar of the current timer inside the minimal interface
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub show_progress_bar: Option<bool>,
+
+    /// Colors the minimal interface (auto-detected if not set, e.g. disabled by `NO_COLOR`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub use_colors: Option<bool>,
+
+    /// Requires skipping a focus interval to be confirmed by pressing `s` a second time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub confirm_skip: Option<bool>,
 }
 
 #[derive(clap::Args, Serialize, Deserialize, Clone, Debug)]
@@ -141,6 +348,16 @@ struct Cli {
     #[command(flatten)]
     client_config: ClientConfig,
 
+    /// Only attach to an already running server and exit with an error otherwise, instead of
+    /// starting a new server (only applies when run without command)
+    #[arg(long)]
+    attach_only: bool,
+
+    /// Only display the timer without being able to control it, e.g. on a shared screen
+    /// (only applies when run without command)
+    #[arg(long)]
+    readonly: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -154,26 +371,213 @@ enum Commands {
     /// zentime into a status bar etc.
     Once,
 
-    /// Toggles between timer play/pause
+    /// Prints only the remaining time of the current timer (e.g. `24:59`).
+    /// This is meant for shell prompts and similar integrations.
+    Remaining {
+        /// Prints the remaining time in seconds instead (e.g. `1499`)
+        #[arg(long)]
+        secs: bool,
+    },
+
+    /// Starts the server (if necessary) and immediately unpauses the timer without attaching
+    /// the interface. Prints the resulting state once.
+    FocusNow,
+
+    /// Attaches to the server and continuously prints each timer state to stdout (like `once`,
+    /// but without terminating). Exits on Ctrl-C.
+    Watch {
+        /// Output format of the printed timer states
+        #[arg(long, value_enum, default_value_t)]
+        format: WatchFormat,
+    },
+
+    /// Toggles between timer play/pause and prints the resulting state (`paused` or `running`)
     ToggleTimer,
 
+    /// Pauses the timer for the given duration (e.g. "5m", at most a day) and resumes it
+    /// automatically afterwards, unless it has been resumed manually before
+    Pause {
+        #[arg(long = "for", visible_alias = "duration", value_parser = parse_duration)]
+        duration: u64,
+    },
+
     /// Skips to next timer interval
     Skip,
 
     /// Resets the timer to the first interval
     Reset,
 
+    /// Moves back to the previous timer interval (the rewound timer starts paused)
+    Previous,
+
     /// Postpones the current break (if possible)
     Postpone,
 
+    /// Mutes the bell and OS-notifications (the timer keeps running)
+    Mute,
+
+    /// Unmutes the bell and OS-notifications
+    Unmute,
+
     /// Sets current timer to a specific time in seconds
     SetTimer { time: u64 },
 
+    /// Overrides the duration of the next focus interval and/or break (e.g. "50m") once,
+    /// without changing the config
+    Next {
+        /// Duration of the next focus interval
+        #[arg(long, value_parser = parse_duration, required_unless_present = "break_")]
+        focus: Option<u64>,
+
+        /// Duration of the next break
+        #[arg(long = "break", value_parser = parse_duration)]
+        break_: Option<u64>,
+    },
+
+    /// Runs a single focus session of the given duration (e.g. "25m"), which does not transition
+    /// into a break
+    Focus {
+        #[arg(value_parser = parse_duration)]
+        duration: u64,
+
+        #[command(flatten)]
+        common_args: CommonArgs,
+
+        /// Replace the session of a running server
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Runs an open-ended focus session, which counts up until it is stopped
+    Stopwatch {
+        #[command(subcommand)]
+        command: StopwatchCommands,
+    },
+
     /// Interact with the zentime server
     Server {
         #[command(subcommand)]
         command: ServerCommands,
     },
+
+    /// Print a shell completion script to stdout
+    /// (e.g. `zentime completions zsh > ~/.zfunc/_zentime`)
+    Completions {
+        /// Shell to generate the completion script for
+        shell: Shell,
+    },
+
+    /// Rebroadcast the timer state as JSON to WebSocket clients on `127.0.0.1:<ws-port>`, which
+    /// may send the commands `play-pause`, `skip`, `postpone`, `reset`, `previous` and `distraction`
+    #[cfg(feature = "ws")]
+    Bridge {
+        #[arg(long)]
+        ws_port: u16,
+
+        /// Origin (e.g. `http://localhost:1420`), from which browser based widgets may connect.
+        /// Can be passed multiple times. Browsers of any other origin are rejected.
+        #[arg(long = "allow-origin")]
+        allowed_origins: Vec<String>,
+    },
+
+    /// Export all timers recorded inside the stats file (see `stats_file` inside the [server]
+    /// section of the config), e.g. to import them into a calendar or spreadsheet
+    Export {
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: ExportFormat,
+
+        /// Only export timers started on or after the given day (e.g. "2024-01-31" or "7d" for
+        /// seven days ago)
+        #[arg(long, value_parser = parse_day_arg)]
+        since: Option<NaiveDate>,
+    },
+
+    /// Print the total focus time and number of intervals recorded inside the stats file (see
+    /// `stats_file` inside the [server] section of the config)
+    Stats {
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: StatsFormat,
+
+        /// Only count timers started on or after the given day (e.g. "2024-01-31" or "7d" for
+        /// seven days ago)
+        #[arg(long, value_parser = parse_day_arg)]
+        since: Option<NaiveDate>,
+
+        /// Only count timers started on or before the given day (e.g. "2024-01-31", "today" or
+        /// "1d" for yesterday)
+        #[arg(long, value_parser = parse_day_arg)]
+        until: Option<NaiveDate>,
+
+        /// Only count timers with the given label (e.g. "zentime", see `l` inside the interface)
+        #[arg(long)]
+        label: Option<String>,
+    },
+
+    /// Inspect the zentime configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// Try out sounds before using them as `sound_file`
+    Sound {
+        #[command(subcommand)]
+        command: SoundCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the effective configuration (defaults merged with the config file and all
+    /// provided flags) without starting anything
+    Show {
+        #[command(flatten)]
+        common_args: CommonArgs,
+    },
+
+    /// Load and validate the config file (e.g. `--config path.toml`) without starting anything.
+    /// Exits with a non-zero code and lists all problems, if the config is invalid.
+    Check {
+        #[command(flatten)]
+        common_args: CommonArgs,
+    },
+}
+
+#[derive(Subcommand)]
+enum SoundCommands {
+    /// Write the default bell (WAV) to the given path, e.g. to use it as a starting point for a
+    /// custom `sound_file`
+    Extract { path: String },
+
+    /// Play back a sound file once to check whether it can be decoded and is audible
+    Test {
+        /// Sound file to play back (defaults to the default bell)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Volume between 0.0 and 1.0
+        #[arg(long, default_value_t = 1.0, value_parser = parse_volume)]
+        volume: f32,
+    },
+}
+
+#[derive(Subcommand)]
+enum StopwatchCommands {
+    /// Start a stopwatch in place of the regular timer
+    Start {
+        // NOTE: Boxed, because `stop` doesn't have any arguments at all
+        #[command(flatten)]
+        common_args: Box<CommonArgs>,
+
+        /// Replace the session of a running server
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Stop the running stopwatch, record it like a focus interval and shut the server down
+    Stop,
 }
 
 #[derive(Subcommand)]
@@ -182,73 +586,422 @@ enum ServerCommands {
     Start {
         #[command(flatten)]
         common_args: CommonArgs,
+
+        /// Runs a single focus session of the given duration (e.g. "25m") instead of the
+        /// pomodoro cycle and shuts the server down afterwards
+        #[arg(long, value_parser = parse_duration)]
+        one_shot: Option<u64>,
+
+        /// Runs a stopwatch, which counts up until it is stopped, instead of the pomodoro cycle
+        /// and shuts the server down afterwards
+        #[arg(long, conflicts_with = "one_shot")]
+        stopwatch: bool,
+
+        /// Runs the server inside the current process instead of daemonizing it and logs to
+        /// stdout/stderr (e.g. to run zentime as a systemd service)
+        #[arg(long)]
+        foreground: bool,
+
+        /// Blocks until the server accepts connections and exits with a non-zero code, if it
+        /// doesn't do so in time (e.g. to chain commands with `&&` inside of scripts)
+        #[arg(long, conflicts_with = "foreground")]
+        wait: bool,
     },
 
     /// Stop the zentime server and close all client connections
     Stop,
 
+    /// Stop the running zentime server and start a fresh one with the given config
+    Restart {
+        #[command(flatten)]
+        common_args: CommonArgs,
+
+        /// Start a new server, even if no server has been running before
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Let the running zentime server read its config again without losing the timer state.
+    /// Notification settings apply right away and changed durations from the next timer on.
+    Reload,
+
     /// Check if the zentime server is currently running
-    Status,
+    Status {
+        /// Print the status as JSON (e.g. `{"running": true, "pid": 1234, "socket": "..."}`)
+        #[arg(long)]
+        json: bool,
+
+        /// Additionally print the server uptime and the time at which the current timer started
+        #[arg(long, short)]
+        verbose: bool,
+    },
+
+    /// List all clients, which are currently connected to the server
+    Clients,
+
+    /// Diagnose an unresponsive server by checking its socket file, process and connection
+    Doctor,
+
+    /// Measure the round trip from sending a message to the server until the next timer state
+    /// arrives (e.g. to check whether the socket is slowing down a prompt integration)
+    Ping {
+        /// Number of round trips to measure
+        #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        count: u32,
+    },
+
+    /// Print the output and error logs of the zentime server
+    Logs {
+        /// Keep printing new log output as it is written
+        #[arg(short, long)]
+        follow: bool,
+    },
 }
 
 fn main() {
-    env_logger::Builder::from_env(Env::default().default_filter_or("warn"))
-        .target(env_logger::Target::Stdout)
-        .init();
     let cli = Cli::parse();
 
-    if let Some(Commands::Server { command }) = &cli.command {
+    if let Some(Commands::Server {
+        command:
+            ServerCommands::Start {
+                common_args,
+                one_shot,
+                stopwatch,
+                foreground,
+                wait,
+            },
+    }) = &cli.command
+    {
+        let mode = match one_shot {
+            Some(duration) => TimerMode::OneShot(*duration),
+            None if *stopwatch => TimerMode::Stopwatch,
+            None => TimerMode::Pomodoro,
+        };
+
+        // NOTE: The server initializes its own logger with the configured log level
+        if *foreground {
+            start_foreground(common_args, mode);
+        } else if *wait {
+            start_and_wait(common_args, mode);
+        } else {
+            start_daemonized(common_args, mode);
+        }
+        return;
+    }
+
+    init_logger("warn");
+
+    // Completions don't depend on any configuration, so that they even work with a broken config
+    if let Some(Commands::Completions { shell }) = &cli.command {
+        completions(*shell, &mut Cli::command());
+        return;
+    }
+
+    // Sounds are tried out before they are configured, which is why the config doesn't matter
+    if let Some(Commands::Sound { command }) = &cli.command {
         match command {
-            ServerCommands::Start { common_args } => start_daemonized(common_args),
-            ServerCommands::Stop => stop(),
-            ServerCommands::Status => status(),
+            SoundCommands::Extract { path } => extract(path),
+            SoundCommands::Test { file, volume } => test(file.as_deref(), *volume),
         }
+        return;
+    }
 
+    // The checked config must not depend on the default config, which might be broken itself
+    if let Some(Commands::Config {
+        command: ConfigCommands::Check { common_args },
+    }) = &cli.command
+    {
+        check(
+            &config_file_path(common_args.config.as_deref()),
+            base_config_or_exit(common_args),
+        );
         return;
     }
 
-    let config_path = &cli.common_args.config;
-    let config: Config = get_client_config(config_path, &cli.client_config);
+    let config: Config = get_client_config(&cli.common_args, &cli.client_config);
+    let socket_name = config.server.socket_name();
 
     match &cli.command {
         Some(Commands::Server { command }) => match command {
-            ServerCommands::Start { common_args } => start_daemonized(common_args),
-            ServerCommands::Stop => stop(),
-            ServerCommands::Status => status(),
+            // Already handled above
+            ServerCommands::Start { .. } => {}
+            ServerCommands::Stop => stop(&socket_name),
+            ServerCommands::Restart { common_args, force } => restart(common_args, *force),
+            ServerCommands::Reload => reload(&socket_name),
+            ServerCommands::Status { json, verbose } => status(&socket_name, *json, *verbose),
+            ServerCommands::Clients => clients(&socket_name),
+            ServerCommands::Doctor => doctor(&socket_name),
+            ServerCommands::Ping { count } => ping(&socket_name, *count),
+            ServerCommands::Logs { follow } => logs(*follow),
+        },
+
+        Some(Commands::Focus {
+            duration,
+            common_args,
+            force,
+        }) => start_one_shot(common_args, *duration, *force),
+
+        Some(Commands::Stopwatch { command }) => match command {
+            StopwatchCommands::Start { common_args, force } => start_stopwatch(common_args, *force),
+            StopwatchCommands::Stop => stop_stopwatch(&socket_name, config.view.silent),
         },
 
         Some(Commands::Postpone) => {
-            postpone(config.view.silent);
+            postpone(&socket_name, config.view.silent);
+        }
+
+        Some(Commands::Mute) => {
+            set_muted(&socket_name, config.view.silent, true);
+        }
+
+        Some(Commands::Unmute) => {
+            set_muted(&socket_name, config.view.silent, false);
         }
 
         Some(Commands::Once) => {
-            query_server_once();
+            query_server_once(&socket_name);
+        }
+
+        Some(Commands::Remaining { secs }) => {
+            remaining(&socket_name, *secs);
+        }
+
+        Some(Commands::Watch { format }) => {
+            watch(config, *format);
+        }
+
+        Some(Commands::FocusNow) => {
+            focus_now(&cli.common_args, config);
         }
 
         Some(Commands::ToggleTimer) => {
-            toggle_timer(config.view.silent);
+            toggle_timer(&socket_name, config.view.silent);
+        }
+
+        Some(Commands::Pause { duration }) => {
+            pause_for(&socket_name, config.view.silent, *duration);
         }
 
         Some(Commands::Skip) => {
-            skip_timer(config.view.silent);
+            skip_timer(&socket_name, config.view.silent);
         }
 
         Some(Commands::Reset) => {
-            reset_timer(config.view.silent);
+            reset_timer(&socket_name, config.view.silent);
+        }
+
+        Some(Commands::Previous) => {
+            previous_interval(&socket_name, config.view.silent);
         }
 
         Some(Commands::SetTimer { time }) => {
-            set_timer(config.view.silent, time.to_owned());
+            set_timer(&socket_name, config.view.silent, time.to_owned());
         }
 
-        None => default_cmd(&cli.common_args, config),
+        Some(Commands::Next { focus, break_ }) => {
+            set_next_interval(&socket_name, config.view.silent, *focus, *break_);
+        }
+
+        Some(Commands::Export { format, since }) => export(&config, *format, *since),
+
+        Some(Commands::Stats {
+            format,
+            since,
+            until,
+            label,
+        }) => stats(&config, *format, *since, *until, label.as_deref()),
+
+        Some(Commands::Config { command }) => match command {
+            ConfigCommands::Show { common_args } => {
+                show(&get_effective_config(common_args, &cli.client_config))
+            }
+            // Already handled above
+            ConfigCommands::Check { .. } => {}
+        },
+
+        #[cfg(feature = "ws")]
+        Some(Commands::Bridge {
+            ws_port,
+            allowed_origins,
+        }) => bridge(&socket_name, *ws_port, allowed_origins.clone()),
+
+        // Already handled above
+        Some(Commands::Completions { .. } | Commands::Sound { .. }) => {}
+
+        None => default_cmd(&cli.common_args, config, cli.attach_only, cli.readonly),
     }
 }
 
 /// Creates the config relevant for client side commands
-fn get_client_config(config_path: &str, client_config: &ClientConfig) -> Config {
-    create_base_config(config_path)
-        .merge(Serialized::defaults(client_config))
+fn get_client_config(common_args: &CommonArgs, client_config: &ClientConfig) -> Config {
+    merge_client_args(base_config_or_exit(common_args), common_args, client_config)
         .extract()
+        .map(validate_or_exit)
         .expect("Could not create config")
 }
+
+/// Creates the config, as it would be used by a server and a client started with the given args
+fn get_effective_config(common_args: &CommonArgs, client_config: &ClientConfig) -> Config {
+    merge_client_args(
+        base_config_or_exit(common_args).merge(Serialized::defaults(&common_args.server_config)),
+        common_args,
+        client_config,
+    )
+    .extract()
+    .map(validate_or_exit)
+    .expect("Could not create config")
+}
+
+/// Initializes the logger, which logs to stdout.
+/// `default_level` is used, unless a level is set via `RUST_LOG`.
+pub fn init_logger(default_level: &str) {
+    env_logger::Builder::from_env(Env::default().default_filter_or(default_level))
+        .target(env_logger::Target::Stdout)
+        .init();
+}
+
+/// Creates the base config for the given args (see [create_base_config]) and terminates the
+/// process, if the selected profile does not exist
+pub fn base_config_or_exit(common_args: &CommonArgs) -> Figment {
+    create_base_config(
+        common_args.config.as_deref(),
+        !common_args.no_local_config,
+        common_args.profile.as_deref(),
+    )
+    .unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    })
+}
+
+/// Connects to the server listening on `socket_name` (see [one_shot_connection]).
+/// If no server is running, this is reported and the process terminates successfully. Any other
+/// connection error terminates the process with a non-zero exit code.
+pub async fn connect_or_exit(socket_name: &str) -> (OwnedReadHalf, OwnedWriteHalf) {
+    match one_shot_connection(socket_name).await {
+        Ok(connection) => connection,
+        Err(ConnectionError::NoServerRunning) => {
+            println!("{}", ConnectionError::NoServerRunning);
+            std::process::exit(0);
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Terminates the process with a list of all problems, if the given config is invalid
+pub fn validate_or_exit(config: Config) -> Config {
+    if let Err(error) = config.validate() {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+
+    config
+}
+
+/// Merges all client side arguments over the given config
+fn merge_client_args(
+    figment: Figment,
+    common_args: &CommonArgs,
+    client_config: &ClientConfig,
+) -> Figment {
+    let figment = merge_server_args(
+        figment.merge(Serialized::defaults(client_config)),
+        common_args,
+    );
+
+    if client_config.view.no_reconnect {
+        return figment.merge(Serialized::default("view.reconnect_attempts", 0));
+    }
+
+    figment
+}
+
+/// Merges server related arguments like `--socket` (if provided) over the given config
+pub fn merge_server_args(figment: Figment, common_args: &CommonArgs) -> Figment {
+    let figment = match &common_args.socket {
+        Some(socket) => figment.merge(Serialized::default("server.socket_path", socket)),
+        None => figment,
+    };
+
+    let figment = match &common_args.log_level {
+        Some(log_level) => figment.merge(Serialized::default("server.log_level", log_level)),
+        None => figment,
+    };
+
+    let figment = match common_args.daily_goal {
+        Some(daily_goal) => figment.merge(Serialized::default("server.daily_goal", daily_goal)),
+        None => figment,
+    };
+
+    let figment = match common_args.pause_on_idle_secs {
+        Some(secs) => figment.merge(Serialized::default("server.pause_on_idle_secs", secs)),
+        None => figment,
+    };
+
+    let figment = match common_args.paused_backoff_secs {
+        Some(secs) => figment.merge(Serialized::default("server.paused_backoff_secs", secs)),
+        None => figment,
+    };
+
+    let figment = match common_args.paused_update_interval_secs {
+        Some(secs) => figment.merge(Serialized::default(
+            "server.paused_update_interval_secs",
+            secs,
+        )),
+        None => figment,
+    };
+
+    let figment = if common_args.start_running {
+        figment.merge(Serialized::default("server.start_running", true))
+    } else {
+        figment
+    };
+
+    // Per event overrides would otherwise still enable the bell/notification for their event
+    let figment = if common_args.no_bell {
+        [
+            "enable_bell",
+            "focus_end.bell",
+            "long_break.bell",
+            "break_end.bell",
+        ]
+        .into_iter()
+        .fold(figment, |figment, key| {
+            figment.merge(Serialized::default(
+                &format!("notifications.{}", key),
+                false,
+            ))
+        })
+    } else {
+        figment
+    };
+
+    let figment = if common_args.no_notification {
+        [
+            "show_notification",
+            "focus_end.notification",
+            "long_break.notification",
+            "break_end.notification",
+        ]
+        .into_iter()
+        .fold(figment, |figment, key| {
+            figment.merge(Serialized::default(
+                &format!("notifications.{}", key),
+                false,
+            ))
+        })
+    } else {
+        figment
+    };
+
+    #[cfg(feature = "http")]
+    let figment = match common_args.http_port {
+        Some(port) => figment.merge(Serialized::default("server.http_port", port)),
+        None => figment,
+    };
+
+    figment
+}
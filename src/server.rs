@@ -1,9 +1,23 @@
 //! Zentime server utilities
 
+mod ambient;
+mod clients;
+mod daily_goal;
+mod events;
+mod history;
+#[cfg(feature = "http")]
+mod http;
+mod idle;
 pub mod notification;
+mod paused_backoff;
+mod pid_file;
+mod runtime_state;
+mod schedule;
 pub mod sound;
 mod start;
+mod stats_recorder;
 pub mod status;
 mod timer_output;
+pub mod webhook;
 
-pub use start::start;
+pub use start::{start, ConfigLoader, TimerMode};
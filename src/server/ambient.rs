@@ -0,0 +1,178 @@
+//! Ambient sound (e.g. white noise), which is looped while a focus interval is running.
+//! Playback is only available if zentime has been built with the `audio` feature.
+
+#[cfg(feature = "audio")]
+use log::error;
+use log::info;
+#[cfg(feature = "audio")]
+use rodio::{Decoder, OutputStream, Sink, Source};
+#[cfg(feature = "audio")]
+use std::io::Cursor;
+#[cfg(feature = "audio")]
+use std::sync::mpsc::{channel, Receiver, Sender};
+#[cfg(feature = "audio")]
+use std::thread;
+#[cfg(feature = "audio")]
+use zentime_rs_timer::pomodoro_timer::TimerKind;
+use zentime_rs_timer::pomodoro_timer::ViewState;
+
+/// Playback state of the ambient sound, which is derived from the current timer
+#[cfg(feature = "audio")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Playback {
+    /// A focus interval is running
+    Playing,
+
+    /// The focus interval has been paused, which is why the loop continues where it left off
+    /// once it is resumed
+    Paused,
+
+    /// Any other timer (e.g. a break) - the loop starts from the beginning with the next interval
+    Stopped,
+}
+
+#[cfg(feature = "audio")]
+impl Playback {
+    fn of(state: &ViewState) -> Self {
+        match (state.kind, state.is_paused) {
+            (TimerKind::Interval, false) => Playback::Playing,
+            (TimerKind::Interval, true) => Playback::Paused,
+            _ => Playback::Stopped,
+        }
+    }
+}
+
+/// Loops the ambient sound on its own long-lived [Sink] (independent of the bell played back by
+/// [super::sound::SoundPlayer]), which follows the timer via [Self::update].
+///
+/// NOTE:
+/// The output stream can't be moved between threads, which is why playback happens on a
+/// dedicated thread, which lives as long as the player.
+#[cfg(feature = "audio")]
+#[derive(Debug)]
+pub struct AmbientSound {
+    playback: Playback,
+    playback_tx: Sender<Playback>,
+}
+
+/// Stand-in for the actual ambient sound, if zentime has been built without the `audio`
+/// feature. Nothing is played back in that case.
+#[cfg(not(feature = "audio"))]
+#[derive(Debug, Copy, Clone)]
+pub struct AmbientSound {}
+
+#[cfg(not(feature = "audio"))]
+impl AmbientSound {
+    /// Only logs that audio is unavailable
+    pub fn new(_sound_file: String) -> Self {
+        info!(
+            "Not playing ambient sound, because zentime has been built without the `audio` feature"
+        );
+        Self {}
+    }
+
+    /// Does nothing
+    pub fn update(&mut self, _state: &ViewState) {}
+}
+
+#[cfg(feature = "audio")]
+impl AmbientSound {
+    /// Creates a new (stopped) ambient sound, which loops the given sound file, and spawns its
+    /// playback thread
+    pub fn new(sound_file: String) -> Self {
+        let (playback_tx, playback_rx) = channel();
+
+        thread::spawn(move || play_back(sound_file, playback_rx));
+
+        Self {
+            playback: Playback::Stopped,
+            playback_tx,
+        }
+    }
+
+    /// Starts, pauses or stops the loop according to the given timer state.
+    /// This is meant to be called on every tick.
+    pub fn update(&mut self, state: &ViewState) {
+        let playback = Playback::of(state);
+        if playback == self.playback {
+            return;
+        }
+
+        info!("Ambient sound: {:?}", playback);
+        self.playback = playback;
+        self.playback_tx.send(playback).ok();
+    }
+}
+
+/// Follows incoming playback changes until the [AmbientSound] has been dropped
+#[cfg(feature = "audio")]
+fn play_back(sound_file: String, playback_rx: Receiver<Playback>) {
+    // Opened lazily, so that a missing output device doesn't matter until the first interval
+    let mut output: Option<(OutputStream, _)> = None;
+    let mut sink: Option<Sink> = None;
+
+    for playback in playback_rx {
+        match playback {
+            Playback::Playing => {
+                // Continue the paused loop
+                if let Some(sink) = &sink {
+                    sink.play();
+                    continue;
+                }
+
+                if output.is_none() {
+                    match OutputStream::try_default() {
+                        Ok(stream) => output = Some(stream),
+                        Err(error) => {
+                            error!("Could not play ambient sound: {}", error);
+                            continue;
+                        }
+                    }
+                }
+
+                let Some((_stream, stream_handle)) = &output else {
+                    continue;
+                };
+
+                // NOTE: The file is read for every interval, so that it might be replaced in the
+                // meantime
+                let audio = match decode(&sound_file) {
+                    Ok(audio) => audio,
+                    Err(error) => {
+                        error!("Could not play ambient sound {}: {}", sound_file, error);
+                        continue;
+                    }
+                };
+
+                match Sink::try_new(stream_handle) {
+                    Ok(new_sink) => {
+                        new_sink.append(audio.repeat_infinite());
+                        sink = Some(new_sink);
+                    }
+                    Err(error) => error!("Could not play ambient sound: {}", error),
+                }
+            }
+
+            Playback::Paused => {
+                if let Some(sink) = &sink {
+                    sink.pause();
+                }
+            }
+
+            // A stopped sink can't play back anything anymore, so the next interval needs a
+            // new one
+            Playback::Stopped => {
+                if let Some(sink) = sink.take() {
+                    sink.stop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+fn decode(sound_file: &str) -> Result<Decoder<Cursor<Vec<u8>>>, String> {
+    let bytes = std::fs::read(sound_file).map_err(|error| error.to_string())?;
+
+    Decoder::new(Cursor::new(bytes)).map_err(|error| error.to_string())
+}
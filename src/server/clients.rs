@@ -0,0 +1,65 @@
+//! Registry of all clients, which are currently connected to the server
+
+use crate::ipc::ClientInfo;
+use chrono::{Local, SubsecRound};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Shared between all connection tasks, which register themselves on accept
+#[derive(Debug, Default)]
+pub struct ClientRegistry {
+    last_id: AtomicU64,
+    clients: Mutex<Vec<ClientInfo>>,
+}
+
+impl ClientRegistry {
+    /// Registers a new connection with the next free id.
+    /// The client stays registered until the returned [ClientRegistration] is dropped.
+    pub fn register(self: &Arc<Self>) -> ClientRegistration {
+        let id = self.last_id.fetch_add(1, Ordering::Relaxed) + 1;
+
+        self.clients
+            .lock()
+            .expect("Client registry lock poisoned")
+            .push(ClientInfo {
+                id,
+                connected_at: Local::now().trunc_subsecs(0),
+            });
+
+        ClientRegistration {
+            id,
+            registry: self.clone(),
+        }
+    }
+}
+
+/// Registration of a single connection, which removes the client from the [ClientRegistry] once
+/// it is dropped (no matter whether the connection has been closed regularly or due to an error)
+#[derive(Debug)]
+pub struct ClientRegistration {
+    /// Id which has been assigned to the connection
+    pub id: u64,
+    registry: Arc<ClientRegistry>,
+}
+
+impl ClientRegistration {
+    /// Returns all other connected clients (in the order they have connected)
+    pub fn other_clients(&self) -> Vec<ClientInfo> {
+        self.registry
+            .clients
+            .lock()
+            .expect("Client registry lock poisoned")
+            .iter()
+            .filter(|client| client.id != self.id)
+            .copied()
+            .collect()
+    }
+}
+
+impl Drop for ClientRegistration {
+    fn drop(&mut self) {
+        if let Ok(mut clients) = self.registry.clients.lock() {
+            clients.retain(|client| client.id != self.id);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Tracking of completed focus intervals against a daily goal
+
+use crate::stats::{filter_by_day, read_records, StatsError, StatsSummary};
+use chrono::{Local, NaiveDate};
+use std::path::Path;
+
+/// Notification which is sent once the daily goal has been reached
+pub const GOAL_REACHED_NOTIFICATION: &str = "Daily goal reached - well done!";
+
+/// Counts the focus intervals completed on the current (local) day.
+/// The counter is reset automatically, as soon as the day changes.
+#[derive(Debug)]
+pub struct DailyGoal {
+    goal: Option<u64>,
+    completed: u64,
+    day: NaiveDate,
+}
+
+impl DailyGoal {
+    /// Creates a new tracker, which starts with the given number of intervals completed today
+    /// (e.g. read from the stats file via [completed_today])
+    pub fn new(goal: Option<u64>, completed: u64) -> Self {
+        Self {
+            goal,
+            completed,
+            day: today(),
+        }
+    }
+
+    /// Replaces the goal (e.g. after the config has been reloaded), while keeping the progress
+    pub fn set_goal(&mut self, goal: Option<u64>) {
+        self.goal = goal;
+    }
+
+    /// Records a completed focus interval.
+    /// Returns `true` if exactly this interval reached the goal, so that callers can celebrate
+    /// it exactly once.
+    pub fn record_completed_interval(&mut self) -> bool {
+        self.reset_on_new_day();
+        self.completed += 1;
+
+        self.goal == Some(self.completed)
+    }
+
+    /// Returns the current progress as (completed, goal) or `None` if no goal has been configured
+    pub fn progress(&mut self) -> Option<(u64, u64)> {
+        self.reset_on_new_day();
+
+        self.goal.map(|goal| (self.completed, goal))
+    }
+
+    fn reset_on_new_day(&mut self) {
+        let today = today();
+
+        if today != self.day {
+            self.day = today;
+            self.completed = 0;
+        }
+    }
+}
+
+/// Counts the focus intervals of the stats file at `path`, which have been started today.
+///
+/// WHY:
+/// The progress would otherwise be lost (and the goal be celebrated again) whenever the server
+/// is restarted during the day.
+pub fn completed_today(path: &Path) -> Result<u64, StatsError> {
+    let records = filter_by_day(read_records(path)?, Some(today()), Some(today()));
+
+    Ok(StatsSummary::from_records(&records).intervals)
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::{append_record, StatsRecord};
+    use chrono::{Duration, SubsecRound};
+    use std::env;
+    use std::fs::remove_file;
+    use zentime_rs_timer::pomodoro_timer::TimerKind;
+
+    #[test]
+    fn progress_is_seeded_from_todays_focus_intervals() {
+        let path = env::temp_dir().join(format!("zentime_goal_test_{}.jsonl", std::process::id()));
+        remove_file(&path).ok();
+
+        let now = Local::now().trunc_subsecs(0);
+        let record = |start, kind| StatsRecord {
+            start,
+            end: start,
+            kind,
+            round: 1,
+            distractions: 0,
+            label: None,
+        };
+        for record in [
+            record(now - Duration::days(1), TimerKind::Interval),
+            record(now, TimerKind::Interval),
+            record(now, TimerKind::Break),
+            record(now, TimerKind::Interval),
+        ] {
+            append_record(&path, &record).expect("Could not write record");
+        }
+
+        let completed = completed_today(&path).expect("Could not read stats file");
+        remove_file(&path).ok();
+
+        // NOTE: This would be off by one, if the test happened to run across midnight
+        assert_eq!(completed, 2);
+
+        let mut daily_goal = DailyGoal::new(Some(3), completed);
+        assert_eq!(daily_goal.progress(), Some((2, 3)));
+        assert!(daily_goal.record_completed_interval());
+        assert!(!daily_goal.record_completed_interval());
+    }
+}
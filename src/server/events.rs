@@ -0,0 +1,274 @@
+//! Emission of [TimerEvent]s at the transitions of the timer
+
+use crate::ipc::TimerEvent;
+use zentime_rs_timer::pomodoro_timer::{TimerKind, ViewState};
+
+/// Transition of the timer, whose resulting state is reported by the next tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transition {
+    /// A new timer has started (e.g. because the previous one has ended or has been reset),
+    /// even if it is of the same kind and round as the previous one
+    NewTimer,
+
+    /// An action has been handed to the timer (e.g. skip, postpone or play/pause), which the
+    /// current timer might also ignore
+    Action,
+}
+
+/// Emits [TimerEvent]s at the transitions of the timer.
+///
+/// Transitions are recorded where they happen (i.e. whenever a timer ends or an action is handed
+/// to the timer, see [EventEmitter::timer_started] and [EventEmitter::action_handled]). The event
+/// itself is emitted with the next tick, which carries the state resulting from the transition.
+#[derive(Debug, Default)]
+pub struct EventEmitter {
+    previous: Option<ViewState>,
+    pending: Option<Transition>,
+}
+
+impl EventEmitter {
+    /// Records that a new timer has started (e.g. because the previous one has ended or the
+    /// timer has been reset)
+    pub fn timer_started(&mut self) {
+        self.pending = Some(Transition::NewTimer);
+    }
+
+    /// Records that an action, which might transition the timer, has been handed to it
+    pub fn action_handled(&mut self) {
+        // NOTE: An unconditional transition must not be weakened by a following action
+        self.pending.get_or_insert(Transition::Action);
+    }
+
+    /// Returns the event of the pending transition (if any) on each tick
+    pub fn tick(&mut self, view_state: &ViewState) -> Option<TimerEvent> {
+        let event = match (self.previous.take(), self.pending.take()) {
+            // The very first state of the timer
+            (None, _) | (_, Some(Transition::NewTimer)) => Some(started_event(view_state)),
+            (Some(previous), Some(Transition::Action)) => action_event(&previous, view_state),
+            (Some(_), None) => None,
+        };
+
+        self.previous = Some(view_state.clone());
+
+        event
+    }
+}
+
+/// Event of an action, which may have transitioned the timer from the `previous` state into the
+/// `current` one (or may have been ignored by it)
+fn action_event(previous: &ViewState, current: &ViewState) -> Option<TimerEvent> {
+    let is_same_timer = previous.kind == current.kind
+        && previous.round == current.round
+        && previous.postpone_count == current.postpone_count;
+
+    if !is_same_timer {
+        return Some(started_event(current));
+    }
+
+    match (previous.is_paused, current.is_paused) {
+        (false, true) => Some(TimerEvent::TimerPaused),
+        (true, false) => Some(TimerEvent::TimerResumed),
+        _ => None,
+    }
+}
+
+/// Event of a timer, which has just been started
+fn started_event(view_state: &ViewState) -> TimerEvent {
+    if view_state.is_session_complete {
+        TimerEvent::SessionComplete {
+            rounds: view_state.round,
+        }
+    } else if view_state.is_break {
+        TimerEvent::BreakStarted {
+            round: view_state.round,
+            // NOTE: The kind is decided by the timer itself, which is why it stays correct
+            // even if `intervals` has been reloaded in the meantime
+            is_long: view_state.kind == TimerKind::LongBreak,
+        }
+    } else if view_state.is_postponed {
+        TimerEvent::BreakPostponed {
+            round: view_state.round,
+            postpone_count: view_state.postpone_count,
+        }
+    } else {
+        TimerEvent::IntervalStarted {
+            round: view_state.round,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(round: u64) -> ViewState {
+        ViewState {
+            kind: TimerKind::Interval,
+            round,
+            ..ViewState::default()
+        }
+    }
+
+    fn short_break(round: u64) -> ViewState {
+        ViewState {
+            kind: TimerKind::Break,
+            is_break: true,
+            round,
+            ..ViewState::default()
+        }
+    }
+
+    fn postponed_break(round: u64, postpone_count: u16) -> ViewState {
+        ViewState {
+            kind: TimerKind::PostponedBreak,
+            is_postponed: true,
+            postpone_count,
+            round,
+            ..ViewState::default()
+        }
+    }
+
+    fn long_break(round: u64) -> ViewState {
+        ViewState {
+            kind: TimerKind::LongBreak,
+            ..short_break(round)
+        }
+    }
+
+    fn paused(view_state: ViewState) -> ViewState {
+        ViewState {
+            is_paused: true,
+            ..view_state
+        }
+    }
+
+    /// Ticks the first state, ends its timer and returns the event of the next tick
+    fn timer_end(from: &ViewState, to: &ViewState) -> Option<TimerEvent> {
+        let mut emitter = EventEmitter::default();
+        emitter.tick(from);
+        emitter.timer_started();
+        emitter.tick(to)
+    }
+
+    /// Ticks the first state, hands an action to the timer and returns the event of the next tick
+    fn action(from: &ViewState, to: &ViewState) -> Option<TimerEvent> {
+        let mut emitter = EventEmitter::default();
+        emitter.tick(from);
+        emitter.action_handled();
+        emitter.tick(to)
+    }
+
+    #[test]
+    fn first_state_and_ticks_without_transition() {
+        let mut emitter = EventEmitter::default();
+
+        assert_eq!(
+            emitter.tick(&interval(1)),
+            Some(TimerEvent::IntervalStarted { round: 1 })
+        );
+        assert_eq!(emitter.tick(&interval(1)), None);
+    }
+
+    #[test]
+    fn pausing_and_resuming() {
+        assert_eq!(
+            action(&interval(1), &paused(interval(1))),
+            Some(TimerEvent::TimerPaused)
+        );
+        assert_eq!(
+            action(&paused(interval(1)), &interval(1)),
+            Some(TimerEvent::TimerResumed)
+        );
+        // Pausing a postponed break is no transition into another timer
+        assert_eq!(
+            action(&postponed_break(2, 1), &paused(postponed_break(2, 1))),
+            Some(TimerEvent::TimerPaused)
+        );
+    }
+
+    #[test]
+    fn ignored_actions_emit_nothing() {
+        // e.g. a focus interval can't be postponed
+        assert_eq!(action(&interval(2), &interval(2)), None);
+    }
+
+    #[test]
+    fn reset_within_the_same_round() {
+        let mut emitter = EventEmitter::default();
+        emitter.tick(&interval(1));
+        emitter.tick(&interval(1));
+
+        emitter.timer_started();
+        // An action of the same tick doesn't turn the reset into a mere action
+        emitter.action_handled();
+
+        assert_eq!(
+            emitter.tick(&paused(interval(1))),
+            Some(TimerEvent::IntervalStarted { round: 1 })
+        );
+        assert_eq!(emitter.tick(&paused(interval(1))), None);
+    }
+
+    #[test]
+    fn long_breaks_are_told_apart_by_their_kind() {
+        assert_eq!(
+            timer_end(&interval(3), &short_break(3)),
+            Some(TimerEvent::BreakStarted {
+                round: 3,
+                is_long: false
+            })
+        );
+        assert_eq!(
+            timer_end(&interval(4), &long_break(4)),
+            Some(TimerEvent::BreakStarted {
+                round: 4,
+                is_long: true
+            })
+        );
+        assert_eq!(
+            timer_end(&long_break(4), &interval(5)),
+            Some(TimerEvent::IntervalStarted { round: 5 })
+        );
+        // Skipping a focus interval
+        assert_eq!(
+            action(&interval(5), &short_break(5)),
+            Some(TimerEvent::BreakStarted {
+                round: 5,
+                is_long: false
+            })
+        );
+    }
+
+    #[test]
+    fn postponed_breaks() {
+        assert_eq!(
+            action(&short_break(2), &postponed_break(2, 1)),
+            Some(TimerEvent::BreakPostponed {
+                round: 2,
+                postpone_count: 1
+            })
+        );
+        // The break starts once the postponed break has ended
+        assert_eq!(
+            timer_end(&postponed_break(2, 1), &short_break(2)),
+            Some(TimerEvent::BreakStarted {
+                round: 2,
+                is_long: false
+            })
+        );
+    }
+
+    #[test]
+    fn completed_session() {
+        let complete = ViewState {
+            kind: TimerKind::SessionComplete,
+            is_session_complete: true,
+            ..interval(4)
+        };
+
+        assert_eq!(
+            timer_end(&short_break(4), &complete),
+            Some(TimerEvent::SessionComplete { rounds: 4 })
+        );
+    }
+}
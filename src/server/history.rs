@@ -0,0 +1,41 @@
+//! Timeline of the timers completed during the current session
+
+use std::collections::VecDeque;
+use zentime_rs_timer::pomodoro_timer::TimerKind;
+
+/// Maximum number of segments which are kept.
+/// WHY: The history is sent to clients with every tick, and IPC messages have a limited size.
+const MAX_HISTORY_LENGTH: usize = 32;
+
+/// Kinds of the most recently completed timers (oldest first).
+/// Skipped timers are not recorded.
+#[derive(Debug, Default)]
+pub struct SessionHistory {
+    segments: VecDeque<TimerKind>,
+}
+
+impl SessionHistory {
+    /// Records a completed timer of the given kind
+    pub fn record(&mut self, kind: TimerKind) {
+        if self.segments.len() == MAX_HISTORY_LENGTH {
+            self.segments.pop_front();
+        }
+
+        self.segments.push_back(kind);
+    }
+
+    /// Removes the most recent segment, because the timer has been moved back to repeat it
+    pub fn rewind(&mut self) {
+        self.segments.pop_back();
+    }
+
+    /// Removes all recorded segments (e.g. because the timer has been reset)
+    pub fn clear(&mut self) {
+        self.segments.clear();
+    }
+
+    /// Returns all recorded segments (oldest first)
+    pub fn segments(&self) -> Vec<TimerKind> {
+        self.segments.iter().copied().collect()
+    }
+}
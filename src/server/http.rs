@@ -0,0 +1,108 @@
+//! Minimal HTTP endpoint, which exposes the current timer state to integrations that can't use
+//! the local socket (e.g. status bars or home automation).
+
+use crate::server::timer_output::TimerOutputAction;
+use anyhow::Context;
+use log::{error, info};
+use std::sync::{Arc, RwLock};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::{error::RecvError, Receiver as BroadcastReceiver};
+use zentime_rs_timer::pomodoro_timer::ViewState;
+
+type SharedViewState = Arc<RwLock<Option<ViewState>>>;
+
+/// Serves `GET /status` on `127.0.0.1:<port>`, which responds with the latest [ViewState] as JSON.
+/// The state is kept up to date by listening to the same timer output as all socket clients.
+pub async fn serve(
+    port: u16,
+    mut timer_output_receiver: BroadcastReceiver<TimerOutputAction>,
+) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .with_context(|| format!("Could not bind HTTP status endpoint to port {}", port))?;
+
+    info!("Serving HTTP status endpoint on port {}", port);
+
+    let view_state: SharedViewState = Arc::new(RwLock::new(None));
+
+    let latest_view_state = view_state.clone();
+    tokio::spawn(async move {
+        loop {
+            match timer_output_receiver.recv().await {
+                Ok(TimerOutputAction::Timer(state)) => {
+                    *latest_view_state.write().expect("View state lock poisoned") = Some(state);
+                }
+                Ok(TimerOutputAction::Event(_)) => continue,
+                // We only care about the latest state, so missed updates are irrelevant
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .context("There was an error with an incoming HTTP connection")?;
+
+        let view_state = view_state.clone();
+        tokio::spawn(async move {
+            if let Err(error) = handle_request(stream, view_state).await {
+                error!("Could not handle HTTP request: {}", error);
+            }
+        });
+    }
+}
+
+async fn handle_request(stream: TcpStream, view_state: SharedViewState) -> anyhow::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .await
+        .context("Could not read HTTP request")?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+
+    let (status, body) = match (method, path) {
+        ("GET", "/status") => {
+            let state = view_state.read().expect("View state lock poisoned").clone();
+
+            match state {
+                Some(state) => (
+                    "200 OK",
+                    serde_json::to_string(&state).context("Could not serialize view state")?,
+                ),
+                None => (
+                    "503 Service Unavailable",
+                    r#"{"error":"Timer state not available yet"}"#.to_string(),
+                ),
+            }
+        }
+        ("GET", _) => ("404 Not Found", r#"{"error":"Not found"}"#.to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            r#"{"error":"Method not allowed"}"#.to_string(),
+        ),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+
+    writer
+        .write_all(response.as_bytes())
+        .await
+        .context("Could not write HTTP response")?;
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Automatic pausing of focus intervals, while no client input has been received
+
+use std::time::{Duration, Instant};
+use zentime_rs_timer::pomodoro_timer::ViewState;
+use zentime_rs_timer::pomodoro_timer_action::PomodoroTimerAction;
+
+/// Pauses a running focus interval, once no input has been received for a given time, and
+/// resumes it on the next input. Breaks are never paused.
+#[derive(Debug)]
+pub struct IdlePause {
+    timeout: Duration,
+
+    /// Set while the timer has been paused because of inactivity
+    paused_at: Option<Instant>,
+}
+
+impl IdlePause {
+    /// Creates a new idle pause, which pauses after `timeout` without any input
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            paused_at: None,
+        }
+    }
+
+    /// Replaces the timeout (e.g. after the config has been reloaded)
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Determines if the timer has to be paused or resumed, given its current state and the time
+    /// of the last input. Returns [PomodoroTimerAction::PlayPause] in both cases.
+    pub fn action(&mut self, view_state: &ViewState, last_input: Instant) -> PomodoroTimerAction {
+        if let Some(paused_at) = self.paused_at {
+            // The timer has been resumed manually or moved on in the meantime
+            if !view_state.is_paused || view_state.is_break {
+                self.paused_at = None;
+                return PomodoroTimerAction::None;
+            }
+
+            if last_input > paused_at {
+                self.paused_at = None;
+                return PomodoroTimerAction::PlayPause;
+            }
+
+            return PomodoroTimerAction::None;
+        }
+
+        if !view_state.is_paused && !view_state.is_break && last_input.elapsed() >= self.timeout {
+            self.paused_at = Some(Instant::now());
+            return PomodoroTimerAction::PlayPause;
+        }
+
+        PomodoroTimerAction::None
+    }
+}
@@ -1,13 +1,17 @@
 //! OS-Notification and sound playback related functions.
 
-use super::sound::{play, AudioPlaybackError};
-use crate::config::NotificationConfig;
+use super::sound::{AudioPlaybackError, SoundPlayer};
+use crate::config::{NotificationConfig, NotificationEvent, NotificationUrgency};
 use anyhow::bail;
-use log::error;
-use notify_rust::{Notification, NotificationHandle};
+use log::{error, info};
+use notify_rust::{Notification, NotificationHandle, Timeout};
 use rand::{seq::SliceRandom, thread_rng};
+use std::collections::HashMap;
 use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
+use zentime_rs_timer::pomodoro_timer_action::PomodoroTimerAction;
 
 /// Something went wrong during notification dispatch
 #[derive(Debug, Error)]
@@ -24,31 +28,157 @@ pub enum NotificationDispatchError {
     OperatingSystemNotification(#[from] anyhow::Error),
 }
 
-/// Play a sound file and send an OS-notification.
+/// Remembers when the last notification of each [NotificationEvent] has been dispatched, so that
+/// notifications following in quick succession can be suppressed (see `notification_debounce_ms`).
+///
+/// WHY:
+/// Different events (e.g. [NotificationEvent::GoalReached] right after
+/// [NotificationEvent::FocusEnd]) are debounced independently, because they carry different
+/// information.
+#[derive(Debug, Default)]
+pub struct NotificationDebounce {
+    last_dispatch: Mutex<HashMap<NotificationEvent, Instant>>,
+}
+
+impl NotificationDebounce {
+    /// Denotes if a notification of the given event may be dispatched right now and records its
+    /// dispatch, if so. Suppressed notifications don't extend the window.
+    fn should_dispatch(&self, event: NotificationEvent, window: Duration) -> bool {
+        let now = Instant::now();
+        let mut last_dispatch = self
+            .last_dispatch
+            .lock()
+            .expect("Notification debounce lock poisoned");
+
+        if last_dispatch
+            .get(&event)
+            .is_some_and(|last_dispatch| now.duration_since(*last_dispatch) < window)
+        {
+            return false;
+        }
+
+        last_dispatch.insert(event, now);
+        true
+    }
+}
+
+/// Remembers the last OS-notification with "Skip" and "Postpone" buttons, whose actions are still
+/// awaited on a separate thread (see [dispatch_notification]).
+///
+/// WHY:
+/// Waiting for an action only ends once the notification has been closed. The previous
+/// notification is therefore closed, as soon as the next one is shown, so that notifications
+/// which are never closed by the user don't keep their threads alive forever.
+#[derive(Debug, Default, Clone)]
+pub struct PendingBreakActions {
+    // NOTE: The notification itself is required to close it (see [PendingBreakActions::close])
+    #[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(dead_code))]
+    pending: Arc<Mutex<Option<(u32, Notification)>>>,
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl PendingBreakActions {
+    /// Closes the pending notification (if any), which also ends the thread waiting for its
+    /// actions
+    fn close(&self) {
+        let pending = self
+            .pending
+            .lock()
+            .expect("Pending break actions lock poisoned")
+            .take();
+
+        // WHY:
+        // The handle of the notification is owned by the waiting thread. Showing the same
+        // notification with its id again only replaces it, but returns a handle which can close it.
+        if let Some((id, mut notification)) = pending {
+            match notification.id(id).show() {
+                Ok(handle) => handle.close(),
+                Err(error) => error!("Could not close previous notification: {:?}", error),
+            }
+        }
+    }
+
+    /// Remembers the notification with the given id as pending
+    fn set(&self, id: u32, notification: Notification) {
+        *self
+            .pending
+            .lock()
+            .expect("Pending break actions lock poisoned") = Some((id, notification));
+    }
+
+    /// Forgets the notification with the given id, once it has been closed (e.g. by the user)
+    fn remove(&self, id: u32) {
+        let mut pending = self
+            .pending
+            .lock()
+            .expect("Pending break actions lock poisoned");
+
+        if pending
+            .as_ref()
+            .is_some_and(|(pending_id, _)| *pending_id == id)
+        {
+            *pending = None;
+        }
+    }
+}
+
+/// Play the sound file configured for the given event via the given player and send an
+/// OS-notification.
+/// A random break suggestion is added to the notification, if the event starts a break.
+/// Nothing is dispatched, if the previous notification of the same event has been dispatched
+/// within `notification_debounce_ms`.
+///
+/// NOTE: The `webhook_url` is not called by this function (see
+/// [super::webhook::notify_webhook]).
+///
+/// Notifications of events which start a break also offer "Skip" and "Postpone" buttons (if the
+/// notification server supports actions). `on_action` is called with the matching
+/// [PomodoroTimerAction], once one of these buttons has been clicked. The previous notification
+/// with these buttons is closed (see [PendingBreakActions]).
 pub fn dispatch_notification(
     config: NotificationConfig,
+    player: &SoundPlayer,
+    debounce: &NotificationDebounce,
+    pending_actions: &PendingBreakActions,
     notification_string: Option<&str>,
-    should_show_suggestion: bool,
+    event: NotificationEvent,
+    on_action: impl FnOnce(PomodoroTimerAction) + Send + 'static,
 ) -> Result<(), NotificationDispatchError> {
-    if config.enable_bell {
-        play(config.sound_file, config.volume)?;
+    if !debounce.should_dispatch(
+        event,
+        Duration::from_millis(config.notification_debounce_ms),
+    ) {
+        info!("Suppressing notification of {:?} (debounced)", event);
+        return Ok(());
     }
 
-    if !config.show_notification || notification_string.is_none() {
+    if config.bell_for(event) {
+        player.play(
+            config.sound_for(event),
+            config.volume,
+            config.fade_in_ms,
+            config.bell_repeat,
+            config.bell_repeat_gap_ms,
+        )?;
+    }
+
+    let should_show_suggestion = matches!(
+        event,
+        NotificationEvent::FocusEnd | NotificationEvent::LongBreak
+    );
+
+    if !config.notification_for(event) || notification_string.is_none() {
         return Ok(());
     };
 
     let mut notification = notification_string.unwrap().to_string();
 
     if !should_show_suggestion {
-        send(&notification)?;
+        send(&config, &notification, event)?;
         return Ok(());
     }
 
-    let suggestions = match config.break_suggestions {
-        Some(suggestions) => suggestions,
-        None => vec![],
-    };
+    let suggestions = config.break_suggestions.as_deref().unwrap_or_default();
 
     let random_suggestion = suggestions.choose(&mut thread_rng());
 
@@ -61,18 +191,141 @@ pub fn dispatch_notification(
         };
     }
 
-    send(&notification)?;
+    send_with_break_actions(&config, &notification, event, pending_actions, on_action)?;
+
+    Ok(())
+}
+
+/// Send an OS-notification with "Skip" and "Postpone" buttons and call `on_action` on a separate
+/// thread, once one of them has been clicked.
+/// Falls back to a plain notification, if the notification server does not support actions.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn send_with_break_actions(
+    config: &NotificationConfig,
+    message: &str,
+    event: NotificationEvent,
+    pending_actions: &PendingBreakActions,
+    on_action: impl FnOnce(PomodoroTimerAction) + Send + 'static,
+) -> anyhow::Result<()> {
+    let supports_actions = notify_rust::get_capabilities()
+        .map(|capabilities| {
+            capabilities
+                .iter()
+                .any(|capability| capability == "actions")
+        })
+        .unwrap_or(false);
+
+    if !supports_actions {
+        send(config, message, event)?;
+        return Ok(());
+    }
+
+    let mut notification = notification(config, message, event);
+    notification
+        .action("skip", "Skip")
+        .action("postpone", "Postpone");
+
+    pending_actions.close();
+
+    let handle = match notification.show() {
+        Ok(handle) => handle,
+        Err(error) => {
+            error!("Error on notification: {:?}", error);
+            bail!(error)
+        }
+    };
+
+    let id = handle.id();
+    pending_actions.set(id, notification);
+    let pending_actions = pending_actions.clone();
+
+    // WHY:
+    // Waiting for an action blocks until the notification has been closed, which must neither
+    // delay the timer nor any following notification.
+    std::thread::spawn(move || {
+        handle.wait_for_action(|action| match action {
+            "skip" => on_action(PomodoroTimerAction::Skip),
+            "postpone" => on_action(PomodoroTimerAction::PostponeBreak),
+            _ => (),
+        });
+        pending_actions.remove(id);
+    });
 
     Ok(())
 }
 
+/// Send an OS-notification.
+/// Action buttons are only supported by XDG notification servers, which is why `on_action` is
+/// never called on this platform.
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn send_with_break_actions(
+    config: &NotificationConfig,
+    message: &str,
+    event: NotificationEvent,
+    _pending_actions: &PendingBreakActions,
+    _on_action: impl FnOnce(PomodoroTimerAction) + Send + 'static,
+) -> anyhow::Result<()> {
+    send(config, message, event)?;
+    Ok(())
+}
+
+/// Summary of all OS-notifications, if none has been configured
+const DEFAULT_SUMMARY: &str = "\u{25EF} zentime";
+
+/// Creates an OS-notification with the configured summary, icon, urgency and timeout
+fn notification(
+    config: &NotificationConfig,
+    message: &str,
+    event: NotificationEvent,
+) -> Notification {
+    let mut notification = Notification::new();
+    notification
+        .summary(
+            config
+                .notification_summary
+                .as_deref()
+                .unwrap_or(DEFAULT_SUMMARY),
+        )
+        .body(message);
+
+    if let Some(icon) = &config.notification_icon {
+        notification.icon(&shellexpand::tilde(icon.trim()));
+    }
+
+    if let Some(timeout_ms) = config.notification_timeout_ms {
+        notification.timeout(match timeout_ms {
+            0 => Timeout::Never,
+            timeout_ms => Timeout::Milliseconds(timeout_ms),
+        });
+    }
+
+    set_urgency(&mut notification, config.urgency_for(event));
+
+    notification
+}
+
+/// Sets the urgency hint of the notification
+#[cfg(all(unix, not(target_os = "macos")))]
+fn set_urgency(notification: &mut Notification, urgency: NotificationUrgency) {
+    notification.urgency(match urgency {
+        NotificationUrgency::Low => notify_rust::Urgency::Low,
+        NotificationUrgency::Normal => notify_rust::Urgency::Normal,
+        NotificationUrgency::Critical => notify_rust::Urgency::Critical,
+    });
+}
+
+/// Urgency hints are only supported by XDG notification servers, which is why the urgency is
+/// ignored on this platform.
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn set_urgency(_notification: &mut Notification, _urgency: NotificationUrgency) {}
+
 /// Send a OS-notificaion
-fn send(message: &str) -> anyhow::Result<NotificationHandle> {
-    match Notification::new()
-        .summary("\u{25EF} zentime")
-        .body(message)
-        .show()
-    {
+fn send(
+    config: &NotificationConfig,
+    message: &str,
+    event: NotificationEvent,
+) -> anyhow::Result<NotificationHandle> {
+    match notification(config, message, event).show() {
         Ok(handle) => Ok(handle),
         Err(error) => {
             // Currently show() will always return ok() (as per the definition of)
@@ -83,3 +336,31 @@ fn send(message: &str) -> anyhow::Result<NotificationHandle> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn debounces_repeated_events() {
+        let debounce = NotificationDebounce::default();
+
+        assert!(debounce.should_dispatch(NotificationEvent::FocusEnd, WINDOW));
+        assert!(!debounce.should_dispatch(NotificationEvent::FocusEnd, WINDOW));
+
+        // Without a window nothing is debounced
+        assert!(debounce.should_dispatch(NotificationEvent::FocusEnd, Duration::ZERO));
+    }
+
+    #[test]
+    fn different_events_back_to_back_are_dispatched() {
+        let debounce = NotificationDebounce::default();
+
+        assert!(debounce.should_dispatch(NotificationEvent::FocusEnd, WINDOW));
+        assert!(debounce.should_dispatch(NotificationEvent::GoalReached, WINDOW));
+        assert!(debounce.should_dispatch(NotificationEvent::ScheduledStart, WINDOW));
+        assert!(!debounce.should_dispatch(NotificationEvent::GoalReached, WINDOW));
+    }
+}
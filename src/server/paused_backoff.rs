@@ -0,0 +1,164 @@
+//! Throttling of view updates, while the timer has been paused for a while
+
+use std::time::{Duration, Instant};
+use zentime_rs_timer::pomodoro_timer::ViewState;
+
+/// Limits the view updates of a timer, which has been paused for longer than `after`, to one
+/// update every `interval`. Changed states (e.g. a new label) and newly connected clients are
+/// still updated right away.
+#[derive(Debug)]
+pub struct PausedBackoff {
+    after: Duration,
+    interval: Duration,
+
+    /// Set while the timer is paused
+    paused_at: Option<Instant>,
+
+    /// Last view state which has been sent and the time at which it has been sent
+    last_sent: Option<(ViewState, Instant)>,
+
+    /// Number of receivers at the time of the last tick
+    receivers: usize,
+}
+
+impl PausedBackoff {
+    /// Creates a new backoff, which starts `after` the timer has been paused and then only lets
+    /// an unchanged view state through once per `interval`
+    pub fn new(after: Duration, interval: Duration) -> Self {
+        Self {
+            after,
+            interval,
+            paused_at: None,
+            last_sent: None,
+            receivers: 0,
+        }
+    }
+
+    /// Determines if the given view state should be sent to the `receivers` (number of
+    /// subscribed connections). This is meant to be called on every tick.
+    pub fn should_send(&mut self, view_state: &ViewState, receivers: usize, now: Instant) -> bool {
+        let is_backing_off = match (view_state.is_paused, self.paused_at) {
+            (false, _) => {
+                self.paused_at = None;
+                false
+            }
+            (true, None) => {
+                self.paused_at = Some(now);
+                false
+            }
+            (true, Some(paused_at)) => now.saturating_duration_since(paused_at) >= self.after,
+        };
+
+        // WHY:
+        // A new connection (e.g. `zentime query`) waits for the next view state, which is why it
+        // should not have to wait for the next interval.
+        let has_new_receivers = receivers > self.receivers;
+        self.receivers = receivers;
+
+        let is_due = match &self.last_sent {
+            Some((last_state, sent_at)) => {
+                last_state != view_state || now.saturating_duration_since(*sent_at) >= self.interval
+            }
+            None => true,
+        };
+
+        if is_backing_off && !has_new_receivers && !is_due {
+            return false;
+        }
+
+        self.last_sent = Some((view_state.clone(), now));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view_state(is_paused: bool) -> ViewState {
+        ViewState {
+            round: 1,
+            intervals_until_long_break: 3,
+            time: String::from("25:00"),
+            remaining_secs: 1500,
+            is_paused,
+            ..ViewState::default()
+        }
+    }
+
+    /// Sends the state on ticks every 100ms for the given duration and returns the number of
+    /// sent updates
+    fn sent_updates(
+        backoff: &mut PausedBackoff,
+        state: &ViewState,
+        start: Instant,
+        duration: Duration,
+    ) -> usize {
+        let ticks = (duration.as_millis() / 100) as u32;
+
+        (0..ticks)
+            .filter(|tick| {
+                backoff.should_send(state, 1, start + Duration::from_millis(100) * *tick)
+            })
+            .count()
+    }
+
+    #[test]
+    fn paused_timer_backs_off_after_a_while() {
+        let mut backoff = PausedBackoff::new(Duration::from_secs(10), Duration::from_secs(5));
+        let start = Instant::now();
+
+        assert_eq!(
+            sent_updates(
+                &mut backoff,
+                &view_state(true),
+                start,
+                Duration::from_secs(10)
+            ),
+            100
+        );
+        assert_eq!(
+            sent_updates(
+                &mut backoff,
+                &view_state(true),
+                start + Duration::from_secs(10),
+                Duration::from_secs(20)
+            ),
+            4
+        );
+    }
+
+    #[test]
+    fn running_timer_and_changes_are_sent_right_away() {
+        let mut backoff = PausedBackoff::new(Duration::from_secs(10), Duration::from_secs(5));
+        let start = Instant::now();
+
+        sent_updates(
+            &mut backoff,
+            &view_state(true),
+            start,
+            Duration::from_secs(20),
+        );
+
+        let now = start + Duration::from_secs(20);
+        let labeled = ViewState {
+            label: Some(String::from("Writing docs")),
+            ..view_state(true)
+        };
+        assert!(backoff.should_send(&labeled, 1, now));
+        assert!(!backoff.should_send(&labeled, 1, now + Duration::from_millis(100)));
+
+        // A newly connected client
+        assert!(backoff.should_send(&labeled, 2, now + Duration::from_millis(200)));
+
+        assert_eq!(
+            sent_updates(
+                &mut backoff,
+                &view_state(false),
+                now + Duration::from_millis(300),
+                Duration::from_secs(20)
+            ),
+            200
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! PID file of the server, which is written next to its socket, so that files left behind by a
+//! crashed server can be detected and cleaned up on the next start
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use sysinfo::{Pid, PidExt, ProcessExt, System, SystemExt};
+
+/// Path of the PID file belonging to the server listening on `socket_name`, e.g.
+/// `/tmp/zentime.sock.pid`.
+/// Namespaced sockets don't have a directory, which is why their PID file is placed inside the
+/// temp dir instead.
+pub fn pid_file_path(socket_name: &str) -> PathBuf {
+    match socket_name.strip_prefix('@') {
+        Some(name) => env::temp_dir().join(format!("{}.pid", name)),
+        None => PathBuf::from(format!("{}.pid", socket_name)),
+    }
+}
+
+/// Writes the id of the current process into the PID file
+pub fn write_pid_file(socket_name: &str) -> io::Result<()> {
+    fs::write(pid_file_path(socket_name), std::process::id().to_string())
+}
+
+/// Removes the PID file (if it exists)
+pub fn remove_pid_file(socket_name: &str) -> io::Result<()> {
+    match fs::remove_file(pid_file_path(socket_name)) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// Removes the PID file and the socket file, if the PID file refers to a process which is not
+/// running anymore (e.g. because the server has crashed).
+/// Returns whether anything has been cleaned up.
+pub fn remove_orphaned_files(socket_name: &str) -> io::Result<bool> {
+    let pid = match fs::read_to_string(pid_file_path(socket_name)) {
+        Ok(pid) => pid.trim().parse::<u32>().ok(),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(error) => return Err(error),
+    };
+
+    // NOTE: A broken PID file can't refer to any server either
+    if pid.is_some_and(is_zentime_process) {
+        return Ok(false);
+    }
+
+    remove_pid_file(socket_name)?;
+
+    // NOTE: Namespaced sockets don't have a file which could be removed
+    if Path::new(socket_name).exists() {
+        fs::remove_file(socket_name)?;
+    }
+
+    Ok(true)
+}
+
+/// Checks if a zentime process with the given id is running.
+///
+/// WHY:
+/// The id of a crashed server might have been reused by an unrelated process in the meantime.
+fn is_zentime_process(pid: u32) -> bool {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+
+    system.refresh_process(pid)
+        && system
+            .process(pid)
+            .is_some_and(|process| process.name().contains("zentime"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Socket path inside the temp dir, which is unique for each test
+    fn socket_name(test: &str) -> String {
+        env::temp_dir()
+            .join(format!(
+                "zentime_pid_file_{}_{}.sock",
+                test,
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn orphaned_pid_and_socket_files_are_removed() {
+        let socket_name = socket_name("orphaned");
+        // Exceeds the maximum pid of every platform, so that no such process can exist
+        fs::write(pid_file_path(&socket_name), u32::MAX.to_string()).unwrap();
+        fs::write(&socket_name, "").unwrap();
+
+        assert!(remove_orphaned_files(&socket_name).unwrap());
+        assert!(!pid_file_path(&socket_name).exists());
+        assert!(!Path::new(&socket_name).exists());
+    }
+
+    #[test]
+    fn files_of_a_running_process_are_kept() {
+        let socket_name = socket_name("running");
+        write_pid_file(&socket_name).unwrap();
+        fs::write(&socket_name, "").unwrap();
+
+        // The test binary itself is a running zentime process
+        assert!(!remove_orphaned_files(&socket_name).unwrap());
+        assert!(pid_file_path(&socket_name).exists());
+        assert!(Path::new(&socket_name).exists());
+
+        remove_pid_file(&socket_name).unwrap();
+        fs::remove_file(&socket_name).unwrap();
+    }
+
+    #[test]
+    fn nothing_is_removed_without_pid_file() {
+        let socket_name = socket_name("missing");
+        fs::write(&socket_name, "").unwrap();
+
+        assert!(!remove_orphaned_files(&socket_name).unwrap());
+        assert!(Path::new(&socket_name).exists());
+
+        fs::remove_file(&socket_name).unwrap();
+    }
+}
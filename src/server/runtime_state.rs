@@ -0,0 +1,247 @@
+//! State of the server, which may be changed by clients at runtime
+
+use crate::config::{Config, NotificationConfig};
+use crate::ipc::ServerInfo;
+use chrono::{DateTime, Local, SubsecRound};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+use tokio::task::JoinHandle;
+use zentime_rs_timer::config::PomodoroTimerConfig;
+use zentime_rs_timer::pomodoro_timer::{TimerKind, ViewState};
+
+/// Kind and round of the current timer together with the time at which it has first been running
+/// and its label
+#[derive(Debug, Clone)]
+struct CurrentTimer {
+    timer: (TimerKind, u64),
+    started_at: Option<DateTime<Local>>,
+    label: Option<String>,
+}
+
+/// Runtime state shared between the timer thread and all client connections
+#[derive(Debug)]
+pub struct RuntimeState {
+    muted: AtomicBool,
+    last_input: Mutex<Instant>,
+    paused: AtomicBool,
+
+    /// Task which resumes the timer after a timed pause (see [ClientToServerMsg::PauseFor])
+    ///
+    /// [ClientToServerMsg::PauseFor]: crate::ipc::ClientToServerMsg::PauseFor
+    scheduled_resume: Mutex<Option<JoinHandle<()>>>,
+
+    started_at: DateTime<Local>,
+    current_timer: Mutex<Option<CurrentTimer>>,
+
+    /// Configuration of notifications, which can be reloaded (see [ClientToServerMsg::Reload])
+    ///
+    /// [ClientToServerMsg::Reload]: crate::ipc::ClientToServerMsg::Reload
+    notifications: Mutex<NotificationConfig>,
+
+    /// Number of focus intervals until a long break (see `timers.intervals`), which can be
+    /// reloaded as well
+    intervals: AtomicU64,
+
+    /// Daily goal (see `server.daily_goal`), which can be reloaded as well
+    daily_goal: Mutex<Option<u64>>,
+
+    /// Idle timeout (see `server.pause_on_idle_secs`), which can be reloaded as well
+    pause_on_idle_secs: Mutex<Option<u64>>,
+}
+
+impl Default for RuntimeState {
+    fn default() -> Self {
+        Self {
+            muted: AtomicBool::new(false),
+            last_input: Mutex::new(Instant::now()),
+            paused: AtomicBool::new(true),
+            scheduled_resume: Mutex::new(None),
+            started_at: Local::now().trunc_subsecs(0),
+            current_timer: Mutex::new(None),
+            notifications: Mutex::new(NotificationConfig::default()),
+            intervals: AtomicU64::new(PomodoroTimerConfig::default().intervals),
+            daily_goal: Mutex::new(None),
+            pause_on_idle_secs: Mutex::new(None),
+        }
+    }
+}
+
+impl RuntimeState {
+    /// Denotes if notifications (bell and OS-notification) are currently muted
+    pub fn is_muted(&self) -> bool {
+        self.muted.load(Ordering::Relaxed)
+    }
+
+    /// Mutes or unmutes notifications
+    pub fn set_muted(&self, muted: bool) {
+        self.muted.store(muted, Ordering::Relaxed);
+    }
+
+    /// Time at which the last input action of any client has been received
+    pub fn last_input(&self) -> Instant {
+        *self.last_input.lock().expect("Last input lock poisoned")
+    }
+
+    /// Records that a client has just sent an input action
+    pub fn register_input(&self) {
+        *self.last_input.lock().expect("Last input lock poisoned") = Instant::now();
+    }
+
+    /// Denotes if the current timer has been paused on its last tick
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Records if the current timer is paused (this is updated by the timer on every tick)
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Replaces the scheduled resume of the timer (if any) with the given task
+    pub fn schedule_resume(&self, task: JoinHandle<()>) {
+        let previous = self
+            .scheduled_resume
+            .lock()
+            .expect("Scheduled resume lock poisoned")
+            .replace(task);
+
+        if let Some(previous) = previous {
+            previous.abort();
+        }
+    }
+
+    /// Cancels the scheduled resume of the timer (if any), e.g. because it has been resumed
+    /// manually
+    pub fn cancel_scheduled_resume(&self) {
+        let scheduled = self
+            .scheduled_resume
+            .lock()
+            .expect("Scheduled resume lock poisoned")
+            .take();
+
+        if let Some(scheduled) = scheduled {
+            scheduled.abort();
+        }
+    }
+
+    /// Records when the timer of the given state has first been running.
+    /// This is meant to be called on every tick.
+    pub fn track_timer(&self, state: &ViewState) {
+        let mut current = self
+            .current_timer
+            .lock()
+            .expect("Current timer lock poisoned");
+        let timer = (state.kind, state.round);
+
+        // WHY:
+        // Skipped or reset timers don't end regularly, which is why a new timer is detected by
+        // its kind and round instead (just like [super::stats_recorder::StatsRecorder] does).
+        let current = match current.as_mut() {
+            Some(current) if current.timer == timer => current,
+            _ => current.insert(CurrentTimer {
+                timer,
+                started_at: None,
+                label: None,
+            }),
+        };
+
+        if !state.is_paused && current.started_at.is_none() {
+            current.started_at = Some(Local::now().trunc_subsecs(0));
+        }
+    }
+
+    /// Start of the server and of the current timer
+    pub fn server_info(&self) -> ServerInfo {
+        let current = self
+            .current_timer
+            .lock()
+            .expect("Current timer lock poisoned");
+
+        ServerInfo {
+            started_at: self.started_at,
+            timer_started_at: current.as_ref().and_then(|current| current.started_at),
+        }
+    }
+
+    /// Kind and round of the current timer (if any has been tracked yet, see
+    /// [RuntimeState::track_timer])
+    pub fn current_timer(&self) -> Option<(TimerKind, u64)> {
+        self.current_timer
+            .lock()
+            .expect("Current timer lock poisoned")
+            .as_ref()
+            .map(|current| current.timer)
+    }
+
+    /// Label of the current timer (if any)
+    pub fn label(&self) -> Option<String> {
+        self.current_timer
+            .lock()
+            .expect("Current timer lock poisoned")
+            .as_ref()
+            .and_then(|current| current.label.clone())
+    }
+
+    /// Labels the current timer (or removes its label). The label is dropped once the next
+    /// timer starts.
+    pub fn set_label(&self, label: Option<String>) {
+        let mut current = self
+            .current_timer
+            .lock()
+            .expect("Current timer lock poisoned");
+
+        if let Some(current) = current.as_mut() {
+            current.label = label
+                .map(|label| label.trim().to_string())
+                .filter(|label| !label.is_empty());
+        }
+    }
+
+    /// Current configuration of notifications
+    pub fn notifications(&self) -> NotificationConfig {
+        self.notifications
+            .lock()
+            .expect("Notifications lock poisoned")
+            .clone()
+    }
+
+    /// Replaces the configuration of notifications (e.g. after the config has been reloaded)
+    pub fn set_notifications(&self, notifications: NotificationConfig) {
+        *self
+            .notifications
+            .lock()
+            .expect("Notifications lock poisoned") = notifications;
+    }
+
+    /// Applies all settings of the given config, which can be changed at runtime (e.g. after the
+    /// config has been reloaded). See [Config::restart_required_changes] for all other settings.
+    pub fn apply_config(&self, config: &Config) {
+        self.set_notifications(config.notifications.clone());
+        self.intervals
+            .store(config.timers.intervals, Ordering::Relaxed);
+        *self.daily_goal.lock().expect("Daily goal lock poisoned") = config.server.daily_goal;
+        *self
+            .pause_on_idle_secs
+            .lock()
+            .expect("Idle timeout lock poisoned") = config.server.pause_on_idle_secs;
+    }
+
+    /// Number of focus intervals until a long break
+    pub fn intervals(&self) -> u64 {
+        self.intervals.load(Ordering::Relaxed)
+    }
+
+    /// Number of focus intervals which should be completed per day (if any)
+    pub fn daily_goal(&self) -> Option<u64> {
+        *self.daily_goal.lock().expect("Daily goal lock poisoned")
+    }
+
+    /// Seconds without any input, after which a running focus interval is paused (if any)
+    pub fn pause_on_idle_secs(&self) -> Option<u64> {
+        *self
+            .pause_on_idle_secs
+            .lock()
+            .expect("Idle timeout lock poisoned")
+    }
+}
@@ -0,0 +1,119 @@
+//! Automatic start of focus sessions at the times configured inside the [ScheduleConfig]
+
+use crate::config::ScheduleConfig;
+use crate::server::runtime_state::RuntimeState;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveTime, TimeZone, Weekday};
+use crossbeam::channel::Sender;
+use log::{error, info};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use zentime_rs_timer::pomodoro_timer_action::PomodoroTimerAction;
+
+/// Notification which is sent once a scheduled focus session has been started
+pub const SCHEDULED_START_NOTIFICATION: &str = "Scheduled focus session started";
+
+/// Maximum time the scheduler sleeps at once.
+/// WHY:
+/// Sleeping is based on a monotonic clock, which might not advance while the system is
+/// suspended. Checking the wall clock regularly keeps the scheduler on time nonetheless.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Parsed [ScheduleConfig]
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    /// Time of day and the weekdays on which it applies (every day, if empty)
+    entries: Vec<(NaiveTime, Vec<Weekday>)>,
+}
+
+impl Schedule {
+    /// Creates a schedule from the given config.
+    /// Invalid entries are skipped (they are reported by [crate::config::Config::validate]).
+    pub fn new(config: &ScheduleConfig) -> Self {
+        let entries = config
+            .entries
+            .iter()
+            .filter_map(|entry| match (entry.time_of_day(), entry.days()) {
+                (Some(time), Ok(days)) => Some((time, days)),
+                _ => {
+                    error!("Ignoring invalid schedule entry: {:?}", entry);
+                    None
+                }
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Denotes if there is nothing to schedule at all
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the first scheduled start after `now` (in local time)
+    pub fn next_start(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        // A week from today contains every weekday at least once
+        (0..=7)
+            .filter_map(|days| {
+                now.date_naive()
+                    .checked_add_signed(ChronoDuration::days(days))
+            })
+            .flat_map(|date| {
+                self.entries
+                    .iter()
+                    .filter(move |(_, days)| days.is_empty() || days.contains(&date.weekday()))
+                    // NOTE: Times which don't exist on that day (due to daylight saving time)
+                    // are skipped
+                    .filter_map(move |(time, _)| {
+                        Local.from_local_datetime(&date.and_time(*time)).earliest()
+                    })
+            })
+            .filter(|start| *start > now)
+            .min()
+    }
+}
+
+/// Waits for each scheduled start and then resets the timer and starts a new focus interval.
+/// `on_start` is called right after each scheduled start, e.g. to send a notification.
+///
+/// NOTE: Each start counts as input (see [RuntimeState::register_input]), so that the new
+/// session isn't paused right away for being idle.
+pub async fn run(
+    schedule: Schedule,
+    timer_input_sender: Sender<PomodoroTimerAction>,
+    runtime_state: Arc<RuntimeState>,
+    on_start: impl Fn(),
+) {
+    let mut next_start = schedule.next_start(Local::now());
+
+    while let Some(start) = next_start {
+        let remaining = (start - Local::now()).to_std().unwrap_or_default();
+
+        if !remaining.is_zero() {
+            sleep(remaining.min(MAX_SLEEP)).await;
+            continue;
+        }
+
+        info!("Starting scheduled focus session");
+
+        runtime_state.register_input();
+
+        // NOTE: A reset always leads to a paused focus interval, which is then started by
+        // play/pause
+        let started = timer_input_sender
+            .send(PomodoroTimerAction::ResetTimer)
+            .and_then(|_| timer_input_sender.send(PomodoroTimerAction::PlayPause));
+
+        if let Err(error) = started {
+            error!("Could not start scheduled focus session: {}", error);
+            return;
+        }
+
+        on_start();
+
+        // WHY:
+        // Starting from the current time (instead of the last start) skips all starts which have
+        // been missed in the meantime, e.g. while the system has been suspended.
+        next_start = schedule.next_start(Local::now());
+    }
+}
@@ -1,77 +1,267 @@
-//! Sound playback related functions
-use log::{error, info};
+//! Sound playback related functions.
+//! Playback is only available if zentime has been built with the `audio` feature. Otherwise
+//! the [SoundPlayer] merely logs, that it could not play back anything.
+use crate::config::SoundOverlap;
+#[cfg(feature = "audio")]
+use log::error;
+use log::info;
+#[cfg(feature = "audio")]
 use rodio::decoder::DecoderError;
-use rodio::StreamError;
+#[cfg(feature = "audio")]
+use rodio::source::Zero;
+#[cfg(feature = "audio")]
+use rodio::{Decoder, OutputStream, Sink, Source, StreamError};
+#[cfg(feature = "audio")]
 use std::io::Cursor;
+#[cfg(feature = "audio")]
+use std::sync::mpsc::{channel, Receiver, Sender};
+#[cfg(feature = "audio")]
 use std::thread;
+#[cfg(feature = "audio")]
+use std::time::Duration;
 use thiserror::Error;
 
 // Code copied from: https://github.com/yuizho/pomors/blob/master/src/sound.rs
 
 /// Error type that describes error that could happen before/during audio playback
 #[derive(Debug, Error)]
+#[cfg_attr(not(feature = "audio"), derive(Clone, Copy))]
 pub enum AudioPlaybackError {
     /// Denotes that the given [SoundFile] could not be decoded
+    #[cfg(feature = "audio")]
     #[error("Could not decode audio data")]
     DecodeError(#[from] DecoderError),
 
     /// Denotes that no output device could be found
+    #[cfg(feature = "audio")]
     #[error("Failed to find output device")]
     DeviceNotFound(#[from] StreamError),
 
     /// The sink on the device to playback the sound could not be created
     #[error("Could not play back sound file because sink could not be created")]
     SinkNotCreated,
+
+    /// The playback thread is no longer running
+    #[error("Could not play back sound file because the sound player has stopped")]
+    PlayerStopped,
+
+    /// Zentime has been built without the `audio` feature
+    #[cfg(not(feature = "audio"))]
+    #[error(
+        "Could not play back sound file because zentime has been built without the `audio` feature"
+    )]
+    AudioUnavailable,
 }
 
-/// Play the sound file from sound_file path or the default sound file
-pub fn play(sound_file: Option<String>, volume: f32) -> Result<(), AudioPlaybackError> {
-    let custom_sound = match sound_file {
-        Some(path) => match std::fs::read(path) {
-            Ok(bytes) => Some(SoundFile::Custom(bytes)),
-            Err(error) => {
-                error!("Could not read custom sound file: {}", error);
-                None
-            }
-        },
-        None => None,
-    };
-
-    let sound_file = custom_sound.unwrap_or_else(|| {
-        info!("No custom sound file provided, falling back to default sound");
-        SoundFile::Default
-    });
-
-    let audio = rodio::Decoder::new(Cursor::new(sound_file.get_bytes()))?;
-
-    thread::spawn(move || -> Result<(), AudioPlaybackError> {
-        let (_stream, stream_handle) = rodio::OutputStream::try_default()?;
-        let sink =
-            rodio::Sink::try_new(&stream_handle).map_err(|_| AudioPlaybackError::SinkNotCreated)?;
-        sink.append(audio);
-        sink.set_volume(volume);
-        sink.sleep_until_end();
-        Ok(())
-    })
-    .join()
-    .unwrap()?;
+/// The bell, which is played back if no custom `sound_file` has been configured
+pub const DEFAULT_SOUND: &[u8] = include_bytes!("bell.wav");
+
+/// Plays back the given sound once and blocks until it has finished (e.g. to try out a sound
+/// file before it is configured)
+#[cfg(feature = "audio")]
+pub fn play_once(bytes: Vec<u8>, volume: f32) -> Result<(), AudioPlaybackError> {
+    let audio = Decoder::new(Cursor::new(bytes))?;
+    let (_stream, stream_handle) = OutputStream::try_default()?;
+    let sink = Sink::try_new(&stream_handle).map_err(|_| AudioPlaybackError::SinkNotCreated)?;
+
+    sink.append(audio.amplify(volume));
+    sink.sleep_until_end();
 
     Ok(())
 }
 
+/// Always fails, because zentime has been built without the `audio` feature
+#[cfg(not(feature = "audio"))]
+pub fn play_once(_bytes: Vec<u8>, _volume: f32) -> Result<(), AudioPlaybackError> {
+    Err(AudioPlaybackError::AudioUnavailable)
+}
+
+/// A decoded sound, which is waiting for its playback
+#[cfg(feature = "audio")]
+struct Sound {
+    /// One decoded source per repetition
+    audio: Vec<Decoder<Cursor<Vec<u8>>>>,
+    volume: f32,
+    fade_in_ms: u64,
+    repeat_gap_ms: u64,
+}
+
+/// Plays back all sounds on a single shared [Sink], so that bells never overlap.
+/// Depending on the [SoundOverlap] a new sound either replaces or queues behind the current one.
+///
+/// NOTE:
+/// The output stream can't be moved between threads, which is why playback happens on a
+/// dedicated thread, which lives as long as the player.
+#[cfg(feature = "audio")]
+#[derive(Debug, Clone)]
+pub struct SoundPlayer {
+    sound_tx: Sender<Sound>,
+}
+
+/// Stand-in for the actual sound player, if zentime has been built without the `audio` feature.
+/// Bells are ignored in that case.
+#[cfg(not(feature = "audio"))]
+#[derive(Debug, Clone, Copy)]
+pub struct SoundPlayer {}
+
+#[cfg(not(feature = "audio"))]
+impl SoundPlayer {
+    /// Creates a new player, which never plays back anything
+    pub fn new(_overlap: SoundOverlap) -> Self {
+        Self {}
+    }
+
+    /// Only logs that audio is unavailable
+    pub fn play(
+        &self,
+        _sound_file: Option<String>,
+        _volume: f32,
+        _fade_in_ms: u64,
+        _repeat: u8,
+        _repeat_gap_ms: u64,
+    ) -> Result<(), AudioPlaybackError> {
+        info!("Not playing sound, because zentime has been built without the `audio` feature");
+        Ok(())
+    }
+}
+
+#[cfg(feature = "audio")]
+impl SoundPlayer {
+    /// Creates a new player and spawns its playback thread
+    pub fn new(overlap: SoundOverlap) -> Self {
+        let (sound_tx, sound_rx) = channel();
+
+        thread::spawn(move || play_back(sound_rx, overlap));
+
+        Self { sound_tx }
+    }
+
+    /// Play the sound file from sound_file path or the default sound file `repeat` times with
+    /// `repeat_gap_ms` of silence in between.
+    /// If `fade_in_ms` is greater than 0, the sound fades in from silence to `volume` over that
+    /// duration. This does not wait for the playback to finish.
+    pub fn play(
+        &self,
+        sound_file: Option<String>,
+        volume: f32,
+        fade_in_ms: u64,
+        repeat: u8,
+        repeat_gap_ms: u64,
+    ) -> Result<(), AudioPlaybackError> {
+        let custom_sound = match sound_file {
+            Some(path) => match std::fs::read(path) {
+                Ok(bytes) => Some(SoundFile::Custom(bytes)),
+                Err(error) => {
+                    error!("Could not read custom sound file: {}", error);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let sound_file = custom_sound.unwrap_or_else(|| {
+            info!("No custom sound file provided, falling back to default sound");
+            SoundFile::Default
+        });
+
+        // WHY:
+        // A [Decoder] can't be cloned, which is why the sound is decoded once per repetition
+        let bytes = sound_file.get_bytes();
+        let audio = (0..repeat.max(1))
+            .map(|_| Decoder::new(Cursor::new(bytes.clone())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.sound_tx
+            .send(Sound {
+                audio,
+                volume,
+                fade_in_ms,
+                repeat_gap_ms,
+            })
+            .map_err(|_| AudioPlaybackError::PlayerStopped)
+    }
+}
+
+/// Plays back incoming sounds until the [SoundPlayer] has been dropped
+#[cfg(feature = "audio")]
+fn play_back(sound_rx: Receiver<Sound>, overlap: SoundOverlap) {
+    // Opened lazily, so that a missing output device only fails single playbacks
+    let mut output: Option<(OutputStream, _)> = None;
+    let mut sink: Option<Sink> = None;
+
+    for sound in sound_rx {
+        if output.is_none() {
+            match OutputStream::try_default() {
+                Ok(stream) => output = Some(stream),
+                Err(error) => {
+                    error!("{}", AudioPlaybackError::from(error));
+                    continue;
+                }
+            }
+        }
+
+        let Some((_stream, stream_handle)) = &output else {
+            continue;
+        };
+
+        let current_sink = match sink.take() {
+            Some(current_sink) if overlap == SoundOverlap::Queue => current_sink,
+            previous_sink => {
+                // A stopped sink can't play back anything anymore, so we need a new one
+                if let Some(previous_sink) = previous_sink {
+                    previous_sink.stop();
+                }
+
+                match Sink::try_new(stream_handle) {
+                    Ok(new_sink) => new_sink,
+                    Err(_) => {
+                        error!("{}", AudioPlaybackError::SinkNotCreated);
+                        continue;
+                    }
+                }
+            }
+        };
+
+        for (index, audio) in sound.audio.into_iter().enumerate() {
+            // Every repetition is preceded by a gap of silence
+            if index > 0 && sound.repeat_gap_ms > 0 {
+                current_sink.append(
+                    Zero::<f32>::new(audio.channels(), audio.sample_rate())
+                        .take_duration(Duration::from_millis(sound.repeat_gap_ms)),
+                );
+            }
+
+            // WHY:
+            // Queued sounds share a single sink, which is why the volume is applied per sound.
+            let audio = audio.amplify(sound.volume);
+
+            if sound.fade_in_ms > 0 {
+                current_sink.append(audio.fade_in(Duration::from_millis(sound.fade_in_ms)));
+            } else {
+                current_sink.append(audio);
+            }
+        }
+
+        sink = Some(current_sink);
+    }
+}
+
+#[cfg(feature = "audio")]
 trait FileData {
     fn get_bytes(&self) -> Vec<u8>;
 }
 
+#[cfg(feature = "audio")]
 enum SoundFile {
     Default,
     Custom(Vec<u8>),
 }
 
+#[cfg(feature = "audio")]
 impl FileData for SoundFile {
     fn get_bytes(&self) -> Vec<u8> {
         match self {
-            SoundFile::Default => include_bytes!("bell.wav").to_vec(),
+            SoundFile::Default => DEFAULT_SOUND.to_vec(),
             SoundFile::Custom(bytes) => bytes.to_owned(),
         }
     }
@@ -1,21 +1,39 @@
-use crate::config::Config;
+use crate::config::{Config, NotificationEvent};
 use crate::ipc::{
-    get_socket_name, ClientToServerMsg, InterProcessCommunication, ServerToClientMsg,
+    is_connection_closed, ClientToServerMsg, Encoding, EventKind, FirstMessage, Handshake,
+    InterProcessCommunication, ServerToClientMsg, TimerEvent, MAX_MISSED_PONGS, PING_INTERVAL,
 };
-use crate::server::notification::dispatch_notification;
-use crate::server::timer_output::TimerOutputAction;
+use crate::server::ambient::AmbientSound;
+use crate::server::clients::{ClientRegistration, ClientRegistry};
+use crate::server::daily_goal::{completed_today, DailyGoal, GOAL_REACHED_NOTIFICATION};
+use crate::server::events::EventEmitter;
+use crate::server::history::SessionHistory;
+use crate::server::idle::IdlePause;
+use crate::server::notification::{
+    dispatch_notification, NotificationDebounce, PendingBreakActions,
+};
+use crate::server::paused_backoff::PausedBackoff;
+use crate::server::runtime_state::RuntimeState;
+use crate::server::schedule::{self, Schedule, SCHEDULED_START_NOTIFICATION};
+use crate::server::sound::SoundPlayer;
+use crate::server::stats_recorder::StatsRecorder;
+use crate::server::timer_output::{TimerOutputAction, TimerOutputReceiver};
+use crate::server::webhook::{notify_webhook, TimerDetails};
 use anyhow::Context;
+use chrono::{Local, SubsecRound};
 use crossbeam::channel::{unbounded, Sender};
-use interprocess::local_socket::tokio::OwnedWriteHalf;
+use interprocess::local_socket::tokio::{OwnedReadHalf, OwnedWriteHalf};
 use log::{error, info};
 use tokio::task::{spawn_blocking, yield_now};
-use zentime_rs_timer::pomodoro_timer::{PomodoroTimer, TimerKind};
+use zentime_rs_timer::pomodoro_timer::{PomodoroTimer, TimerKind, ViewState};
 use zentime_rs_timer::pomodoro_timer_action::PomodoroTimerAction;
 
+use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::Arc;
 use tokio::select;
 use tokio::sync::{self, broadcast::Receiver as BroadcastReceiver};
+use tokio::time::{interval_at, sleep, Instant};
 
 use futures::io::BufReader;
 use interprocess::local_socket::tokio::{LocalSocketListener, LocalSocketStream};
@@ -23,21 +41,68 @@ use interprocess::local_socket::tokio::{LocalSocketListener, LocalSocketStream};
 use std::time::Duration;
 use tokio::fs::{metadata, remove_file};
 
-
+use super::pid_file::{remove_orphaned_files, remove_pid_file, write_pid_file};
 use super::status::{server_status, ServerStatus};
 
+/// Longest timed pause (see [ClientToServerMsg::PauseFor]), which is already far longer than any
+/// interruption it is meant for
+const MAX_PAUSE_SECS: u64 = 24 * 60 * 60;
+
+/// Determines which timer the server runs
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum TimerMode {
+    /// The regular pomodoro cycle of focus intervals and breaks
+    #[default]
+    Pomodoro,
+
+    /// A single focus session of the given number of seconds
+    OneShot(u64),
+
+    /// An open-ended focus session, which counts up until it is stopped
+    Stopwatch,
+}
+
+/// Re-creates the configuration the server has been started with (e.g. by reading the config file
+/// again), so that it can be reloaded at runtime (see [ClientToServerMsg::Reload])
+pub type ConfigLoader = Arc<dyn Fn() -> anyhow::Result<Config> + Send + Sync>;
+
+/// Describes how the server has been started, which is shared with every connection
+#[derive(Clone)]
+struct ServerSetup {
+    socket_name: String,
+    load_config: ConfigLoader,
+
+    /// Config the server has been started with, which still holds all settings that can't be
+    /// reloaded (see [Config::restart_required_changes])
+    config: Arc<Config>,
+}
+
 /// Starts the server by opening the zentime socket and listening for incoming connections.
 /// This will just quit if another zentime server process is already running.
 ///
+/// Unless the `mode` is [TimerMode::Pomodoro], the server shuts down once its single session has
+/// ended (or the stopwatch has been stopped).
+///
+/// `load_config` is called whenever a client requests to reload the configuration.
+///
 /// NOTE:
 /// This spawns a tokio runtime and should therefore not be run inside another tokio runtime.
 #[tokio::main]
-pub async fn start(config: Config) -> anyhow::Result<()> {
-    let socket_name = get_socket_name();
+pub async fn start(
+    config: Config,
+    mode: TimerMode,
+    load_config: ConfigLoader,
+) -> anyhow::Result<()> {
+    let socket_name = config.server.socket_name();
+
+    // A crashed server leaves its PID file and socket file behind
+    if remove_orphaned_files(&socket_name).context("Could not remove orphaned files")? {
+        info!("Removed PID file and socket file of a server, which is not running anymore");
+    }
 
-    let socket_file_already_exists = metadata(socket_name).await.is_ok();
+    let socket_file_already_exists = metadata(&socket_name).await.is_ok();
 
-    if socket_file_already_exists && server_status() == ServerStatus::Running {
+    if socket_file_already_exists && server_status(&socket_name) == ServerStatus::Running {
         info!("Server is already running. Terminating this process...");
         // Apparently a server is already running and we don't need to do anything
         return Ok(());
@@ -48,14 +113,16 @@ pub async fn start(config: Config) -> anyhow::Result<()> {
 
         // We have a dangling socket file without an attached server process.
         // In that case we simply remove the file and start a new server process
-        remove_file(socket_name)
+        remove_file(&socket_name)
             .await
             .context("Could not remove existing socket file")?
     };
 
+    write_pid_file(&socket_name).context("Could not write PID file")?;
+
     info!("Start listening for connections...");
 
-    listen(config, socket_name)
+    listen(config, &socket_name, mode, load_config)
         .await
         .context("Error while listening for connections")?;
 
@@ -65,7 +132,12 @@ pub async fn start(config: Config) -> anyhow::Result<()> {
 /// This starts a blocking tokio task which runs the actual synchronous timer logic, but
 /// also listens for incoming client connections and spawns a new async task for each incoming
 /// connection.
-async fn listen(config: Config, socket_name: &str) -> anyhow::Result<()> {
+async fn listen(
+    config: Config,
+    socket_name: &str,
+    mode: TimerMode,
+    load_config: ConfigLoader,
+) -> anyhow::Result<()> {
     info!("Binding to socket...");
     let listener =
         LocalSocketListener::bind(socket_name).context("Could not bind to local socket")?;
@@ -79,36 +151,363 @@ async fn listen(config: Config, socket_name: &str) -> anyhow::Result<()> {
     // to continuously subscribe to it on incoming client connections
     let timer_out_tx = timer_output_sender.clone();
 
+    // State which can be changed by clients at runtime (e.g. muting notifications)
+    let runtime_state = Arc::new(RuntimeState::default());
+    runtime_state.apply_config(&config);
+    let client_registry = Arc::new(ClientRegistry::default());
+    let timer_runtime_state = runtime_state.clone();
+
+    #[cfg(feature = "http")]
+    if let Some(port) = config.server.http_port {
+        let output_rx = timer_output_sender.subscribe();
+
+        tokio::spawn(async move {
+            if let Err(error) = super::http::serve(port, output_rx).await {
+                error!("HTTP status endpoint failed: {}", error);
+            }
+        });
+    }
+
+    let timer_socket_name = socket_name.to_string();
+    let notification_input_tx = timer_input_sender.clone();
+    // Shared with the scheduler, so that its bells don't overlap with the ones of the timer
+    let player = Arc::new(SoundPlayer::new(config.notifications.sound_overlap));
+    let debounce = Arc::new(NotificationDebounce::default());
+    let pending_actions = PendingBreakActions::default();
+
+    // One-shot and stopwatch servers shut down after their single session anyway
+    let schedule = Schedule::new(&config.schedule);
+    if mode == TimerMode::Pomodoro && !schedule.is_empty() {
+        let schedule_input_tx = timer_input_sender.clone();
+        let schedule_runtime_state = runtime_state.clone();
+        let schedule_player = player.clone();
+        let schedule_debounce = debounce.clone();
+
+        tokio::spawn(schedule::run(
+            schedule,
+            schedule_input_tx,
+            runtime_state.clone(),
+            move || {
+                let notifications = schedule_runtime_state.notifications();
+                notify_webhook(
+                    &notifications,
+                    NotificationEvent::ScheduledStart,
+                    TimerDetails::default(),
+                );
+
+                if schedule_runtime_state.is_muted() {
+                    return;
+                }
+
+                if let Err(error) = dispatch_notification(
+                    notifications,
+                    &schedule_player,
+                    &schedule_debounce,
+                    &PendingBreakActions::default(),
+                    Some(SCHEDULED_START_NOTIFICATION),
+                    NotificationEvent::ScheduledStart,
+                    |_| {},
+                ) {
+                    error!("{}", error);
+                }
+            },
+        ));
+    }
+
+    let startup_config = Arc::new(config.clone());
+
     spawn_blocking(move || {
         info!("Starting timer...");
 
-        PomodoroTimer::new(
-            config.timers,
-            Rc::new(move |_, msg, kind| {
-                let result = dispatch_notification(
-                    config.clone().notifications,
-                    msg,
-                    kind == TimerKind::Interval
+        // Shared between both timer callbacks, which are run on this thread only
+        let completed_today = match config.server.stats_file() {
+            Some(path) => completed_today(&path).unwrap_or_else(|error| {
+                error!(
+                    "Could not read today's progress from the stats file: {}",
+                    error
+                );
+                0
+            }),
+            None => 0,
+        };
+        let daily_goal = Rc::new(RefCell::new(DailyGoal::new(
+            config.server.daily_goal,
+            completed_today,
+        )));
+        let tick_daily_goal = daily_goal.clone();
+        let history = Rc::new(RefCell::new(SessionHistory::default()));
+        let tick_history = history.clone();
+        let stats = config
+            .server
+            .stats_file()
+            .map(|path| Rc::new(RefCell::new(StatsRecorder::new(path))));
+        let tick_stats = stats.clone();
+        let stopwatch_stats = stats.clone();
+        let tick_runtime_state = timer_runtime_state.clone();
+        let end_runtime_state = timer_runtime_state.clone();
+        let events = Rc::new(RefCell::new(EventEmitter::default()));
+        let end_events = events.clone();
+        let end_timer_out_tx = timer_out_tx.clone();
+        // NOTE: Created lazily, because the idle timeout might be (re)configured at runtime
+        let idle_pause: RefCell<Option<IdlePause>> = RefCell::new(None);
+        let paused_backoff = config
+            .server
+            .paused_backoff()
+            .map(|(after, interval)| RefCell::new(PausedBackoff::new(after, interval)));
+        let ambient_sound = config
+            .notifications
+            .ambient_sound
+            .clone()
+            .map(|sound_file| RefCell::new(AmbientSound::new(sound_file)));
+
+        let notify = move |msg: Option<&str>, event: NotificationEvent, round: u64| {
+            // NOTE: The notification config might have been reloaded in the meantime
+            let notifications = timer_runtime_state.notifications();
+            notify_webhook(
+                &notifications,
+                event,
+                TimerDetails {
+                    round: Some(round),
+                    label: timer_runtime_state.label(),
+                },
+            );
+
+            // While muted, neither the bell nor the OS-notification should disturb the user
+            if timer_runtime_state.is_muted() {
+                return;
+            }
+
+            // Skip/Postpone buttons of OS-notifications act just like client input
+            let action_input_tx = notification_input_tx.clone();
+            let action_runtime_state = timer_runtime_state.clone();
+            let on_action = move |action| {
+                // WHY:
+                // The buttons might be clicked long after the break has ended, in which case
+                // they must not affect whichever timer is running by then.
+                // NOTE: A break keeps the round of the focus interval it follows
+                let is_break_of_round = matches!(
+                    action_runtime_state.current_timer(),
+                    Some((TimerKind::Break | TimerKind::LongBreak, current_round)) if current_round == round
                 );
+                if !is_break_of_round {
+                    info!(
+                        "Ignoring {:?} of a notification, because its break is over",
+                        action
+                    );
+                    return;
+                }
 
-                if let Err(error) = result {
-                    error!("{}", error);
+                action_runtime_state.register_input();
+                if let Err(error) = action_input_tx.send(action) {
+                    error!("Could not send notification action to timer: {}", error);
+                }
+            };
+
+            if let Err(error) = dispatch_notification(
+                notifications,
+                &player,
+                &debounce,
+                &pending_actions,
+                msg,
+                event,
+                on_action,
+            ) {
+                error!("{}", error);
+            }
+        };
+
+        let timer = PomodoroTimer::new(
+            config.timers,
+            Rc::new(move |state, msg, kind| {
+                // WHY:
+                // A one-shot session also ends as [TimerKind::Interval], but neither starts a
+                // break nor counts towards the daily goal.
+                let is_one_shot = matches!(mode, TimerMode::OneShot(_));
+                let event = if is_one_shot {
+                    NotificationEvent::OneShotEnd
+                } else {
+                    notification_event(kind, state.round, end_runtime_state.intervals())
+                };
+                notify(msg, event, state.round);
+
+                // The next timer starts right away
+                end_events.borrow_mut().timer_started();
+
+                // NOTE: Postponed breaks also end as [TimerKind::Interval], but only the end of
+                // an actual focus interval comes with a notification message
+                let is_postponed_break = kind == TimerKind::Interval && msg.is_none();
+                if !is_postponed_break {
+                    history.borrow_mut().record(kind);
+                }
+
+                if let Some(stats) = &stats {
+                    if let Err(error) = stats.borrow_mut().record() {
+                        error!("Could not record timer: {:#}", anyhow::Error::new(error));
+                    }
+                }
+
+                if kind == TimerKind::Interval
+                    && !is_postponed_break
+                    && !is_one_shot
+                    && daily_goal.borrow_mut().record_completed_interval()
+                {
+                    notify(
+                        Some(GOAL_REACHED_NOTIFICATION),
+                        NotificationEvent::GoalReached,
+                        state.round,
+                    );
+
+                    if let Some((completed, _)) = daily_goal.borrow_mut().progress() {
+                        end_timer_out_tx
+                            .send(TimerOutputAction::Event(TimerEvent::GoalReached {
+                                completed,
+                            }))
+                            .ok();
+                    }
                 }
             }),
             Rc::new(move |view_state| {
-                // Update the view
-                timer_out_tx.send(TimerOutputAction::Timer(view_state)).ok();
+                tick_runtime_state.set_paused(view_state.is_paused);
+                tick_runtime_state.track_timer(&view_state);
+
+                // NOTE: The daily goal might have been reloaded in the meantime
+                let goal_progress = {
+                    let mut daily_goal = tick_daily_goal.borrow_mut();
+                    daily_goal.set_goal(tick_runtime_state.daily_goal());
+                    daily_goal.progress()
+                };
+
+                let view_state = ViewState {
+                    goal_progress,
+                    is_muted: tick_runtime_state.is_muted(),
+                    label: tick_runtime_state.label(),
+                    history: tick_history.borrow().segments(),
+                    ..view_state
+                };
+
+                if let Some(stats) = &tick_stats {
+                    stats.borrow_mut().track(&view_state);
+                }
+
+                if let Some(ambient_sound) = &ambient_sound {
+                    ambient_sound.borrow_mut().update(&view_state);
+                }
+
+                // Update the view (less often, if the timer has been paused for a while)
+                let should_send = paused_backoff.as_ref().is_none_or(|backoff| {
+                    backoff.borrow_mut().should_send(
+                        &view_state,
+                        timer_out_tx.receiver_count(),
+                        std::time::Instant::now(),
+                    )
+                });
+                if should_send {
+                    timer_out_tx
+                        .send(TimerOutputAction::Timer(view_state.clone()))
+                        .ok();
+                }
+
+                // Notify subscribed clients about transitions of the timer
+                if let Some(event) = events.borrow_mut().tick(&view_state) {
+                    timer_out_tx.send(TimerOutputAction::Event(event)).ok();
+                }
 
                 // Handle app actions and hand them to the timer caller
-                match timer_input_receiver.recv_timeout(Duration::from_millis(100)) {
+                let action = match timer_input_receiver.recv_timeout(Duration::from_millis(100)) {
+                    Ok(PomodoroTimerAction::ResetTimer) => {
+                        // A reset starts a new session
+                        tick_history.borrow_mut().clear();
+
+                        Some(PomodoroTimerAction::ResetTimer)
+                    }
+                    Ok(PomodoroTimerAction::PreviousInterval) => {
+                        // The previous timer is going to be repeated
+                        tick_history.borrow_mut().rewind();
+
+                        Some(PomodoroTimerAction::PreviousInterval)
+                    }
                     Ok(action) => Some(action),
-                    _ => Some(PomodoroTimerAction::None),
+                    _ => {
+                        let mut idle_pause = idle_pause.borrow_mut();
+                        let idle_pause = match tick_runtime_state.pause_on_idle_secs() {
+                            Some(secs) => {
+                                let timeout = Duration::from_secs(secs);
+                                let idle_pause =
+                                    idle_pause.get_or_insert_with(|| IdlePause::new(timeout));
+                                idle_pause.set_timeout(timeout);
+                                Some(idle_pause)
+                            }
+                            None => {
+                                *idle_pause = None;
+                                None
+                            }
+                        };
+
+                        match idle_pause {
+                            Some(idle_pause) => Some(
+                                idle_pause.action(&view_state, tick_runtime_state.last_input()),
+                            ),
+                            None => Some(PomodoroTimerAction::None),
+                        }
+                    }
+                };
+
+                // Record transitions, whose events are emitted with the next tick
+                match action {
+                    // These always start a new timer - even one of the same kind and round
+                    Some(
+                        PomodoroTimerAction::ResetTimer | PomodoroTimerAction::PreviousInterval,
+                    ) => events.borrow_mut().timer_started(),
+                    Some(
+                        PomodoroTimerAction::PlayPause
+                        | PomodoroTimerAction::Skip
+                        | PomodoroTimerAction::PostponeBreak,
+                    ) => events.borrow_mut().action_handled(),
+                    _ => {}
                 }
+
+                action
             }),
-        )
-        .init()
+        );
+
+        match mode {
+            TimerMode::OneShot(time) => {
+                timer.one_shot(time).init();
+
+                // WHY:
+                // A one-shot session does not transition into a break, so there is nothing left
+                // to serve once it has ended.
+                info!("One-shot timer ended. Shutting down...");
+                shut_down(&timer_socket_name);
+            }
+            TimerMode::Stopwatch => {
+                timer.stopwatch().init();
+
+                // The stopwatch never ends on its own, which is why it is recorded like a focus
+                // interval once it has been stopped
+                if let Some(stats) = &stopwatch_stats {
+                    if let Err(error) = stats.borrow_mut().record() {
+                        error!(
+                            "Could not record stopwatch: {:#}",
+                            anyhow::Error::new(error)
+                        );
+                    }
+                }
+
+                info!("Stopwatch stopped. Shutting down...");
+                shut_down(&timer_socket_name);
+            }
+            TimerMode::Pomodoro if config.server.start_running => timer.init_running(),
+            TimerMode::Pomodoro => timer.init(),
+        }
     });
 
+    let setup = ServerSetup {
+        socket_name: socket_name.to_string(),
+        load_config,
+        config: startup_config,
+    };
+
     // Set up our loop boilerplate that processes our incoming connections.
     loop {
         let connection = listener
@@ -118,37 +517,134 @@ async fn listen(config: Config, socket_name: &str) -> anyhow::Result<()> {
 
         let input_tx = timer_input_sender.clone();
         let output_rx = timer_output_sender.subscribe();
+        let setup = setup.clone();
+        let runtime_state = runtime_state.clone();
+        let client_registry = client_registry.clone();
 
         // Spawn new parallel asynchronous tasks onto the Tokio runtime
         // and hand the connection over to them so that multiple clients
         // could be processed simultaneously in a lightweight fashion.
         tokio::spawn(async move {
-            info!("New connection received.");
-            if let Err(error) = handle_conn(connection, input_tx, output_rx).await {
-                error!("Could not handle connection: {}", error);
+            // The client is unregistered once the task ends, even if it ends with an error
+            let registration = client_registry.register();
+            info!("New connection received (client {}).", registration.id);
+
+            if let Err(error) = handle_conn(
+                connection,
+                input_tx,
+                output_rx,
+                &setup,
+                &runtime_state,
+                &registration,
+            )
+            .await
+            {
+                // NOTE: Clients which have exited without detaching are treated just like
+                // detached clients
+                if is_connection_closed(&error) {
+                    info!(
+                        "Client {} closed the connection without detaching.",
+                        registration.id
+                    );
+                } else {
+                    error!("Could not handle connection: {}", error);
+                }
             };
         });
     }
 }
 
+/// Removes the socket file and the PID file and exits the server process
+fn shut_down(socket_name: &str) -> ! {
+    // NOTE: Namespaced sockets don't have a file which could be removed
+    if std::path::Path::new(socket_name).exists() {
+        if let Err(error) = std::fs::remove_file(socket_name) {
+            error!("Could not remove socket file: {}", error);
+        }
+    }
+
+    if let Err(error) = remove_pid_file(socket_name) {
+        error!("Could not remove PID file: {}", error);
+    }
+
+    std::process::exit(0);
+}
+
+/// Determines which event the end of a timer of the given kind represents.
+/// Focus intervals (and postponed breaks) of every `intervals`-th round are followed by a long break.
+fn notification_event(kind: TimerKind, round: u64, intervals: u64) -> NotificationEvent {
+    // NOTE: Postponed breaks end as [TimerKind::Interval] and a completed session never ends,
+    // but we still handle their kinds for completeness.
+    match kind {
+        TimerKind::Break | TimerKind::LongBreak | TimerKind::SessionComplete => {
+            NotificationEvent::BreakEnd
+        }
+        TimerKind::Interval | TimerKind::PostponedBreak | TimerKind::PostponedLongBreak
+            if round % intervals == 0 =>
+        {
+            NotificationEvent::LongBreak
+        }
+        TimerKind::Interval | TimerKind::PostponedBreak | TimerKind::PostponedLongBreak => {
+            NotificationEvent::FocusEnd
+        }
+    }
+}
+
 /// Describe the things we do when we've got a connection ready.
 /// This will continously send the current timer state to the client and also listen for incoming
 /// [ClientToServerMsg]s.
 async fn handle_conn(
     conn: LocalSocketStream,
     timer_input_sender: Sender<PomodoroTimerAction>,
-    mut timer_output_receiver: BroadcastReceiver<TimerOutputAction>,
+    timer_output_receiver: BroadcastReceiver<TimerOutputAction>,
+    setup: &ServerSetup,
+    runtime_state: &Arc<RuntimeState>,
+    registration: &ClientRegistration,
 ) -> anyhow::Result<()> {
+    let mut timer_output_receiver = TimerOutputReceiver::new(timer_output_receiver);
+
     // Split the connection into two halves to process
     // received and sent data concurrently.
-    let (reader, mut writer) = conn.into_split();
+    let (reader, writer) = conn.into_split();
     let mut reader = BufReader::new(reader);
+    let mut writer = ClientWriter {
+        writer,
+        encoding: Encoding::Msgpack,
+    };
+
+    // Set once the first message has been received, which might have been a [Handshake]
+    let mut has_received_msg = false;
+
+    // Event kinds this client has subscribed to
+    let mut subscriptions: Vec<EventKind> = vec![];
+
+    // WHY:
+    // A killed client does not necessarily close its half of the socket, which would otherwise
+    // keep this connection task alive forever.
+    let mut ping_interval = interval_at(Instant::now() + PING_INTERVAL, PING_INTERVAL);
+    let mut missed_pongs = 0;
 
     loop {
         select! {
-            msg = InterProcessCommunication::recv_ipc_message::<ClientToServerMsg>(&mut reader) => {
+            msg = recv_client_msg(&mut reader, has_received_msg, writer.encoding) => {
                 let msg = msg.context("Could not receive message from socket")?;
-                if let CloseConnection::Yes = handle_client_to_server_msg(msg, &timer_input_sender)
+                has_received_msg = true;
+                // Any message proves that the client is still alive
+                missed_pongs = 0;
+
+                let msg = match msg {
+                    FirstMessage::Handshake(Handshake { encoding }) => {
+                        info!("Client requested {:?} encoding", encoding);
+                        writer.encoding = encoding;
+                        writer.send(ServerToClientMsg::HandshakeAck(encoding))
+                            .await
+                            .context("Could not acknowledge handshake")?;
+                        continue;
+                    }
+                    FirstMessage::Message(msg) => msg,
+                };
+
+                if let CloseConnection::Yes = handle_client_to_server_msg(msg, &timer_input_sender, setup, runtime_state, &mut subscriptions, registration, &mut writer)
                     .await
                     .context("Could not handle client to server message")? {
                         break;
@@ -156,7 +652,18 @@ async fn handle_conn(
             },
             value = timer_output_receiver.recv() => {
                 let action = value.context("Could not receive output from timer")?;
-                handle_timer_output_action(action, &mut writer).await.context("Couuld not handle timer output action")?;
+                handle_timer_output_action(action, &subscriptions, &mut writer).await.context("Couuld not handle timer output action")?;
+            }
+            _ = ping_interval.tick() => {
+                if missed_pongs >= MAX_MISSED_PONGS {
+                    info!("Client did not answer {} pings - closing connection", missed_pongs);
+                    break;
+                }
+
+                writer.send(ServerToClientMsg::Ping)
+                    .await
+                    .context("Could not send ping to client")?;
+                missed_pongs += 1;
             }
         }
 
@@ -172,23 +679,65 @@ enum CloseConnection {
     No,
 }
 
+/// Write half of a client connection together with the [Encoding] the client has requested
+struct ClientWriter {
+    writer: OwnedWriteHalf,
+    encoding: Encoding,
+}
+
+impl ClientWriter {
+    async fn send(&mut self, msg: ServerToClientMsg) -> anyhow::Result<()> {
+        InterProcessCommunication::send_ipc_message_as(msg, self.encoding, &mut self.writer).await
+    }
+}
+
+/// Receives the next message of a client. Only the very first message may be a [Handshake],
+/// all following messages are decoded with the `encoding` of the connection.
+async fn recv_client_msg(
+    reader: &mut BufReader<OwnedReadHalf>,
+    has_received_msg: bool,
+    encoding: Encoding,
+) -> anyhow::Result<FirstMessage<ClientToServerMsg>> {
+    if has_received_msg {
+        InterProcessCommunication::recv_ipc_message_as(encoding, reader)
+            .await
+            .map(FirstMessage::Message)
+    } else {
+        InterProcessCommunication::recv_first_ipc_message(reader).await
+    }
+}
+
 async fn handle_client_to_server_msg(
     msg: ClientToServerMsg,
     timer_input_sender: &Sender<PomodoroTimerAction>,
+    setup: &ServerSetup,
+    runtime_state: &Arc<RuntimeState>,
+    subscriptions: &mut Vec<EventKind>,
+    registration: &ClientRegistration,
+    writer: &mut ClientWriter,
 ) -> anyhow::Result<CloseConnection> {
+    // Every control action counts as user activity (see [IdlePause])
+    if !matches!(
+        msg,
+        ClientToServerMsg::Quit
+            | ClientToServerMsg::Detach
+            | ClientToServerMsg::Sync
+            | ClientToServerMsg::Subscribe(_)
+            | ClientToServerMsg::Pong
+            | ClientToServerMsg::ListClients
+            | ClientToServerMsg::GetServerInfo
+            | ClientToServerMsg::Reload
+    ) {
+        runtime_state.register_input();
+    }
+
     match msg {
         // Shutdown server
         ClientToServerMsg::Quit => {
             info!("\nClient told server to shutdown");
 
-            info!("Cleaning up socket file");
-            let socket_name = get_socket_name();
-            remove_file(socket_name)
-                .await
-                .context("Could not remove existing socket file")?;
-
-            info!("Shutting down...");
-            std::process::exit(0);
+            info!("Cleaning up socket file and shutting down...");
+            shut_down(&setup.socket_name);
         }
 
         ClientToServerMsg::Reset => {
@@ -197,8 +746,18 @@ async fn handle_client_to_server_msg(
                 .context("Could not send ResetTimer to timer")?;
         }
 
+        // Move back to the previous timer interval
+        ClientToServerMsg::Previous => {
+            timer_input_sender
+                .send(PomodoroTimerAction::PreviousInterval)
+                .context("Could not send PreviousInterval to timer")?;
+        }
+
         // Play/Pause the timer
         ClientToServerMsg::PlayPause => {
+            // The user decides on their own, when to continue after a timed pause
+            runtime_state.cancel_scheduled_resume();
+
             timer_input_sender
                 .send(PomodoroTimerAction::PlayPause)
                 .context("Could not send Play/Pause to timer")?;
@@ -233,7 +792,131 @@ async fn handle_client_to_server_msg(
             timer_input_sender
                 .send(PomodoroTimerAction::SetTimer(time))
                 .context("Could not send SetTimer to timer")?;
-        },
+        }
+
+        // Count a distraction during the current focus interval
+        ClientToServerMsg::Distraction => {
+            timer_input_sender
+                .send(PomodoroTimerAction::LogDistraction)
+                .context("Could not send LogDistraction to timer")?;
+        }
+
+        // Override the durations of the upcoming interval and/or break once
+        ClientToServerMsg::SetNextInterval { focus, break_ } => {
+            timer_input_sender
+                .send(PomodoroTimerAction::SetNextInterval {
+                    focus,
+                    break_secs: break_,
+                })
+                .context("Could not send SetNextInterval to timer")?;
+        }
+
+        // Mute/Unmute notifications (the new state will be visible with the next tick)
+        ClientToServerMsg::SetMuted(is_muted) => {
+            info!("Client set muted to {}", is_muted);
+            runtime_state.set_muted(is_muted);
+        }
+
+        // Send timer events of the given kind to this client from now on
+        ClientToServerMsg::Subscribe(kind) => {
+            info!("Client subscribed to {:?}", kind);
+            if !subscriptions.contains(&kind) {
+                subscriptions.push(kind);
+            }
+        }
+
+        // Stop the stopwatch (ignored by all other timers)
+        ClientToServerMsg::StopStopwatch => {
+            timer_input_sender
+                .send(PomodoroTimerAction::StopStopwatch)
+                .context("Could not send StopStopwatch to timer")?;
+        }
+
+        // Pause the timer and resume it once the given time has passed
+        ClientToServerMsg::PauseFor(secs) => {
+            let secs = secs.min(MAX_PAUSE_SECS);
+            info!("Client paused the timer for {} seconds", secs);
+
+            if !runtime_state.is_paused() {
+                timer_input_sender
+                    .send(PomodoroTimerAction::PlayPause)
+                    .context("Could not send Play/Pause to timer")?;
+            }
+
+            let resume_tx = timer_input_sender.clone();
+            let resume_runtime_state = runtime_state.clone();
+            runtime_state.schedule_resume(tokio::spawn(async move {
+                sleep(Duration::from_secs(secs)).await;
+
+                // NOTE: The timer might have been resumed differently in the meantime (e.g. by
+                // the idle pause), in which case it must not be paused again
+                if resume_runtime_state.is_paused() {
+                    info!("Resuming timer after timed pause");
+                    resume_tx.send(PomodoroTimerAction::PlayPause).ok();
+                }
+            }));
+
+            let resume_at = Local::now().trunc_subsecs(0)
+                + chrono::Duration::seconds(i64::try_from(secs).unwrap_or_default());
+            writer
+                .send(ServerToClientMsg::ResumeAt(resume_at))
+                .await
+                .context("Could not send resume time to client")?;
+        }
+
+        // The client is still alive, which is already handled by the connection
+        ClientToServerMsg::Pong => {}
+
+        ClientToServerMsg::SetLabel(label) => {
+            info!("Client set label to {:?}", label);
+            runtime_state.set_label(label);
+        }
+
+        ClientToServerMsg::GetServerInfo => {
+            writer
+                .send(ServerToClientMsg::ServerInfo(runtime_state.server_info()))
+                .await
+                .context("Could not send server info to client")?;
+        }
+
+        // The requesting client itself is not part of the list
+        ClientToServerMsg::ListClients => {
+            writer
+                .send(ServerToClientMsg::Clients(registration.other_clients()))
+                .await
+                .context("Could not send clients to client")?;
+        }
+
+        // Runtime settings (e.g. notifications) are updated right away, while the timer only
+        // applies its new configuration once the next timer starts. Settings which can't be
+        // changed without a restart are reported back to the client.
+        ClientToServerMsg::Reload => {
+            let result = match (setup.load_config)() {
+                Ok(config) => {
+                    runtime_state.apply_config(&config);
+                    let restart_required = setup.config.restart_required_changes(&config);
+
+                    timer_input_sender
+                        .send(PomodoroTimerAction::ReloadConfig(config.timers))
+                        .context("Could not send ReloadConfig to timer")?;
+
+                    info!(
+                        "Client reloaded the config (changes which require a restart: {:?})",
+                        restart_required
+                    );
+                    Ok(restart_required.into_iter().map(String::from).collect())
+                }
+                Err(error) => {
+                    error!("Could not reload config: {:#}", error);
+                    Err(format!("{:#}", error))
+                }
+            };
+
+            writer
+                .send(ServerToClientMsg::Reloaded(result))
+                .await
+                .context("Could not answer reload")?;
+        }
     }
 
     Ok(CloseConnection::No)
@@ -241,11 +924,26 @@ async fn handle_client_to_server_msg(
 
 async fn handle_timer_output_action(
     action: TimerOutputAction,
-    writer: &mut OwnedWriteHalf,
+    subscriptions: &[EventKind],
+    writer: &mut ClientWriter,
 ) -> anyhow::Result<()> {
-    let TimerOutputAction::Timer(state) = action;
-    let msg = ServerToClientMsg::Timer(state);
-    InterProcessCommunication::send_ipc_message(msg, writer)
+    let msg = match action {
+        TimerOutputAction::Timer(state) => ServerToClientMsg::Timer(state),
+        TimerOutputAction::Event(event) => {
+            let is_subscribed = subscriptions
+                .iter()
+                .any(|kind| *kind == EventKind::All || *kind == event.kind());
+
+            if !is_subscribed {
+                return Ok(());
+            }
+
+            ServerToClientMsg::Event(event)
+        }
+    };
+
+    writer
+        .send(msg)
         .await
         .context("Could not send IPC message from server to client")?;
 
@@ -0,0 +1,86 @@
+//! Recording of completed timers inside the stats file (see [crate::stats])
+
+use crate::stats::{append_record, StatsError, StatsRecord};
+use chrono::{DateTime, Local, SubsecRound};
+use std::path::PathBuf;
+use zentime_rs_timer::pomodoro_timer::{TimerKind, ViewState};
+
+/// Tracks the current timer on every tick, so that a complete [StatsRecord] can be written
+/// once it ends
+#[derive(Debug)]
+pub struct StatsRecorder {
+    path: PathBuf,
+
+    /// Kind and round of the current timer
+    current: Option<(TimerKind, u64)>,
+
+    /// Time at which the current timer has first been running (it might start paused)
+    started_at: Option<DateTime<Local>>,
+
+    /// Distractions logged during the current timer
+    distractions: u16,
+
+    /// Label of the current timer
+    label: Option<String>,
+}
+
+impl StatsRecorder {
+    /// Creates a recorder, which appends to the stats file at `path`
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            current: None,
+            started_at: None,
+            distractions: 0,
+            label: None,
+        }
+    }
+
+    /// Tracks the timer of the given state
+    pub fn track(&mut self, state: &ViewState) {
+        let timer = (state.kind, state.round);
+
+        // WHY:
+        // Skipped or reset timers don't end regularly, which is why a new timer is detected by
+        // its kind and round instead.
+        if self.current != Some(timer) {
+            self.current = Some(timer);
+            self.started_at = None;
+        }
+
+        if !state.is_paused && self.started_at.is_none() {
+            self.started_at = Some(Local::now().trunc_subsecs(0));
+        }
+
+        self.distractions = state.distractions;
+
+        if self.label != state.label {
+            self.label.clone_from(&state.label);
+        }
+    }
+
+    /// Appends a record of the current timer, which has just ended
+    pub fn record(&mut self) -> Result<(), StatsError> {
+        let Some((kind, round)) = self.current.take() else {
+            return Ok(());
+        };
+
+        let end = Local::now().trunc_subsecs(0);
+        let record = StatsRecord {
+            start: self.started_at.take().unwrap_or(end),
+            end,
+            kind,
+            round,
+            // NOTE: Other timers still show the distractions of the preceding focus interval,
+            // which must not be counted twice
+            distractions: if kind == TimerKind::Interval {
+                self.distractions
+            } else {
+                0
+            },
+            label: self.label.take(),
+        };
+
+        append_record(&self.path, &record)
+    }
+}
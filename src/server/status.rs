@@ -1,7 +1,9 @@
 //! Code related to server status information
 use std::fmt::Display;
 
-use sysinfo::{ProcessExt, System, SystemExt};
+use sysinfo::{PidExt, ProcessExt, System, SystemExt};
+
+use crate::ipc::get_socket_name;
 
 /// Current status of the zentime server
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -22,9 +24,18 @@ impl Display for ServerStatus {
     }
 }
 
-/// Gets the current status of the zentime server, by checking if a process is running
-/// which was started by a `zentime server`-command.
-pub fn server_status() -> ServerStatus {
+/// Gets the current status of the zentime server listening on `socket_name`, by checking if a
+/// process is running which was started by a `zentime server`-command.
+pub fn server_status(socket_name: &str) -> ServerStatus {
+    if server_pid(socket_name).is_some() {
+        ServerStatus::Running
+    } else {
+        ServerStatus::Stopped
+    }
+}
+
+/// Returns the process id of the zentime server listening on `socket_name` (if one is running)
+pub fn server_pid(socket_name: &str) -> Option<u32> {
     let system = System::new_all();
 
     let mut zentime_process_instances = system.processes_by_name("zentime");
@@ -35,23 +46,49 @@ pub fn server_status() -> ServerStatus {
     //
     // NOTE: During debug builds we use a different socket and therefore the server is not
     // shared with the production one
-    let server_is_running = if cfg!(debug_assertions) {
-        zentime_process_instances.any(|p| {
-            p.cmd()[0].contains(&String::from("target/debug"))
+    let server_process = if cfg!(debug_assertions) {
+        zentime_process_instances.find(|p| {
+            is_debug_binary(p.cmd())
                 && p.cmd().contains(&String::from("server"))
                 && p.cmd().contains(&String::from("start"))
+                && serves_socket(p.cmd(), socket_name)
         })
     } else {
-        zentime_process_instances.any(|p| {
-            !p.cmd()[0].contains(&String::from("target/debug"))
+        zentime_process_instances.find(|p| {
+            !is_debug_binary(p.cmd())
                 && p.cmd().contains(&String::from("server"))
                 && p.cmd().contains(&String::from("start"))
+                && serves_socket(p.cmd(), socket_name)
         })
     };
 
-    if server_is_running {
-        ServerStatus::Running
-    } else {
-        ServerStatus::Stopped
+    server_process.map(|p| p.pid().as_u32())
+}
+
+/// Checks if the process with the given command line arguments has been started from a debug build.
+///
+/// NOTE: A process which is just about to exit might not report any command line arguments at all
+fn is_debug_binary(cmd: &[String]) -> bool {
+    cmd.first()
+        .is_some_and(|executable| executable.contains("target/debug"))
+}
+
+/// Checks if a server process with the given command line arguments listens on `socket_name`.
+///
+/// WHY:
+/// Multiple independent servers might be running on different sockets. A server which has
+/// been started without an explicit `--socket` argument is assumed to use the default socket.
+fn serves_socket(cmd: &[String], socket_name: &str) -> bool {
+    let socket_arg = cmd.iter().enumerate().find_map(|(index, arg)| {
+        if arg == "--socket" {
+            cmd.get(index + 1).map(|value| value.as_str())
+        } else {
+            arg.strip_prefix("--socket=")
+        }
+    });
+
+    match socket_arg {
+        Some(socket_arg) => shellexpand::tilde(socket_arg.trim()) == socket_name,
+        None => socket_name == get_socket_name(),
     }
 }
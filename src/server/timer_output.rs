@@ -1,8 +1,144 @@
+use crate::ipc::TimerEvent;
+use log::info;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::broadcast::Receiver as BroadcastReceiver;
 use zentime_rs_timer::pomodoro_timer::ViewState;
 
-/// Carries the timer state as view state
+/// Carries the timer state as view state or a timer event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TimerOutputAction {
     Timer(ViewState),
+    Event(TimerEvent),
+}
+
+/// Receives [TimerOutputAction]s from the timer broadcast channel.
+/// If the receiver has lagged behind (e.g. because its client stalled), all queued view states
+/// except the freshest one are skipped. Queued events are still forwarded in order.
+/// NOTE: Actions which the channel itself has already dropped can't be recovered.
+pub struct TimerOutputReceiver {
+    receiver: BroadcastReceiver<TimerOutputAction>,
+    pending: VecDeque<TimerOutputAction>,
+}
+
+impl TimerOutputReceiver {
+    pub fn new(receiver: BroadcastReceiver<TimerOutputAction>) -> Self {
+        Self {
+            receiver,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Receives the next [TimerOutputAction].
+    /// This is cancel safe, because actions are only ever buffered synchronously.
+    pub async fn recv(&mut self) -> Result<TimerOutputAction, RecvError> {
+        loop {
+            if let Some(action) = self.pending.pop_front() {
+                return Ok(action);
+            }
+
+            match self.receiver.recv().await {
+                Err(RecvError::Lagged(skipped)) => {
+                    info!(
+                        "Receiver lagged behind - skipping {} timer outputs",
+                        skipped
+                    );
+
+                    self.drain_queue();
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Drains all queued actions into the pending buffer, keeping every event and only the
+    /// freshest view state
+    fn drain_queue(&mut self) {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(action @ TimerOutputAction::Timer(_)) => {
+                    self.pending
+                        .retain(|pending| !matches!(pending, TimerOutputAction::Timer(_)));
+                    self.pending.push_back(action);
+                }
+                Ok(action @ TimerOutputAction::Event(_)) => self.pending.push_back(action),
+                Err(TryRecvError::Lagged(_)) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => return,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::broadcast::channel;
+
+    fn view_state(round: u64) -> ViewState {
+        ViewState {
+            round,
+            time: "25:00".to_string(),
+            remaining_secs: 1500,
+            ..ViewState::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn lagging_receiver_skips_to_freshest_view_state() {
+        let (sender, receiver) = channel(4);
+        let mut receiver = TimerOutputReceiver::new(receiver);
+
+        // The receiver stalls while the timer keeps sending
+        for round in 1..=10 {
+            sender
+                .send(TimerOutputAction::Timer(view_state(round)))
+                .ok();
+        }
+
+        let action = receiver
+            .recv()
+            .await
+            .expect("Lagged receiver should not error");
+        assert!(matches!(action, TimerOutputAction::Timer(state) if state.round == 10));
+
+        // Nothing outdated is left in the queue
+        sender.send(TimerOutputAction::Timer(view_state(11))).ok();
+        let action = receiver.recv().await.expect("Receiver should not error");
+        assert!(matches!(action, TimerOutputAction::Timer(state) if state.round == 11));
+    }
+
+    #[tokio::test]
+    async fn lagging_receiver_still_forwards_queued_events() {
+        let (sender, receiver) = channel(4);
+        let mut receiver = TimerOutputReceiver::new(receiver);
+
+        for round in 1..=6 {
+            sender
+                .send(TimerOutputAction::Timer(view_state(round)))
+                .ok();
+        }
+        sender
+            .send(TimerOutputAction::Event(TimerEvent::TimerPaused))
+            .ok();
+        sender.send(TimerOutputAction::Timer(view_state(7))).ok();
+        sender
+            .send(TimerOutputAction::Event(TimerEvent::TimerResumed))
+            .ok();
+
+        let mut received = vec![];
+        for _ in 0..3 {
+            received.push(receiver.recv().await.expect("Receiver should not error"));
+        }
+
+        assert!(matches!(
+            received[0],
+            TimerOutputAction::Event(TimerEvent::TimerPaused)
+        ));
+        assert!(matches!(&received[1], TimerOutputAction::Timer(state) if state.round == 7));
+        assert!(matches!(
+            received[2],
+            TimerOutputAction::Event(TimerEvent::TimerResumed)
+        ));
+    }
 }
@@ -0,0 +1,99 @@
+//! Webhook, which is called on every notification (see `webhook_url`), e.g. to integrate zentime
+//! with chat bots or home automation.
+//! Webhooks are only sent if zentime has been built with the `http` feature.
+
+use crate::config::{NotificationConfig, NotificationEvent};
+#[cfg(feature = "http")]
+use anyhow::{bail, Context};
+#[cfg(feature = "http")]
+use log::error;
+use log::info;
+use serde::Serialize;
+#[cfg(feature = "http")]
+use std::thread;
+#[cfg(feature = "http")]
+use std::time::Duration;
+
+/// Time after which connecting to, writing to or reading from the webhook is given up on
+#[cfg(feature = "http")]
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Details of the timer a notification belongs to
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TimerDetails {
+    /// Round of the timer, if the event belongs to one
+    pub round: Option<u64>,
+
+    /// Label of the current timer (if any)
+    pub label: Option<String>,
+}
+
+/// JSON body which is posted to the webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    /// Event which has caused the notification
+    pub event: NotificationEvent,
+
+    /// Round and label (flattened into the body)
+    #[serde(flatten)]
+    pub details: TimerDetails,
+}
+
+/// Posts the event to the configured `webhook_url` (if any).
+///
+/// NOTE: Webhooks are meant for automations, which is why they are sent regardless of muting and
+/// `notification_debounce_ms` (unlike the bell and OS-notifications).
+pub fn notify_webhook(
+    config: &NotificationConfig,
+    event: NotificationEvent,
+    details: TimerDetails,
+) {
+    if let Some(webhook_url) = &config.webhook_url {
+        send_webhook(webhook_url.clone(), WebhookPayload { event, details });
+    }
+}
+
+/// Posts the payload to the given URL on a separate thread, so that a slow webhook never blocks
+/// the timer. Failures are only logged.
+#[cfg(feature = "http")]
+pub fn send_webhook(url: String, payload: WebhookPayload) {
+    thread::spawn(move || {
+        if let Err(error) = post(&url, &payload) {
+            error!("Could not send webhook to {}: {:#}", url, error);
+        }
+    });
+}
+
+/// Only logs that webhooks are unavailable
+#[cfg(not(feature = "http"))]
+pub fn send_webhook(_url: String, _payload: WebhookPayload) {
+    info!("Not sending webhook, because zentime has been built without the `http` feature");
+}
+
+/// Sends a single `POST` request (via `http://` or `https://`) and fails unless the webhook
+/// responds with a 2xx status.
+#[cfg(feature = "http")]
+fn post(url: &str, payload: &WebhookPayload) -> anyhow::Result<()> {
+    let body = serde_json::to_string(payload).context("Could not serialize payload")?;
+    let agent = ureq::AgentBuilder::new().timeout(WEBHOOK_TIMEOUT).build();
+
+    match agent
+        .post(url)
+        .set("Content-Type", "application/json")
+        .send_string(&body)
+    {
+        Ok(response) if (200..300).contains(&response.status()) => {
+            info!("Sent webhook ({:?})", payload.event);
+            Ok(())
+        }
+        Ok(response) => bail!(
+            "Unexpected response: {} {}",
+            response.status(),
+            response.status_text()
+        ),
+        Err(ureq::Error::Status(status, response)) => {
+            bail!("Unexpected response: {} {}", status, response.status_text())
+        }
+        Err(error) => Err(error).context("Could not send request"),
+    }
+}
@@ -0,0 +1,216 @@
+//! Persistence of completed timers inside the stats file (see `server.stats_file`).
+//! Every completed timer is appended as a single line of JSON (a [StatsRecord]).
+
+use chrono::{DateTime, Days, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::Path;
+use thiserror::Error;
+use zentime_rs_timer::pomodoro_timer::TimerKind;
+
+/// A single completed timer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsRecord {
+    /// Time at which the timer has first been started
+    pub start: DateTime<Local>,
+
+    /// Time at which the timer has ended
+    pub end: DateTime<Local>,
+
+    /// Kind of the timer.
+    /// Postponed breaks are recorded as such (and not as [TimerKind::Interval]), because they
+    /// extend the preceding focus interval.
+    pub kind: TimerKind,
+
+    /// Round of the session the timer belonged to
+    pub round: u64,
+
+    /// Number of distractions logged during the timer (only counted during focus intervals)
+    #[serde(default)]
+    pub distractions: u16,
+
+    /// Label the timer had when it ended (see [zentime_rs_timer::pomodoro_timer::ViewState::label])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Something went wrong while accessing the stats file
+#[derive(Debug, Error)]
+pub enum StatsError {
+    /// The stats file could not be read or written
+    #[error("Could not access stats file {0}")]
+    Io(String, #[source] std::io::Error),
+
+    /// A line of the stats file is not a valid [StatsRecord]
+    #[error("Invalid record in line {line} of stats file {path}")]
+    InvalidRecord {
+        /// Path of the stats file
+        path: String,
+
+        /// Line number of the invalid record (starting at 1)
+        line: usize,
+
+        /// Reason why the record could not be parsed
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// Appends the given record to the stats file at `path`.
+/// The file (and its parent directories) are created, if necessary.
+pub fn append_record(path: &Path, record: &StatsRecord) -> Result<(), StatsError> {
+    let io_error = |error| StatsError::Io(path.display().to_string(), error);
+
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(io_error)?;
+    }
+
+    let line = serde_json::to_string(record).map_err(|error| io_error(error.into()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(io_error)?;
+
+    writeln!(file, "{}", line).map_err(io_error)
+}
+
+/// Reads all records of the stats file at `path` (oldest first).
+/// A missing stats file is treated like an empty one, because nothing has been recorded yet.
+pub fn read_records(path: &Path) -> Result<Vec<StatsRecord>, StatsError> {
+    let io_error = |error| StatsError::Io(path.display().to_string(), error);
+
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(io_error(error)),
+    };
+
+    let mut records = vec![];
+
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(io_error)?;
+
+        // NOTE: Empty lines might be left behind by manual edits
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record = serde_json::from_str(&line).map_err(|source| StatsError::InvalidRecord {
+            path: path.display().to_string(),
+            line: index + 1,
+            source,
+        })?;
+
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+/// Keeps the records, which have been started within the given (local) days.
+/// Both days are inclusive and each of them may be omitted.
+pub fn filter_by_day(
+    records: Vec<StatsRecord>,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+) -> Vec<StatsRecord> {
+    records
+        .into_iter()
+        .filter(|record| {
+            let day = record.start.date_naive();
+
+            since.is_none_or(|since| day >= since) && until.is_none_or(|until| day <= until)
+        })
+        .collect()
+}
+
+/// Keeps the records with the given label (all records, if no label is given).
+/// Labels are compared exactly, which is why records without any label never match.
+pub fn filter_by_label(records: Vec<StatsRecord>, label: Option<&str>) -> Vec<StatsRecord> {
+    records
+        .into_iter()
+        .filter(|record| label.is_none_or(|label| record.label.as_deref() == Some(label)))
+        .collect()
+}
+
+/// Totals of a number of records
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StatsSummary {
+    /// Number of completed focus intervals
+    pub intervals: u64,
+
+    /// Time spent in focus intervals and postponed breaks (in seconds)
+    pub focus_secs: u64,
+
+    /// Time spent in short and long breaks (in seconds)
+    pub break_secs: u64,
+
+    /// Number of distractions logged during the focus intervals
+    pub distractions: u64,
+}
+
+impl StatsSummary {
+    /// Sums up the given records.
+    /// The time of a record is measured from its start until its end and therefore includes
+    /// pauses.
+    pub fn from_records(records: &[StatsRecord]) -> Self {
+        records.iter().fold(Self::default(), |mut summary, record| {
+            let secs = (record.end - record.start).num_seconds().max(0) as u64;
+
+            match record.kind {
+                TimerKind::Interval => {
+                    summary.intervals += 1;
+                    summary.focus_secs += secs;
+                }
+                TimerKind::PostponedBreak | TimerKind::PostponedLongBreak => {
+                    summary.focus_secs += secs;
+                }
+                TimerKind::Break | TimerKind::LongBreak => summary.break_secs += secs,
+                TimerKind::SessionComplete => {}
+            }
+
+            summary.distractions += u64::from(record.distractions);
+            summary
+        })
+    }
+}
+
+/// Error returned by [parse_day], if a day could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid day \"{0}\" (expected e.g. \"2024-01-31\", \"today\" or \"7d\")")]
+pub struct ParseDayError(String);
+
+/// Parses a day, which is either given as date (e.g. `2024-01-31`), as `today` or relative to
+/// `today` (e.g. `7d` for seven days ago).
+///
+/// ## Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use zentime_rs::stats::parse_day;
+///
+/// let today = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+///
+/// assert_eq!(parse_day("2024-01-01", today), Ok(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()));
+/// assert_eq!(parse_day("today", today), Ok(today));
+/// assert_eq!(parse_day("7d", today), Ok(NaiveDate::from_ymd_opt(2024, 1, 24).unwrap()));
+/// assert!(parse_day("last week", today).is_err());
+/// ```
+pub fn parse_day(day: &str, today: NaiveDate) -> Result<NaiveDate, ParseDayError> {
+    let error = || ParseDayError(day.to_string());
+    let trimmed = day.trim();
+
+    if trimmed == "today" {
+        return Ok(today);
+    }
+
+    if let Some(days) = trimmed.strip_suffix('d') {
+        let days = days.parse::<u64>().map_err(|_| error())?;
+        return today.checked_sub_days(Days::new(days)).ok_or_else(error);
+    }
+
+    NaiveDate::parse_from_str(trimmed, "%Y-%m-%d").map_err(|_| error())
+}
@@ -1,7 +1,22 @@
+#[cfg(feature = "ws")]
+pub mod bridge;
+pub mod completions;
+pub mod config;
+pub mod export;
+pub mod focus_now;
+pub mod mute;
+pub mod pause;
 pub mod postpone;
+pub mod previous_interval;
 pub mod query_server_once;
+pub mod remaining;
 pub mod reset_timer;
 pub mod server;
+pub mod set_next_interval;
 pub mod set_timer;
 pub mod skip_timer;
+pub mod sound;
+pub mod stats;
+pub mod stopwatch;
 pub mod toggle_timer;
+pub mod watch;
@@ -0,0 +1,196 @@
+//! WebSocket bridge, which exposes the timer to browser based widgets (e.g. Electron/Tauri overlays)
+
+use anyhow::Context;
+use futures::io::BufReader;
+use futures::{SinkExt, StreamExt};
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::select;
+use tokio::sync::broadcast::{self, error::RecvError, Receiver as BroadcastReceiver};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::{accept_hdr_async, tungstenite::Message};
+use zentime_rs::client::one_shot_connection::one_shot_connection;
+use zentime_rs::ipc::{ClientToServerMsg, InterProcessCommunication, ServerToClientMsg};
+
+/// Connects to the server as a regular client and serves a WebSocket on `127.0.0.1:<ws_port>`.
+/// Each [zentime_rs_timer::pomodoro_timer::ViewState] is rebroadcast as JSON to all connected
+/// WebSocket clients, which in turn may send the text commands
+/// `play-pause`, `skip`, `postpone`, `reset`, `previous` and `distraction`.
+///
+/// Handshakes of browsers are only accepted from the `allowed_origins`, so that an arbitrary
+/// website can't control the timer via `ws://127.0.0.1:<ws_port>`. Clients which don't send an
+/// `Origin` header (i.e. no browsers) are always accepted.
+#[tokio::main]
+pub async fn bridge(socket_name: &str, ws_port: u16, allowed_origins: Vec<String>) {
+    if let Err(error) = run(socket_name, ws_port, allowed_origins.into()).await {
+        eprintln!("{:#}", error);
+        std::process::exit(1);
+    }
+}
+
+async fn run(
+    socket_name: &str,
+    ws_port: u16,
+    allowed_origins: Arc<[String]>,
+) -> anyhow::Result<()> {
+    let (reader, mut writer) = one_shot_connection(socket_name)
+        .await
+        .context("Could not connect to server")?;
+    let mut reader = BufReader::new(reader);
+
+    let listener = TcpListener::bind(("127.0.0.1", ws_port))
+        .await
+        .with_context(|| format!("Could not bind WebSocket bridge to port {}", ws_port))?;
+
+    println!("Serving WebSocket bridge on ws://127.0.0.1:{}", ws_port);
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Sync, &mut writer)
+        .await
+        .context("Could not sync with server")?;
+
+    let (view_state_tx, _) = broadcast::channel::<String>(24);
+    let (command_tx, mut command_rx) = unbounded_channel::<ClientToServerMsg>();
+
+    loop {
+        select! {
+            msg = InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader) => {
+                match msg.context("Lost connection to server")? {
+                    ServerToClientMsg::Timer(state) => {
+                        let json = serde_json::to_string(&state).context("Could not serialize timer state")?;
+
+                        // Sending only fails while no WebSocket client is connected
+                        view_state_tx.send(json).ok();
+                    }
+                    ServerToClientMsg::Ping => {
+                        InterProcessCommunication::send_ipc_message(ClientToServerMsg::Pong, &mut writer)
+                            .await
+                            .context("Could not answer ping of the server")?;
+                    }
+                    ServerToClientMsg::Event(_)
+                    | ServerToClientMsg::Clients(_)
+                    | ServerToClientMsg::ResumeAt(_)
+                    | ServerToClientMsg::HandshakeAck(_)
+                    | ServerToClientMsg::ServerInfo(_)
+                    | ServerToClientMsg::Reloaded(_) => {}
+                }
+            },
+
+            Some(msg) = command_rx.recv() => {
+                InterProcessCommunication::send_ipc_message(msg, &mut writer)
+                    .await
+                    .context("Could not send command to server")?;
+            },
+
+            connection = listener.accept() => {
+                let (stream, _) = connection.context("Could not accept WebSocket connection")?;
+                let view_state_rx = view_state_tx.subscribe();
+                let command_tx = command_tx.clone();
+                let allowed_origins = allowed_origins.clone();
+
+                tokio::spawn(async move {
+                    if let Err(error) = handle_ws_client(stream, &allowed_origins, view_state_rx, command_tx).await {
+                        eprintln!("WebSocket client disconnected: {:#}", error);
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Forwards timer states to a single WebSocket client and its commands to the server
+async fn handle_ws_client(
+    stream: TcpStream,
+    allowed_origins: &[String],
+    mut view_state_rx: BroadcastReceiver<String>,
+    command_tx: UnboundedSender<ClientToServerMsg>,
+) -> anyhow::Result<()> {
+    // WHY: The size of the error response is dictated by tungstenite's handshake callback
+    #[allow(clippy::result_large_err)]
+    let check_origin = |request: &Request, response: Response| {
+        let origin = request
+            .headers()
+            .get("origin")
+            .map(|origin| origin.to_str().unwrap_or_default());
+
+        if is_allowed_origin(origin, allowed_origins) {
+            Ok(response)
+        } else {
+            let mut error = ErrorResponse::new(Some(format!(
+                "Origin {} is not allowed (see `zentime bridge --allow-origin`)",
+                origin.unwrap_or_default()
+            )));
+            *error.status_mut() = StatusCode::FORBIDDEN;
+            Err(error)
+        }
+    };
+
+    let ws_stream = accept_hdr_async(stream, check_origin)
+        .await
+        .context("WebSocket handshake failed")?;
+    let (mut ws_tx, mut ws_rx) = ws_stream.split();
+
+    loop {
+        select! {
+            view_state = view_state_rx.recv() => match view_state {
+                Ok(json) => ws_tx.send(Message::Text(json)).await.context("Could not send timer state")?,
+                // The client can simply continue with the latest state
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            },
+
+            msg = ws_rx.next() => match msg {
+                Some(Ok(Message::Text(text))) => match parse_command(&text) {
+                    Some(command) => command_tx.send(command).context("Bridge has shut down")?,
+                    None => eprintln!("Unknown WebSocket command: {}", text.trim()),
+                },
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => continue,
+                Some(Err(error)) => return Err(error).context("Could not receive WebSocket message"),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Translates a WebSocket text command into the matching [ClientToServerMsg]
+fn parse_command(text: &str) -> Option<ClientToServerMsg> {
+    match text.trim() {
+        "play-pause" => Some(ClientToServerMsg::PlayPause),
+        "skip" => Some(ClientToServerMsg::Skip),
+        "postpone" => Some(ClientToServerMsg::PostPone),
+        "reset" => Some(ClientToServerMsg::Reset),
+        "previous" => Some(ClientToServerMsg::Previous),
+        "distraction" => Some(ClientToServerMsg::Distraction),
+        _ => None,
+    }
+}
+
+/// Checks whether a WebSocket handshake with the given `Origin` header may connect. Requests
+/// without an origin don't come from a browser and therefore can't be sent by a website.
+fn is_allowed_origin(origin: Option<&str>, allowed_origins: &[String]) -> bool {
+    origin.is_none_or(|origin| allowed_origins.iter().any(|allowed| allowed == origin))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_allowed_origins_may_connect() {
+        let allowed_origins = vec!["tauri://localhost".to_string()];
+
+        assert!(is_allowed_origin(None, &allowed_origins));
+        assert!(is_allowed_origin(
+            Some("tauri://localhost"),
+            &allowed_origins
+        ));
+        assert!(!is_allowed_origin(
+            Some("https://example.com"),
+            &allowed_origins
+        ));
+        assert!(!is_allowed_origin(Some("https://example.com"), &[]));
+    }
+}
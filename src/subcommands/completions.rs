@@ -0,0 +1,10 @@
+use clap::Command;
+use clap_complete::{generate, Shell};
+use std::io;
+
+/// Prints the completion script of the given shell for the zentime cli to stdout
+pub fn completions(shell: Shell, cli: &mut Command) {
+    // NOTE: The command name defaults to the package name (`zentime-rs`), but completions have to
+    // be registered for the name of the binary
+    generate(shell, cli, env!("CARGO_BIN_NAME"), &mut io::stdout());
+}
@@ -0,0 +1,43 @@
+use figment::Figment;
+use std::path::Path;
+use std::process;
+use zentime_rs::config::Config;
+
+/// Prints the fully merged configuration (defaults + config file + cli flags) as TOML
+pub fn show(config: &Config) {
+    match toml::to_string_pretty(config) {
+        Ok(serialized_config) => print!("{}", serialized_config),
+        Err(error) => {
+            eprintln!("Could not serialize config: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/// Loads and validates the configuration file at `config_path` (merged into `figment`) without
+/// starting a server or client. Terminates with a non-zero exit code and a list of all
+/// problems, if the configuration is invalid.
+pub fn check(config_path: &Path, figment: Figment) {
+    if !config_path.exists() {
+        eprintln!("Config file does not exist: {}", config_path.display());
+        process::exit(1);
+    }
+
+    let config: Config = match figment.extract() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("Invalid configuration:");
+            for error in error {
+                eprintln!("  - {}", error);
+            }
+            process::exit(1);
+        }
+    };
+
+    if let Err(error) = config.validate() {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+
+    println!("{} is valid", config_path.display());
+}
@@ -0,0 +1,123 @@
+use super::stats::read_stats_or_exit;
+use chrono::{DateTime, NaiveDate, SecondsFormat, TimeZone, Utc};
+use clap::ValueEnum;
+use zentime_rs::config::Config;
+use zentime_rs::stats::{filter_by_day, StatsRecord};
+use zentime_rs_timer::pomodoro_timer::TimerKind;
+
+/// Output formats of `zentime export`
+#[derive(ValueEnum, Copy, Clone, Debug, Default)]
+pub enum ExportFormat {
+    /// One row per timer (start, end, kind, label) with a header row
+    #[default]
+    Csv,
+
+    /// iCalendar file with one event per timer
+    Ical,
+}
+
+/// Prints all records of the stats file, which have been started on or after `since`, in the
+/// given format
+pub fn export(config: &Config, format: ExportFormat, since: Option<NaiveDate>) {
+    let records = filter_by_day(read_stats_or_exit(config), since, None);
+
+    match format {
+        ExportFormat::Csv => print!("{}", to_csv(&records)),
+        ExportFormat::Ical => print!("{}", to_ical(&records, Utc::now())),
+    }
+}
+
+/// CSV with a header row, which is the only row if there are no records
+fn to_csv(records: &[StatsRecord]) -> String {
+    let mut csv = String::from("start,end,kind,label\n");
+
+    for record in records {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            record.start.to_rfc3339_opts(SecondsFormat::Secs, false),
+            record.end.to_rfc3339_opts(SecondsFormat::Secs, false),
+            kind_name(record.kind),
+            csv_field(record.label.as_deref().unwrap_or_default())
+        ));
+    }
+
+    csv
+}
+
+/// Quotes a CSV field (RFC 4180), if it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// iCalendar (RFC 5545) with one VEVENT per record.
+/// A calendar without any events is still valid, if there are no records.
+fn to_ical(records: &[StatsRecord], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//zentime//zentime export//EN"),
+    ];
+
+    for record in records {
+        lines.extend([
+            String::from("BEGIN:VEVENT"),
+            // Timers never overlap, which makes their times a stable identifier
+            format!(
+                "UID:{}-{}@zentime",
+                record.start.timestamp(),
+                record.end.timestamp()
+            ),
+            format!("DTSTAMP:{}", ical_time(now)),
+            format!("DTSTART:{}", ical_time(record.start)),
+            format!("DTEND:{}", ical_time(record.end)),
+        ]);
+
+        match &record.label {
+            Some(label) => lines.extend([
+                format!("SUMMARY:{}: {}", kind_name(record.kind), ical_text(label)),
+                format!("CATEGORIES:{}", ical_text(label)),
+            ]),
+            None => lines.push(format!("SUMMARY:{}", kind_name(record.kind))),
+        }
+
+        lines.push(String::from("END:VEVENT"));
+    }
+
+    lines.push(String::from("END:VCALENDAR"));
+
+    // NOTE: iCalendar requires CRLF line endings
+    lines
+        .into_iter()
+        .map(|line| format!("{}\r\n", line))
+        .collect()
+}
+
+/// Escapes a TEXT value as required by iCalendar (RFC 5545, section 3.3.11)
+fn ical_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// UTC date-time as required by iCalendar, e.g. `20240101T090000Z`
+fn ical_time<Tz: TimeZone>(time: DateTime<Tz>) -> String {
+    time.with_timezone(&Utc)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+fn kind_name(kind: TimerKind) -> &'static str {
+    match kind {
+        TimerKind::Interval => "Focus",
+        TimerKind::Break => "Break",
+        TimerKind::LongBreak => "Long break",
+        TimerKind::PostponedBreak | TimerKind::PostponedLongBreak => "Postponed break",
+        TimerKind::SessionComplete => "Session complete",
+    }
+}
@@ -0,0 +1,88 @@
+use super::query_server_once::status_line;
+use futures::io::BufReader;
+use std::time::Duration;
+use tokio::time::timeout;
+use zentime_rs::config::Config;
+use zentime_rs::ipc::ClientToServerMsg;
+use zentime_rs::ipc::InterProcessCommunication;
+use zentime_rs::ipc::ServerToClientMsg;
+use zentime_rs::server::status::server_status;
+use zentime_rs::server::status::ServerStatus;
+
+use crate::default_cmd::warn_about_ignored_session_flags;
+use crate::subcommands::server::spawn_server;
+use crate::{connect_or_exit, CommonArgs};
+
+/// Maximum time we wait for the server to report the running timer
+const START_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Starts the server (if necessary) and unpauses the current timer without attaching a client.
+/// Prints the resulting timer state once.
+///
+/// NOTE: The server is spawned before entering the async context, because spawning it blocks
+/// until the daemonization has finished.
+pub fn focus_now(common_args: &CommonArgs, config: Config) {
+    if server_status(&config.server.socket_name()) == ServerStatus::Stopped {
+        spawn_server(common_args, &config, &[]);
+    } else {
+        warn_about_ignored_session_flags(common_args);
+    }
+
+    start_focus(config);
+}
+
+#[tokio::main]
+async fn start_focus(config: Config) {
+    let socket_name = config.server.socket_name();
+    let (reader, mut writer) = connect_or_exit(&socket_name).await;
+
+    let mut reader = BufReader::new(reader);
+
+    if let Err(err) =
+        InterProcessCommunication::send_ipc_message(ClientToServerMsg::Sync, &mut writer).await
+    {
+        panic!("Could not sync with server: {}", err)
+    };
+
+    let mut state =
+        match InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await {
+            Ok(ServerToClientMsg::Timer(state)) => state,
+            _ => panic!("Could not receive timer state from the server"),
+        };
+
+    // WHY:
+    // PlayPause would pause an already running timer again
+    if state.is_paused {
+        if let Err(err) =
+            InterProcessCommunication::send_ipc_message(ClientToServerMsg::PlayPause, &mut writer)
+                .await
+        {
+            panic!("Could not send to the server: {}", err)
+        };
+
+        let _ = timeout(START_TIMEOUT, async {
+            while state.is_paused {
+                match InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader)
+                    .await
+                {
+                    Ok(ServerToClientMsg::Timer(next_state)) => state = next_state,
+                    Ok(_) => continue,
+                    Err(_) => break,
+                }
+            }
+        })
+        .await;
+    }
+
+    if !config.view.silent {
+        println!(
+            "{} {}",
+            status_line(&state),
+            if state.is_paused { "paused" } else { "running" }
+        );
+    }
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+        .await
+        .ok();
+}
@@ -0,0 +1,33 @@
+use crate::connect_or_exit;
+use futures::io::BufReader;
+use zentime_rs::ipc::ClientToServerMsg;
+use zentime_rs::ipc::InterProcessCommunication;
+use zentime_rs::ipc::ServerToClientMsg;
+
+/// Mutes or unmutes all notifications of the server, while the timer keeps running
+#[tokio::main]
+pub async fn set_muted(socket_name: &str, silent: bool, muted: bool) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
+
+    let mut reader = BufReader::new(reader);
+
+    if let Err(err) =
+        InterProcessCommunication::send_ipc_message(ClientToServerMsg::SetMuted(muted), &mut writer)
+            .await
+    {
+        panic!("Could not send to the server: {}", err)
+    };
+
+    // Wait for the server to respond, so that we know our message has been received
+    InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader)
+        .await
+        .ok();
+
+    if !silent {
+        println!("Notifications {}", if muted { "muted" } else { "unmuted" });
+    }
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+        .await
+        .ok();
+}
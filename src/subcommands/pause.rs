@@ -0,0 +1,43 @@
+use crate::connect_or_exit;
+use futures::io::BufReader;
+use zentime_rs::ipc::ClientToServerMsg;
+use zentime_rs::ipc::InterProcessCommunication;
+use zentime_rs::ipc::ServerToClientMsg;
+
+/// Pauses the timer for `duration` seconds, after which the server resumes it on its own
+/// (unless it has been resumed manually before), and prints the time of the resume
+#[tokio::main]
+pub async fn pause_for(socket_name: &str, silent: bool, duration: u64) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
+
+    let mut reader = BufReader::new(reader);
+
+    if let Err(err) = InterProcessCommunication::send_ipc_message(
+        ClientToServerMsg::PauseFor(duration),
+        &mut writer,
+    )
+    .await
+    {
+        panic!("Could not send to the server: {}", err)
+    };
+
+    // NOTE: Timer states might arrive before the answer
+    let resume_at = loop {
+        match InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await {
+            Ok(ServerToClientMsg::ResumeAt(resume_at)) => break resume_at,
+            Ok(_) => continue,
+            Err(error) => {
+                eprintln!("Could not receive resume time: {}", error);
+                std::process::exit(1);
+            }
+        }
+    };
+
+    if !silent {
+        println!("Paused until {}", resume_at.format("%H:%M:%S"));
+    }
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+        .await
+        .ok();
+}
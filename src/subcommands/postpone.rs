@@ -1,15 +1,13 @@
+use super::query_server_once::status_line;
+use crate::connect_or_exit;
 use futures::io::BufReader;
-use zentime_rs::client::one_shot_connection::one_shot_connection;
 use zentime_rs::ipc::ClientToServerMsg;
 use zentime_rs::ipc::InterProcessCommunication;
 use zentime_rs::ipc::ServerToClientMsg;
 
 #[tokio::main]
-pub async fn postpone(silent: bool) {
-    let (reader, mut writer) = match one_shot_connection().await {
-        Ok(c) => c,
-        Err(error) => panic!("Could not conenct to server: {}", error),
-    };
+pub async fn postpone(socket_name: &str, silent: bool) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
 
     let mut reader = BufReader::new(reader);
 
@@ -24,12 +22,7 @@ pub async fn postpone(silent: bool) {
 
     if !silent {
         if let Ok(ServerToClientMsg::Timer(state)) = msg_result {
-            println!(
-                "{} {} {}",
-                state.round,
-                state.time,
-                if state.is_break { "Break" } else { "Focus" }
-            );
+            println!("{}", status_line(&state));
         }
     }
 
@@ -1,14 +1,12 @@
+use crate::connect_or_exit;
 use futures::io::BufReader;
-use zentime_rs::client::one_shot_connection::one_shot_connection;
 use zentime_rs::ipc::ClientToServerMsg;
 use zentime_rs::ipc::{InterProcessCommunication, ServerToClientMsg};
+use zentime_rs_timer::pomodoro_timer::{TimerKind, ViewState};
 
 #[tokio::main]
-pub async fn query_server_once() {
-    let (reader, mut writer) = match one_shot_connection().await {
-        Ok(c) => c,
-        Err(error) => panic!("Could not conenct to server: {}", error),
-    };
+pub async fn query_server_once(socket_name: &str) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
 
     let mut reader = BufReader::new(reader);
 
@@ -22,15 +20,31 @@ pub async fn query_server_once() {
         InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await;
 
     if let Ok(ServerToClientMsg::Timer(state)) = msg_result {
-        println!(
-            "{} {} {}",
-            state.round,
-            state.time,
-            if state.is_break { "Break" } else { "Focus" }
-        );
+        println!("{}", status_line(&state));
     }
 
     InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
         .await
         .ok();
 }
+
+/// Single line status of the given state like `2 04:59 Break`, which is printed by the one-shot
+/// commands.
+/// Distinguishes the same kinds of timers as the terminal interface (`Focus`, `Stopwatch`,
+/// `Overtime`, `Break`, `LongBreak`, `Postponed` followed by the number of postponements, or
+/// `Complete`).
+pub fn status_line(state: &ViewState) -> String {
+    let kind = match state.kind {
+        TimerKind::SessionComplete => String::from("Complete"),
+        TimerKind::Interval if state.is_stopwatch => String::from("Stopwatch"),
+        TimerKind::Interval if state.is_overtime => String::from("Overtime"),
+        TimerKind::Interval => String::from("Focus"),
+        TimerKind::Break => String::from("Break"),
+        TimerKind::LongBreak => String::from("LongBreak"),
+        TimerKind::PostponedBreak | TimerKind::PostponedLongBreak => {
+            format!("Postponed ({})", state.postpone_count)
+        }
+    };
+
+    format!("{} {} {}", state.round, state.time, kind)
+}
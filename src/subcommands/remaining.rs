@@ -0,0 +1,36 @@
+use crate::connect_or_exit;
+use futures::io::BufReader;
+use zentime_rs::ipc::ClientToServerMsg;
+use zentime_rs::ipc::{InterProcessCommunication, ServerToClientMsg};
+
+/// Prints the remaining time of the current timer (e.g. `24:59` or `1499` if `as_secs` is set).
+// WHY:
+// This is meant to be run by shell prompts, which is why we avoid the startup cost of a
+// multi-threaded runtime.
+#[tokio::main(flavor = "current_thread")]
+pub async fn remaining(socket_name: &str, as_secs: bool) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
+
+    let mut reader = BufReader::new(reader);
+
+    if let Err(err) =
+        InterProcessCommunication::send_ipc_message(ClientToServerMsg::Sync, &mut writer).await
+    {
+        panic!("Could not sync with server: {}", err)
+    };
+
+    let msg_result =
+        InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await;
+
+    if let Ok(ServerToClientMsg::Timer(state)) = msg_result {
+        if as_secs {
+            println!("{}", state.remaining_secs);
+        } else {
+            println!("{}", state.time);
+        }
+    }
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+        .await
+        .ok();
+}
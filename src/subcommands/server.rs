@@ -1,46 +1,65 @@
+use anyhow::Context;
+use chrono::{DateTime, Local};
 use daemonize::Daemonize;
 use figment::providers::Serialized;
+use futures::io::BufReader;
 use interprocess::local_socket::tokio::LocalSocketStream;
 use log::{error, info};
-use std::env::current_dir;
+use serde::Serialize;
+use std::env::{current_dir, current_exe};
 use std::fs::File;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::process::{self, Command};
+use std::sync::Arc;
 use std::thread::sleep;
-use std::time::Duration;
-use zentime_rs::config::create_base_config;
-use zentime_rs::config::Config;
-use zentime_rs::ipc::get_socket_name;
+use std::time::{Duration, Instant};
+use zentime_rs::client::one_shot_connection::one_shot_connection;
+use zentime_rs::config::{config_file_path, create_base_config, Config};
 use zentime_rs::ipc::ClientToServerMsg;
 use zentime_rs::ipc::InterProcessCommunication;
-use zentime_rs::server::start;
-use zentime_rs::server::status::server_status;
+use zentime_rs::ipc::ServerInfo;
+use zentime_rs::ipc::ServerToClientMsg;
+use zentime_rs::server::status::{server_pid, server_status, ServerStatus};
+use zentime_rs::server::{start, ConfigLoader, TimerMode};
+use zentime_rs_timer::util::seconds_to_time;
 
-use crate::CommonArgs;
+use crate::default_cmd::get_server_args;
+use crate::{
+    base_config_or_exit, connect_or_exit, init_logger, merge_server_args, validate_or_exit,
+    CommonArgs,
+};
 
 const DEFAULT_OUT_FILE: &str = "/tmp/zentime.d.out";
 const DEFAULT_ERROR_FILE: &str = "/tmp/zentime.d.err";
 const DEBUG_OUT_FILE: &str = "/tmp/zentime_debug.d.out";
 const DEBUG_ERROR_FILE: &str = "/tmp/zentime_debug.d.err";
 
+/// Time we wait for a stopped server to actually exit, before giving up on a restart
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time we wait for a started server to accept connections (see [start_and_wait])
+const READY_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Daemonizes the current process and then starts a zentime server instance in it (if there isn't
 /// another server already running - otherwise the process terminates).
 ///
+/// Unless the `mode` is [TimerMode::Pomodoro], the server runs a single focus session or a
+/// stopwatch (see [start_one_shot] and [start_stopwatch]).
+///
 /// NOTE: It's important, that we run this synchronously.
 /// [server::start()] will then create a tokio runtime, after the process has been
 /// deamonized
-pub fn start_daemonized(args: &CommonArgs) {
-    let stdout_path = if cfg!(debug_assertions) {
-        DEBUG_OUT_FILE
-    } else {
-        DEFAULT_OUT_FILE
-    };
+pub fn start_daemonized(args: &CommonArgs, mode: TimerMode) {
+    // We create the config before daemonizing, so that configuration errors are still reported
+    // to the calling terminal
+    let config = get_server_config(args);
+
+    let (stdout_path, stderr_path) = log_file_paths();
+
     let stdout = File::create(stdout_path)
         .unwrap_or_else(|error| panic!("Could not create {}: {}", stdout_path, error));
 
-    let stderr_path = if cfg!(debug_assertions) {
-        DEBUG_ERROR_FILE
-    } else {
-        DEFAULT_ERROR_FILE
-    };
     let stderr = File::create(stderr_path)
         .unwrap_or_else(|error| panic!("Could not create {}: {}", stderr_path, error));
 
@@ -57,31 +76,140 @@ pub fn start_daemonized(args: &CommonArgs) {
         panic!("Could not daemonize server process: {}", error);
     };
 
+    init_logger(config.server.log_level());
+
     info!("Daemonized server process");
 
+    info!("Using socket: {}", config.server.socket_name());
+
+    if let Err(error) = start(config, mode, config_loader(args)) {
+        error!("A server error occured: {}", error);
+    };
+}
+
+/// Starts a zentime server instance inside the current process without daemonizing it (if there
+/// isn't another server already running - otherwise the process terminates).
+/// Logs are written to stdout/stderr, which makes it possible to run the server under a service
+/// manager like systemd or supervisord.
+///
+/// Unless the `mode` is [TimerMode::Pomodoro], the server runs a single focus session or a
+/// stopwatch.
+pub fn start_foreground(args: &CommonArgs, mode: TimerMode) {
     let config = get_server_config(args);
 
-    if let Err(error) = start(config) {
+    init_logger(config.server.log_level());
+
+    info!("Using socket: {}", config.server.socket_name());
+
+    if let Err(error) = start(config, mode, config_loader(args)) {
         error!("A server error occured: {}", error);
+        process::exit(1);
     };
 }
 
+/// Starts a new server daemon (see [start_daemonized]) and blocks until it accepts connections.
+/// Terminates with a non-zero exit code, if the server isn't ready within [READY_TIMEOUT].
+///
+/// NOTE: A server which is already running is considered ready right away
+pub fn start_and_wait(args: &CommonArgs, mode: TimerMode) {
+    let config = get_server_config(args);
+    let socket_name = config.server.socket_name();
+
+    let extra_args = match mode {
+        TimerMode::Pomodoro => vec![],
+        TimerMode::OneShot(duration) => vec!["--one-shot".to_string(), duration.to_string()],
+        TimerMode::Stopwatch => vec!["--stopwatch".to_string()],
+    };
+
+    spawn_server(args, &config, &extra_args);
+
+    if let Err(error) = wait_until_ready(&socket_name) {
+        eprintln!("{} (see `zentime server logs`)", error);
+        process::exit(1);
+    }
+}
+
+/// Blocks until the server accepts connections on `socket_name`
+#[tokio::main]
+async fn wait_until_ready(socket_name: &str) -> anyhow::Result<()> {
+    let started_waiting = Instant::now();
+
+    loop {
+        if let Ok(connection) = LocalSocketStream::connect(socket_name).await {
+            let (_, mut writer) = connection.into_split();
+            InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+                .await
+                .ok();
+
+            return Ok(());
+        }
+
+        if started_waiting.elapsed() > READY_TIMEOUT {
+            anyhow::bail!(
+                "Server did not accept connections within {:?}",
+                READY_TIMEOUT
+            );
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Returns the paths of the files the daemon redirects its stdout and stderr to.
+/// Debug builds use separate files, so that they don't interfere with an installed zentime.
+fn log_file_paths() -> (&'static str, &'static str) {
+    if cfg!(debug_assertions) {
+        (DEBUG_OUT_FILE, DEBUG_ERROR_FILE)
+    } else {
+        (DEFAULT_OUT_FILE, DEFAULT_ERROR_FILE)
+    }
+}
+
 fn get_server_config(args: &CommonArgs) -> Config {
-    let config_path = &args.config;
-    info!("Creating config from path: {}", config_path);
+    info!(
+        "Creating config from path: {}",
+        config_file_path(args.config.as_deref()).display()
+    );
+
+    merge_server_args(
+        base_config_or_exit(args).merge(Serialized::defaults(args.server_config.clone())),
+        args,
+    )
+    .extract()
+    .map(validate_or_exit)
+    .expect("Could not create config")
+}
+
+/// Creates the config just like [get_server_config], but returns all errors instead of
+/// terminating the process (e.g. because the server should keep running with its current config)
+fn load_server_config(args: &CommonArgs) -> anyhow::Result<Config> {
+    let base_config = create_base_config(
+        args.config.as_deref(),
+        !args.no_local_config,
+        args.profile.as_deref(),
+    )?;
+
+    let config: Config = merge_server_args(
+        base_config.merge(Serialized::defaults(args.server_config.clone())),
+        args,
+    )
+    .extract()
+    .context("Could not create config")?;
+    config.validate()?;
 
-    create_base_config(config_path)
-        .merge(Serialized::defaults(args.server_config.clone()))
-        .extract()
-        .expect("Could not create config")
+    Ok(config)
+}
+
+/// Lets the server read its config again with the args it has been started with
+fn config_loader(args: &CommonArgs) -> ConfigLoader {
+    let args = args.clone();
+    Arc::new(move || load_server_config(&args))
 }
 
 /// Stops a currently running zentime server (there can only ever be a single instance - all
 /// clients will automatically shutdown, when their connection closes).
 #[tokio::main]
-pub async fn stop() {
-    let socket_name = get_socket_name();
-
+pub async fn stop(socket_name: &str) {
     let mut connection_tries = 0;
 
     info!("Connecting to server...");
@@ -114,7 +242,616 @@ pub async fn stop() {
     info!("Done.");
 }
 
-/// Prints the current status of the zentime server
-pub fn status() {
-    println!("Server is {}", server_status());
+/// Restarts the zentime server by stopping the currently running instance, waiting for it to exit
+/// and starting a fresh daemon with the given config afterwards.
+/// If no server is running, this terminates with an error - unless `force` is set, in which case
+/// a new server is simply started.
+pub fn restart(args: &CommonArgs, force: bool) {
+    let config = get_server_config(args);
+    let socket_name = config.server.socket_name();
+
+    if server_status(&socket_name) == ServerStatus::Running {
+        println!("Stopping server...");
+        stop_and_wait(&socket_name, "restart server");
+    } else if !force {
+        eprintln!("No zentime server running (use --force to start one anyway)");
+        process::exit(1);
+    }
+
+    println!("Starting server...");
+    spawn_server(args, &config, &[]);
+    println!("Server restarted");
+}
+
+/// Runs a single focus session of `duration` seconds, which ends without transitioning into a
+/// break. A running server is only replaced by a new server running the session (which shuts
+/// down once the session has ended), if `force` is set.
+pub fn start_one_shot(args: &CommonArgs, duration: u64, force: bool) {
+    let config = get_server_config(args);
+
+    replace_running_server(&config.server.socket_name(), force, "start focus session");
+    spawn_server(
+        args,
+        &config,
+        &["--one-shot".to_string(), duration.to_string()],
+    );
+    println!("Focusing for {}", seconds_to_time(duration));
+}
+
+/// Starts a stopwatch, which counts up until it is stopped via `zentime stopwatch stop`.
+/// A running server is only replaced by a new server running the stopwatch (which shuts down
+/// once the stopwatch has been stopped), if `force` is set.
+pub fn start_stopwatch(args: &CommonArgs, force: bool) {
+    let config = get_server_config(args);
+
+    replace_running_server(&config.server.socket_name(), force, "start stopwatch");
+    spawn_server(args, &config, &["--stopwatch".to_string()]);
+    println!("Stopwatch started");
+}
+
+/// Starts a new server daemon and waits for the daemonization to finish.
+/// Terminates the current process, if the server could not be started.
+pub fn spawn_server(args: &CommonArgs, config: &Config, extra_args: &[String]) {
+    // WHY:
+    // We spawn a regular `zentime server start` process instead of daemonizing the current
+    // process, because server processes are identified by their command (see [server_status]).
+    let current_exe = current_exe().expect("Could not determine path of zentime executable");
+    let result = Command::new(current_exe)
+        .arg("server")
+        .arg("start")
+        .args(get_server_args(args, config))
+        .args(extra_args)
+        .status();
+
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            eprintln!("Server start exited with {}", status);
+            process::exit(1);
+        }
+        Err(error) => {
+            eprintln!("Could not start server: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/// Stops the server running on `socket_name` (if any), so that a new server can take its place.
+/// Terminates the current process instead, if a server is running and `force` is not set,
+/// because its current session would be lost.
+fn replace_running_server(socket_name: &str, force: bool, action: &str) {
+    if server_status(socket_name) != ServerStatus::Running {
+        return;
+    }
+
+    if !force {
+        eprintln!(
+            "Could not {}: A zentime server is already running (use --force to replace its session)",
+            action
+        );
+        process::exit(1);
+    }
+
+    stop_and_wait(socket_name, action);
+}
+
+/// Stops the server running on `socket_name` and waits for its shutdown.
+/// Terminates the current process, if the server did not shut down in time.
+fn stop_and_wait(socket_name: &str, action: &str) {
+    stop(socket_name);
+
+    if let Err(error) = wait_for_shutdown(socket_name) {
+        eprintln!("Could not {}: {}", action, error);
+        process::exit(1);
+    }
+}
+
+/// Blocks until the server process has exited and its socket file has been removed
+fn wait_for_shutdown(socket_name: &str) -> anyhow::Result<()> {
+    let started_waiting = Instant::now();
+
+    while server_status(socket_name) == ServerStatus::Running || Path::new(socket_name).exists() {
+        if started_waiting.elapsed() > SHUTDOWN_TIMEOUT {
+            anyhow::bail!("Server did not shut down within {:?}", SHUTDOWN_TIMEOUT);
+        }
+
+        sleep(Duration::from_millis(200));
+    }
+
+    Ok(())
+}
+
+/// Status of the zentime server as printed by `zentime server status --json`
+#[derive(Serialize)]
+struct StatusReport<'a> {
+    running: bool,
+    pid: Option<u32>,
+    socket: Option<&'a str>,
+
+    /// Only reported with `--verbose`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uptime_secs: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<DateTime<Local>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timer_started_at: Option<DateTime<Local>>,
+}
+
+/// Prints the current status of the zentime server (optionally as JSON for scripting).
+/// With `verbose` the server is additionally asked for its uptime and the start of the current
+/// timer.
+pub fn status(socket_name: &str, json: bool, verbose: bool) {
+    let pid = server_pid(socket_name);
+
+    let info = match pid {
+        Some(_) if verbose => match request_server_info(socket_name) {
+            Ok(info) => Some(info),
+            Err(error) => {
+                eprintln!("Could not request server info: {:#}", error);
+                process::exit(1);
+            }
+        },
+        _ => None,
+    };
+
+    if !json {
+        // NOTE: The status is derived from the pid, so that the process table is only scanned once
+        let status = match pid {
+            Some(_) => ServerStatus::Running,
+            None => ServerStatus::Stopped,
+        };
+        println!("Server is {}", status);
+
+        if let Some(info) = info {
+            println!(
+                "Uptime:        {} (since {})",
+                format_duration(Local::now() - info.started_at),
+                info.started_at.format("%Y-%m-%d %H:%M:%S")
+            );
+            match info.timer_started_at {
+                Some(started_at) => println!(
+                    "Current timer: started at {}",
+                    started_at.format("%Y-%m-%d %H:%M:%S")
+                ),
+                None => println!("Current timer: not started yet"),
+            }
+        }
+
+        return;
+    }
+
+    let report = StatusReport {
+        running: pid.is_some(),
+        pid,
+        socket: pid.map(|_| socket_name),
+        uptime_secs: info.map(|info| (Local::now() - info.started_at).num_seconds()),
+        started_at: info.map(|info| info.started_at),
+        timer_started_at: info.and_then(|info| info.timer_started_at),
+    };
+
+    match serde_json::to_string(&report) {
+        Ok(report) => println!("{}", report),
+        Err(error) => {
+            eprintln!("Could not serialize server status: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/// Asks the running server for its [ServerInfo]
+#[tokio::main]
+async fn request_server_info(socket_name: &str) -> anyhow::Result<ServerInfo> {
+    let (reader, mut writer) = one_shot_connection(socket_name).await?;
+    let mut reader = BufReader::new(reader);
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::GetServerInfo, &mut writer)
+        .await?;
+
+    // NOTE: Timer states might arrive before the answer
+    let info = loop {
+        match InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await? {
+            ServerToClientMsg::ServerInfo(info) => break info,
+            _ => continue,
+        }
+    };
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+        .await
+        .ok();
+
+    Ok(info)
+}
+
+/// Formats a duration like `2h 05m 09s` (omitting leading zero units)
+fn format_duration(duration: chrono::Duration) -> String {
+    let secs = duration.num_seconds().max(0);
+    let (hours, minutes, secs) = (secs / 3600, secs % 3600 / 60, secs % 60);
+
+    match (hours, minutes) {
+        (0, 0) => format!("{}s", secs),
+        (0, _) => format!("{}m {:02}s", minutes, secs),
+        _ => format!("{}h {:02}m {:02}s", hours, minutes, secs),
+    }
+}
+
+/// Lets the running server read its config again and apply it (see [ClientToServerMsg::Reload]).
+/// Terminates with a non-zero exit code, if the config could not be reloaded.
+#[tokio::main]
+pub async fn reload(socket_name: &str) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
+
+    let mut reader = BufReader::new(reader);
+
+    if let Err(error) =
+        InterProcessCommunication::send_ipc_message(ClientToServerMsg::Reload, &mut writer).await
+    {
+        eprintln!("Could not request reload: {}", error);
+        process::exit(1);
+    }
+
+    // NOTE: Timer states might arrive before the answer
+    let result = loop {
+        match InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await {
+            Ok(ServerToClientMsg::Reloaded(result)) => break result,
+            Ok(_) => continue,
+            Err(error) => {
+                eprintln!("Could not receive answer of the server: {}", error);
+                process::exit(1);
+            }
+        }
+    };
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+        .await
+        .ok();
+
+    match result {
+        Ok(restart_required) => {
+            println!("Reloaded config (changed durations apply from the next timer on)");
+
+            if !restart_required.is_empty() {
+                println!(
+                    "The following settings only apply after `zentime server restart`: {}",
+                    restart_required.join(", ")
+                );
+            }
+        }
+        Err(error) => {
+            eprintln!("Could not reload config: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/// Prints all other clients, which are currently connected to the server, with their id and the
+/// time at which they have connected
+#[tokio::main]
+pub async fn clients(socket_name: &str) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
+
+    let mut reader = BufReader::new(reader);
+
+    if let Err(error) =
+        InterProcessCommunication::send_ipc_message(ClientToServerMsg::ListClients, &mut writer)
+            .await
+    {
+        eprintln!("Could not request clients: {}", error);
+        process::exit(1);
+    }
+
+    // NOTE: Timer states might arrive before the answer
+    let clients = loop {
+        match InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await {
+            Ok(ServerToClientMsg::Clients(clients)) => break clients,
+            Ok(_) => continue,
+            Err(error) => {
+                eprintln!("Could not receive clients: {}", error);
+                process::exit(1);
+            }
+        }
+    };
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+        .await
+        .ok();
+
+    match clients.len() {
+        0 => println!("No clients connected"),
+        1 => println!("1 client connected"),
+        count => println!("{} clients connected", count),
+    }
+
+    for client in clients {
+        println!(
+            "  {:>4}  connected at {}",
+            client.id,
+            client.connected_at.format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+}
+
+/// Measures `count` round trips to the server and prints each of them in milliseconds (followed
+/// by min/avg/max for more than one round trip).
+///
+/// Every round trip uses its own [one_shot_connection] (just like `zentime once` does) and
+/// measures the time from sending [ClientToServerMsg::Sync] until the next timer state arrives.
+/// NOTE: The server only sends timer states on ticks, so this includes waiting for the next tick.
+#[tokio::main]
+pub async fn ping(socket_name: &str, count: u32) {
+    let mut round_trips = vec![];
+
+    for _ in 0..count {
+        match round_trip(socket_name).await {
+            Ok(duration) => {
+                println!("{:.2} ms", as_millis(duration));
+                round_trips.push(duration);
+            }
+            Err(error) => {
+                eprintln!("Could not ping server: {:#}", error);
+                process::exit(1);
+            }
+        }
+    }
+
+    if round_trips.len() > 1 {
+        let min = round_trips.iter().min().copied().unwrap_or_default();
+        let max = round_trips.iter().max().copied().unwrap_or_default();
+        let avg = round_trips.iter().sum::<Duration>() / round_trips.len() as u32;
+
+        println!(
+            "min/avg/max = {:.2}/{:.2}/{:.2} ms",
+            as_millis(min),
+            as_millis(avg),
+            as_millis(max)
+        );
+    }
+}
+
+async fn round_trip(socket_name: &str) -> anyhow::Result<Duration> {
+    let (reader, mut writer) = one_shot_connection(socket_name).await?;
+    let mut reader = BufReader::new(reader);
+
+    let started = Instant::now();
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Sync, &mut writer).await?;
+
+    loop {
+        if let ServerToClientMsg::Timer(_) =
+            InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await?
+        {
+            break;
+        }
+    }
+    let duration = started.elapsed();
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+        .await
+        .ok();
+
+    Ok(duration)
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Findings of `zentime server doctor`, which are used to diagnose an inconsistent server state
+#[derive(Debug, Clone, Copy)]
+struct Diagnosis {
+    /// Denotes if the socket file exists (`None` for namespaced sockets, which have no file)
+    socket_file_exists: Option<bool>,
+
+    /// Process id of the server process listening on the socket (if one is running)
+    pid: Option<u32>,
+
+    /// Denotes if a connection to the socket could be established
+    is_connectable: bool,
+}
+
+impl Diagnosis {
+    /// Describes the overall state and how to fix it.
+    /// Returns `None` as problem, if everything is consistent.
+    fn verdict(&self, socket_name: &str) -> (String, Option<String>) {
+        let socket_exists = self.socket_file_exists.unwrap_or(self.is_connectable);
+
+        match (socket_exists, self.pid, self.is_connectable) {
+            (true, Some(_), true) => (String::from("Server is running and accepts connections"), None),
+            (false, None, false) => (
+                String::from("No server is running"),
+                None,
+            ),
+            (true, None, false) => (
+                String::from("Found a stale socket file without a server process"),
+                Some(format!(
+                    "Remove it with `rm {}` or simply start a new server with `zentime server start`, which removes it automatically.",
+                    socket_name
+                )),
+            ),
+            (_, None, true) => (
+                String::from("A server accepts connections, but its process could not be identified (e.g. because it has been started from a different build)"),
+                Some(String::from(
+                    "`zentime server stop` should still shut it down. Afterwards start a new server with the current build.",
+                )),
+            ),
+            (false, Some(pid), _) => (
+                String::from("The server process is running, but its socket file is missing (e.g. because /tmp has been cleaned up), so that clients can't connect"),
+                Some(format!(
+                    "Terminate the server with `kill {}` and start a new one with `zentime server start`.",
+                    pid
+                )),
+            ),
+            (true, Some(pid), false) => (
+                String::from("The server process is running, but does not accept connections"),
+                Some(format!(
+                    "Check `zentime server logs`, terminate the server with `kill {}` and start a new one with `zentime server start`.",
+                    pid
+                )),
+            ),
+        }
+    }
+}
+
+/// Reports whether the socket file exists, whether a server process is running and whether the
+/// server accepts connections - together with hints on how to fix an inconsistent state.
+/// Exits with a non-zero code, if a problem has been found.
+#[tokio::main]
+pub async fn doctor(socket_name: &str) {
+    // NOTE: Namespaced sockets don't have a file which could be checked
+    let socket_file_exists =
+        (!socket_name.starts_with('@')).then(|| Path::new(socket_name).exists());
+
+    let is_connectable = match LocalSocketStream::connect(socket_name).await {
+        Ok(connection) => {
+            let (_, mut writer) = connection.into_split();
+            InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+                .await
+                .ok();
+            true
+        }
+        Err(_) => false,
+    };
+
+    let diagnosis = Diagnosis {
+        socket_file_exists,
+        pid: server_pid(socket_name),
+        is_connectable,
+    };
+
+    println!(
+        "Socket:     {} ({})",
+        socket_name,
+        match diagnosis.socket_file_exists {
+            Some(true) => "file exists",
+            Some(false) => "file does not exist",
+            None => "namespaced",
+        }
+    );
+    println!(
+        "Process:    {}",
+        diagnosis
+            .pid
+            .map(|pid| format!("running (pid {})", pid))
+            .unwrap_or_else(|| String::from("not found"))
+    );
+    println!(
+        "Connection: {}",
+        if diagnosis.is_connectable {
+            "ok"
+        } else {
+            "failed"
+        }
+    );
+
+    let (summary, hint) = diagnosis.verdict(socket_name);
+    println!("\n{}", summary);
+
+    if let Some(hint) = hint {
+        println!("Hint: {}", hint);
+        process::exit(1);
+    }
+}
+
+/// Prints the output and error logs of the server daemon.
+/// If `follow` is set, this keeps running and prints new log output as soon as it is written.
+pub fn logs(follow: bool) {
+    let (stdout_path, stderr_path) = log_file_paths();
+
+    if !Path::new(stdout_path).exists() && !Path::new(stderr_path).exists() {
+        println!("No logs found - the zentime server has never been started");
+        return;
+    }
+
+    let mut out_log = LogFile::new(stdout_path);
+    let mut err_log = LogFile::new(stderr_path);
+
+    loop {
+        if let Err(error) = out_log.print_new_content(&mut io::stdout()) {
+            eprintln!("Could not read {}: {}", stdout_path, error);
+            process::exit(1);
+        }
+
+        if let Err(error) = err_log.print_new_content(&mut io::stderr()) {
+            eprintln!("Could not read {}: {}", stderr_path, error);
+            process::exit(1);
+        }
+
+        if !follow {
+            break;
+        }
+
+        sleep(Duration::from_millis(500));
+    }
+}
+
+/// A log file which remembers how much of its content has already been printed
+struct LogFile {
+    path: &'static str,
+    offset: u64,
+}
+
+impl LogFile {
+    fn new(path: &'static str) -> Self {
+        Self { path, offset: 0 }
+    }
+
+    /// Writes everything that has been appended to the file since the last call into `output`
+    fn print_new_content(&mut self, output: &mut impl Write) -> io::Result<()> {
+        let mut file = match File::open(self.path) {
+            Ok(file) => file,
+            // The file might not have been created yet
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error),
+        };
+
+        // A restarted server recreates its log files, in which case we start from the beginning
+        if file.metadata()?.len() < self.offset {
+            self.offset = 0;
+        }
+
+        file.seek(SeekFrom::Start(self.offset))?;
+        self.offset += io::copy(&mut file, output)?;
+        output.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Commands, ServerCommands};
+    use clap::Parser;
+    use figment::Jail;
+
+    // WHY: [Jail] requires closures returning a [figment::Result], whose error is rather large
+    #[allow(clippy::result_large_err)]
+    #[test]
+    fn reload_reads_config_file_again_with_spawn_args() {
+        Jail::expect_with(|jail| {
+            jail.create_file("zentime.toml", "[server]\ndaily_goal = 2\n")?;
+
+            let cli = Cli::parse_from(["zentime", "-c", "zentime.toml"]);
+            let config = load_server_config(&cli.common_args).expect("Could not load config");
+
+            // Arguments of the server, which is spawned by the default command
+            let spawn_args = get_server_args(&cli.common_args, &config);
+            let server_cli = Cli::parse_from(
+                ["zentime", "server", "start"]
+                    .into_iter()
+                    .map(String::from)
+                    .chain(spawn_args),
+            );
+            let Some(Commands::Server {
+                command: ServerCommands::Start { common_args, .. },
+            }) = server_cli.command
+            else {
+                panic!("Spawn args should result in a server start");
+            };
+
+            let load_config = config_loader(&common_args);
+            assert_eq!(load_config().unwrap().server.daily_goal, Some(2));
+
+            jail.create_file("zentime.toml", "[server]\ndaily_goal = 4\n")?;
+            assert_eq!(load_config().unwrap().server.daily_goal, Some(4));
+
+            Ok(())
+        });
+    }
 }
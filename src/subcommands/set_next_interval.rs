@@ -0,0 +1,40 @@
+use super::query_server_once::status_line;
+use crate::connect_or_exit;
+use futures::io::BufReader;
+use zentime_rs::ipc::ClientToServerMsg;
+use zentime_rs::ipc::InterProcessCommunication;
+use zentime_rs::ipc::ServerToClientMsg;
+
+#[tokio::main]
+pub async fn set_next_interval(
+    socket_name: &str,
+    silent: bool,
+    focus: Option<u64>,
+    break_: Option<u64>,
+) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
+
+    let mut reader = BufReader::new(reader);
+
+    if let Err(err) = InterProcessCommunication::send_ipc_message(
+        ClientToServerMsg::SetNextInterval { focus, break_ },
+        &mut writer,
+    )
+    .await
+    {
+        panic!("Could not send to the server: {}", err)
+    };
+
+    let msg_result =
+        InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await;
+
+    if !silent {
+        if let Ok(ServerToClientMsg::Timer(state)) = msg_result {
+            println!("{}", status_line(&state));
+        }
+    }
+
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+        .await
+        .ok();
+}
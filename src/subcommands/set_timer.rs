@@ -1,20 +1,19 @@
+use super::query_server_once::status_line;
+use crate::connect_or_exit;
 use futures::io::BufReader;
-use zentime_rs::client::one_shot_connection::one_shot_connection;
 use zentime_rs::ipc::ClientToServerMsg;
 use zentime_rs::ipc::InterProcessCommunication;
 use zentime_rs::ipc::ServerToClientMsg;
 
 #[tokio::main]
-pub async fn set_timer(silent: bool, time: u64) {
-    let (reader, mut writer) = match one_shot_connection().await {
-        Ok(c) => c,
-        Err(error) => panic!("Could not connect to server: {}", error),
-    };
+pub async fn set_timer(socket_name: &str, silent: bool, time: u64) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
 
     let mut reader = BufReader::new(reader);
 
     if let Err(err) =
-        InterProcessCommunication::send_ipc_message(ClientToServerMsg::SetTimer(time), &mut writer).await
+        InterProcessCommunication::send_ipc_message(ClientToServerMsg::SetTimer(time), &mut writer)
+            .await
     {
         panic!("Could not send to the server: {}", err)
     };
@@ -24,12 +23,7 @@ pub async fn set_timer(silent: bool, time: u64) {
 
     if !silent {
         if let Ok(ServerToClientMsg::Timer(state)) = msg_result {
-            println!(
-                "{} {} {}",
-                state.round,
-                state.time,
-                if state.is_break { "Break" } else { "Focus" }
-            );
+            println!("{}", status_line(&state));
         }
     }
 
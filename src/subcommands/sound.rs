@@ -0,0 +1,58 @@
+use std::fs;
+use std::process;
+use zentime_rs::server::sound::{play_once, DEFAULT_SOUND};
+
+/// Writes the default bell to `path`, so that it can be used as a starting point for a custom
+/// `sound_file`
+pub fn extract(path: &str) {
+    let path = shellexpand::tilde(path.trim());
+
+    if let Err(error) = fs::write(path.as_ref(), DEFAULT_SOUND) {
+        eprintln!("Could not write default sound to {}: {}", path, error);
+        process::exit(1);
+    }
+
+    println!("Wrote default sound to {}", path);
+}
+
+/// Plays back the given sound file (or the default bell) once at the given volume and waits
+/// until it has finished.
+/// Terminates with a non-zero exit code, if the file can't be read, decoded or played back.
+pub fn test(file: Option<&str>, volume: f32) {
+    let bytes = match file {
+        Some(file) => {
+            let file = shellexpand::tilde(file.trim());
+
+            match fs::read(file.as_ref()) {
+                Ok(bytes) => bytes,
+                Err(error) => {
+                    eprintln!("Could not read sound file {}: {}", file, error);
+                    process::exit(1);
+                }
+            }
+        }
+        None => DEFAULT_SOUND.to_vec(),
+    };
+
+    if let Err(error) = play_once(bytes, volume) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+/// Parses a volume between 0.0 and 1.0 (like `notifications.volume`)
+pub fn parse_volume(volume: &str) -> Result<f32, String> {
+    let volume = volume
+        .trim()
+        .parse::<f32>()
+        .map_err(|error| error.to_string())?;
+
+    if (0.0..=1.0).contains(&volume) {
+        Ok(volume)
+    } else {
+        Err(format!(
+            "volume has to be between 0.0 and 1.0 (got {})",
+            volume
+        ))
+    }
+}
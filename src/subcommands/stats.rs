@@ -0,0 +1,90 @@
+use chrono::{Local, NaiveDate};
+use clap::ValueEnum;
+use std::process;
+use zentime_rs::config::Config;
+use zentime_rs::stats::{
+    filter_by_day, filter_by_label, parse_day, read_records, ParseDayError, StatsRecord,
+    StatsSummary,
+};
+
+/// Output formats of `zentime stats`
+#[derive(ValueEnum, Copy, Clone, Debug, Default)]
+pub enum StatsFormat {
+    /// Human readable summary
+    #[default]
+    Text,
+
+    /// JSON object with all totals (times in seconds)
+    Json,
+}
+
+/// Prints the totals of all records of the stats file, which have been started within the given
+/// days (both inclusive) and have the given label (if any)
+pub fn stats(
+    config: &Config,
+    format: StatsFormat,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+    label: Option<&str>,
+) {
+    let records = filter_by_label(
+        filter_by_day(read_stats_or_exit(config), since, until),
+        label,
+    );
+    let summary = StatsSummary::from_records(&records);
+
+    match format {
+        StatsFormat::Text => {
+            println!(
+                "Focus:        {} ({} {})",
+                format_secs(summary.focus_secs),
+                summary.intervals,
+                if summary.intervals == 1 {
+                    "interval"
+                } else {
+                    "intervals"
+                }
+            );
+            println!("Breaks:       {}", format_secs(summary.break_secs));
+            println!("Distractions: {}", summary.distractions);
+        }
+        StatsFormat::Json => match serde_json::to_string(&summary) {
+            Ok(json) => println!("{}", json),
+            Err(error) => {
+                eprintln!("Could not serialize stats: {}", error);
+                process::exit(1);
+            }
+        },
+    }
+}
+
+/// Reads all records of the configured stats file and terminates the process, if no stats file
+/// has been configured or it can't be read
+pub fn read_stats_or_exit(config: &Config) -> Vec<StatsRecord> {
+    let Some(stats_file) = config.server.stats_file() else {
+        eprintln!("No stats file configured - set `stats_file` inside the [server] section of your config to record timers");
+        process::exit(1);
+    };
+
+    read_records(&stats_file).unwrap_or_else(|error| {
+        eprintln!("{:#}", anyhow::Error::new(error));
+        process::exit(1);
+    })
+}
+
+/// Parses a day argument relative to the current (local) day (see [parse_day])
+pub fn parse_day_arg(day: &str) -> Result<NaiveDate, ParseDayError> {
+    parse_day(day, Local::now().date_naive())
+}
+
+/// Formats seconds as hours and minutes, e.g. `3h 25m`
+fn format_secs(secs: u64) -> String {
+    let hours = secs / 3600;
+    let minutes = secs % 3600 / 60;
+
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
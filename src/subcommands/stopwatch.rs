@@ -0,0 +1,51 @@
+use crate::connect_or_exit;
+use futures::io::BufReader;
+use zentime_rs::ipc::ClientToServerMsg;
+use zentime_rs::ipc::{InterProcessCommunication, ServerToClientMsg};
+use zentime_rs_timer::util::seconds_to_time;
+
+/// Stops the running stopwatch (see `zentime stopwatch start`), which shuts the server down,
+/// and prints the elapsed time.
+/// Exits with an error, if the server doesn't run a stopwatch.
+#[tokio::main]
+pub async fn stop_stopwatch(socket_name: &str, silent: bool) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
+
+    let mut reader = BufReader::new(reader);
+
+    if let Err(err) =
+        InterProcessCommunication::send_ipc_message(ClientToServerMsg::Sync, &mut writer).await
+    {
+        panic!("Could not sync with server: {}", err)
+    };
+
+    let msg_result =
+        InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await;
+
+    let elapsed_secs = match msg_result {
+        Ok(ServerToClientMsg::Timer(state)) if state.is_stopwatch => state.elapsed_secs,
+        _ => {
+            InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+                .await
+                .ok();
+            eprintln!("No stopwatch running");
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(err) =
+        InterProcessCommunication::send_ipc_message(ClientToServerMsg::StopStopwatch, &mut writer)
+            .await
+    {
+        panic!("Could not send to the server: {}", err)
+    };
+
+    if !silent {
+        println!("Stopped after {}", seconds_to_time(elapsed_secs));
+    }
+
+    // NOTE: The server might already have shut down
+    InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
+        .await
+        .ok();
+}
@@ -1,36 +1,58 @@
+use crate::connect_or_exit;
 use futures::io::BufReader;
-use zentime_rs::client::one_shot_connection::one_shot_connection;
+use std::time::Duration;
+use tokio::time::timeout;
 use zentime_rs::ipc::ClientToServerMsg;
 use zentime_rs::ipc::InterProcessCommunication;
 use zentime_rs::ipc::ServerToClientMsg;
 
+/// Maximum time we wait for the server to report the toggled timer state
+const TOGGLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Toggles the timer and prints whether it is `paused` or `running` afterwards
 #[tokio::main]
-pub async fn toggle_timer(silent: bool) {
-    let (reader, mut writer) = match one_shot_connection().await {
-        Ok(c) => c,
-        Err(error) => panic!("Could not conenct to server: {}", error),
-    };
+pub async fn toggle_timer(socket_name: &str, silent: bool) {
+    let (reader, mut writer) = connect_or_exit(socket_name).await;
 
     let mut reader = BufReader::new(reader);
 
+    if let Err(err) =
+        InterProcessCommunication::send_ipc_message(ClientToServerMsg::Sync, &mut writer).await
+    {
+        panic!("Could not sync with server: {}", err)
+    };
+
+    // WHY:
+    // Messages which have been queued before our toggle has been handled still contain the
+    // previous state. We therefore remember it and wait until the server reports the change.
+    let mut is_paused =
+        match InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await {
+            Ok(ServerToClientMsg::Timer(state)) => state.is_paused,
+            _ => panic!("Could not receive timer state from the server"),
+        };
+
     if let Err(err) =
         InterProcessCommunication::send_ipc_message(ClientToServerMsg::PlayPause, &mut writer).await
     {
         panic!("Could not send to the server: {}", err)
     };
 
-    let msg_result =
-        InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader).await;
+    let previous_is_paused = is_paused;
+    let _ = timeout(TOGGLE_TIMEOUT, async {
+        while is_paused == previous_is_paused {
+            match InterProcessCommunication::recv_ipc_message::<ServerToClientMsg>(&mut reader)
+                .await
+            {
+                Ok(ServerToClientMsg::Timer(state)) => is_paused = state.is_paused,
+                Ok(_) => continue,
+                Err(_) => break,
+            }
+        }
+    })
+    .await;
 
     if !silent {
-        if let Ok(ServerToClientMsg::Timer(state)) = msg_result {
-            println!(
-                "{} {} {}",
-                state.round,
-                state.time,
-                if state.is_break { "Break" } else { "Focus" }
-            );
-        }
+        println!("{}", if is_paused { "paused" } else { "running" });
     }
 
     InterProcessCommunication::send_ipc_message(ClientToServerMsg::Detach, &mut writer)
@@ -0,0 +1,19 @@
+use clap::ValueEnum;
+use zentime_rs::client::start::watch as watch_client;
+use zentime_rs::config::Config;
+
+/// Output formats of `zentime watch`
+#[derive(ValueEnum, Copy, Clone, Debug, Default)]
+pub enum WatchFormat {
+    /// One JSON object per timer state and line
+    #[default]
+    Json,
+}
+
+/// Attaches a client which streams the timer states to stdout (until SIGINT is received)
+#[tokio::main]
+pub async fn watch(config: Config, format: WatchFormat) {
+    match format {
+        WatchFormat::Json => watch_client(config).await,
+    }
+}
@@ -0,0 +1,192 @@
+// WHY: [Jail] requires closures returning a [figment::Result], whose error is rather large
+#![allow(clippy::result_large_err)]
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use figment::Jail;
+use std::path::Path;
+use zentime_rs::client::terminal_io::input::ClientInputAction;
+use zentime_rs::client::terminal_io::keymap::Keymap;
+use zentime_rs::config::{create_base_config, Config, ConfigProblem};
+
+// NOTE:
+// Every test which creates a config runs inside a [Jail], because env vars (and the current
+// directory) are process global and tests run in parallel.
+
+#[test]
+fn env_var_overrides_config_file() {
+    Jail::expect_with(|jail| {
+        jail.create_file(
+            "zentime.toml",
+            "[timers]\ntimer = 600\n\n[notifications]\nvolume = 0.2\nsound_file = \"/from/file.mp3\"\n",
+        )?;
+        jail.set_env("ZENTIME_TIMERS_TIMER", "1500");
+        jail.set_env("ZENTIME_NOTIFICATIONS_SOUND_FILE", "/from/env.mp3");
+
+        let config: Config = create_base_config(Some(Path::new("zentime.toml")), false, None)
+            .expect("Could not create base config")
+            .extract()?;
+
+        assert_eq!(config.timers.timer, 1500);
+        assert_eq!(
+            config.notifications.sound_file,
+            Some("/from/env.mp3".to_string())
+        );
+        // Values without an env var still come from the file
+        assert_eq!(config.notifications.volume, 0.2);
+
+        Ok(())
+    });
+}
+
+#[test]
+fn profile_is_merged_over_config_file() {
+    Jail::expect_with(|jail| {
+        jail.create_file(
+            "zentime.toml",
+            "[timers]\nminor_break = 600\nintervals = 3\n\n[profiles.work.timers]\nminor_break = 900\n\n[profiles.study.timers]\nminor_break = 300\n",
+        )?;
+
+        let config: Config =
+            create_base_config(Some(Path::new("zentime.toml")), false, Some("work"))
+                .expect("Could not select profile")
+                .extract()?;
+        let unknown_profile =
+            create_base_config(Some(Path::new("zentime.toml")), false, Some("play"));
+
+        assert_eq!(config.timers.minor_break, 900);
+        // Values which are not part of the profile still come from the file
+        assert_eq!(config.timers.intervals, 3);
+        assert_eq!(
+            unknown_profile.unwrap_err().available,
+            vec!["study".to_string(), "work".to_string()]
+        );
+
+        Ok(())
+    });
+}
+
+#[test]
+fn local_config_overrides_global_but_not_explicit_config() {
+    Jail::expect_with(|jail| {
+        let home = jail.directory().display().to_string();
+        jail.set_env("HOME", home);
+        jail.create_dir(".config/zentime")?;
+        jail.create_file(
+            ".config/zentime/zentime.toml",
+            "[timers]\ntimer = 600\nintervals = 3\n",
+        )?;
+        jail.create_file(".zentime.toml", "[timers]\ntimer = 900\n")?;
+        jail.create_file("explicit.toml", "[timers]\ntimer = 1200\n")?;
+
+        let global_config: Config = create_base_config(None, true, None)
+            .expect("Could not create base config")
+            .extract()?;
+        let explicit_config: Config =
+            create_base_config(Some(Path::new("explicit.toml")), true, None)
+                .expect("Could not create base config")
+                .extract()?;
+
+        assert_eq!(global_config.timers.timer, 900);
+        assert_eq!(global_config.timers.intervals, 3);
+        assert_eq!(explicit_config.timers.timer, 1200);
+        // The global config is not used at all, if another config has been passed
+        assert_eq!(explicit_config.timers.intervals, 4);
+
+        Ok(())
+    });
+}
+
+#[test]
+fn keybindings_accept_multiple_keys_and_report_conflicts() {
+    Jail::expect_with(|jail| {
+        jail.create_file(
+            "zentime.toml",
+            "[keybindings]\nplay_pause = [\"space\", \"p\"]\n\n[profiles.vim.keybindings]\npostpone = [\"ctrl-p\"]\n",
+        )?;
+
+        let extract = |profile| -> figment::Result<Config> {
+            create_base_config(Some(Path::new("zentime.toml")), false, profile)
+                .expect("Could not select profile")
+                .extract()
+        };
+        let conflicting_config = extract(None)?;
+        let config = extract(Some("vim"))?;
+
+        // "p" is still bound to postpone by default
+        assert!(conflicting_config.validate().unwrap_err().0.contains(
+            &ConfigProblem::ConflictingKeyBinding("p".to_string(), "play_pause", "postpone")
+        ));
+
+        let keymap = Keymap::new(&config.keybindings).expect("Keybindings should be valid");
+        let key = |code, modifiers| keymap.action(&KeyEvent::new(code, modifiers));
+
+        assert_eq!(
+            key(KeyCode::Char(' '), KeyModifiers::NONE),
+            ClientInputAction::PlayPause
+        );
+        assert_eq!(
+            key(KeyCode::Char('p'), KeyModifiers::NONE),
+            ClientInputAction::PlayPause
+        );
+        assert_eq!(
+            key(KeyCode::Char('p'), KeyModifiers::CONTROL),
+            ClientInputAction::PostPone
+        );
+        // Default bindings of other actions are kept
+        assert_eq!(
+            key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ClientInputAction::Detach
+        );
+
+        Ok(())
+    });
+}
+
+#[test]
+fn zero_intervals_are_rejected() {
+    let mut config = Config::default();
+    config.timers.intervals = 0;
+
+    assert!(config
+        .validate()
+        .unwrap_err()
+        .0
+        .contains(&ConfigProblem::IntervalsTooLow(0)));
+}
+
+#[test]
+fn webhook_urls_have_to_use_http_or_https() {
+    let mut config = Config::default();
+
+    for url in ["http://localhost:8123/hook", "https://[::1]/hook"] {
+        config.notifications.webhook_url = Some(url.to_string());
+        assert!(config.validate().is_ok(), "{} should be valid", url);
+    }
+
+    config.notifications.webhook_url = Some("ftp://example.com".to_string());
+    assert!(config
+        .validate()
+        .unwrap_err()
+        .0
+        .contains(&ConfigProblem::UnsupportedWebhookUrl(
+            "ftp://example.com".to_string()
+        )));
+}
+
+#[test]
+fn reloading_reports_only_settings_which_require_a_restart() {
+    let config = Config::default();
+    let mut reloaded = config.clone();
+
+    reloaded.timers.timer = 50 * 60;
+    reloaded.notifications.volume = 0.1;
+    reloaded.server.daily_goal = Some(8);
+    assert!(config.restart_required_changes(&reloaded).is_empty());
+
+    reloaded.server.socket_path = Some("/tmp/zentime.sock".to_string());
+    reloaded.server.stats_file = Some("~/stats.jsonl".to_string());
+    assert_eq!(
+        config.restart_required_changes(&reloaded),
+        vec!["server.socket_path", "server.stats_file"]
+    );
+}
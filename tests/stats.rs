@@ -0,0 +1,107 @@
+use chrono::{Local, NaiveDate, TimeZone};
+use std::env::temp_dir;
+use zentime_rs::stats::{
+    append_record, filter_by_day, filter_by_label, read_records, StatsRecord, StatsSummary,
+};
+use zentime_rs_timer::pomodoro_timer::TimerKind;
+
+#[test]
+fn appended_records_are_read_back_in_order() {
+    let stats_path = temp_dir()
+        .join(format!("zentime-stats-test-{}", std::process::id()))
+        .join("stats.jsonl");
+
+    // Nothing has been recorded yet
+    assert_eq!(read_records(&stats_path).unwrap(), vec![]);
+
+    let start = Local.with_ymd_and_hms(2024, 1, 31, 9, 0, 0).unwrap();
+    let records = [
+        StatsRecord {
+            start,
+            end: start + chrono::Duration::minutes(25),
+            kind: TimerKind::Interval,
+            round: 1,
+            distractions: 2,
+            label: Some(String::from("zentime")),
+        },
+        StatsRecord {
+            start: start + chrono::Duration::minutes(25),
+            end: start + chrono::Duration::minutes(30),
+            kind: TimerKind::Break,
+            round: 1,
+            distractions: 0,
+            label: None,
+        },
+    ];
+
+    for record in &records {
+        append_record(&stats_path, record).expect("Could not append record");
+    }
+
+    let read = read_records(&stats_path);
+    std::fs::remove_dir_all(stats_path.parent().unwrap()).ok();
+
+    assert_eq!(read.unwrap(), records);
+}
+
+#[test]
+fn summary_only_counts_records_within_the_given_days() {
+    let record = |day, kind, minutes| {
+        let start = Local.with_ymd_and_hms(2024, 1, day, 9, 0, 0).unwrap();
+
+        StatsRecord {
+            start,
+            end: start + chrono::Duration::minutes(minutes),
+            kind,
+            round: 1,
+            distractions: 1,
+            label: None,
+        }
+    };
+    let records = vec![
+        record(1, TimerKind::Interval, 25),
+        record(2, TimerKind::Interval, 25),
+        record(2, TimerKind::PostponedBreak, 5),
+        record(2, TimerKind::Break, 5),
+        record(3, TimerKind::Interval, 25),
+    ];
+
+    let day = |day| NaiveDate::from_ymd_opt(2024, 1, day);
+    let summary = StatsSummary::from_records(&filter_by_day(records, day(2), day(2)));
+
+    // Postponed breaks extend the focus time, but are no intervals of their own
+    assert_eq!(
+        summary,
+        StatsSummary {
+            intervals: 1,
+            focus_secs: 30 * 60,
+            break_secs: 5 * 60,
+            distractions: 3,
+        }
+    );
+}
+
+#[test]
+fn summary_only_counts_records_with_the_given_label() {
+    let start = Local.with_ymd_and_hms(2024, 1, 31, 9, 0, 0).unwrap();
+    let record = |label: Option<&str>| StatsRecord {
+        start,
+        end: start + chrono::Duration::minutes(25),
+        kind: TimerKind::Interval,
+        round: 1,
+        distractions: 0,
+        label: label.map(String::from),
+    };
+    let records = vec![
+        record(Some("zentime")),
+        record(Some("other project")),
+        record(None),
+        record(Some("zentime")),
+    ];
+
+    assert_eq!(filter_by_label(records.clone(), None), records);
+
+    let summary = StatsSummary::from_records(&filter_by_label(records, Some("zentime")));
+    assert_eq!(summary.intervals, 2);
+    assert_eq!(summary.focus_secs, 2 * 25 * 60);
+}
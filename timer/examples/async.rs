@@ -9,7 +9,7 @@ use zentime_rs_timer::pomodoro_timer_action::PomodoroTimerAction;
 
 #[tokio::main]
 async fn main() {
-    let (terminal_input_sender,  terminal_input_receiver) = channel();
+    let (terminal_input_sender, terminal_input_receiver) = channel();
     let (view_sender, mut view_receiver) = tokio::sync::mpsc::unbounded_channel();
 
     let config = PomodoroTimerConfig::default();
@@ -26,12 +26,7 @@ async fn main() {
 
                 sleep(Duration::from_secs(1));
 
-                let input = terminal_input_receiver.try_recv();
-
-                match input {
-                    Ok(action) => Some(action),
-                    _ => None,
-                }
+                terminal_input_receiver.try_recv().ok()
             }),
         );
 
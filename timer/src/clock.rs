@@ -0,0 +1,64 @@
+//! Source of the current time used by [crate::Timer].
+//! The timer uses the [SystemClock] by default, while a [MockClock] can be used to advance time
+//! synthetically (e.g. inside tests).
+
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+/// Provides the current point in time
+pub trait Clock: Debug {
+    /// Returns the current point in time
+    fn now(&self) -> Instant;
+}
+
+/// Clock returning the actual (monotonic) system time
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Clock which only moves forward when [MockClock::advance] is called
+///
+/// ## Example
+///
+/// ```
+/// use std::time::Duration;
+/// use zentime_rs_timer::clock::{Clock, MockClock};
+///
+/// let clock = MockClock::default();
+/// let start = clock.now();
+///
+/// clock.advance(Duration::from_secs(60));
+///
+/// assert_eq!(clock.now() - start, Duration::from_secs(60));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Cell<Instant>,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self {
+            now: Cell::new(Instant::now()),
+        }
+    }
+}
+
+impl MockClock {
+    /// Moves the clock forward by the given duration
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
@@ -1,20 +1,27 @@
 //! Configuration of a [Timer]
-use serde::{Deserialize, Serialize};
+use crate::util::{parse_duration, TimeFormat};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
 
 /// Timer configuration which determines certain aspects of the timer,
 /// like the duration of `intervals` and break lengths.
 #[derive(Debug, Deserialize, Serialize, Clone, Copy)]
 pub struct PomodoroTimerConfig {
-    /// Timer in seconds
+    /// Timer in seconds (or a human readable duration like "25m", see [parse_duration])
+    #[serde(deserialize_with = "deserialize_duration")]
     pub timer: u64,
 
-    /// Minor break time in seconds
+    /// Minor break time in seconds (or a human readable duration like "5m")
+    #[serde(deserialize_with = "deserialize_duration")]
     pub minor_break: u64,
 
-    /// Major break time in seconds
+    /// Major break time in seconds (or a human readable duration like "15m")
+    #[serde(deserialize_with = "deserialize_duration")]
     pub major_break: u64,
 
-    /// Intervals before major break
+    /// Intervals before major break.
+    /// Has to be at least 1, because a value of 0 would never lead to a major break.
     pub intervals: u64,
 
     /// Determines how often a break may be postponed.
@@ -22,8 +29,62 @@ pub struct PomodoroTimerConfig {
     /// disabled.
     pub postpone_limit: u16,
 
-    /// Determines how long each postpone timer runs (in seconds)
+    /// Determines how long each postpone timer runs (in seconds or as human readable duration)
+    #[serde(deserialize_with = "deserialize_duration")]
     pub postpone_timer: u64,
+
+    /// Number of rounds (focus interval + break) after which the session is complete and the
+    /// timer stops, instead of starting the next interval.
+    /// Runs indefinitely, if `None`.
+    #[serde(default)]
+    pub max_rounds: Option<u64>,
+
+    /// Breaks can not be skipped, if this is set (postponing them is still possible).
+    #[serde(default)]
+    pub strict_breaks: bool,
+
+    /// Focus intervals start counting right away once a break has ended, instead of waiting for
+    /// play/pause.
+    #[serde(default)]
+    pub auto_start_focus: bool,
+
+    /// The focus interval following a long break always waits for play/pause (even if
+    /// `auto_start_focus` is set), which marks the end of each full cycle.
+    #[serde(default)]
+    pub pause_after_long_break: bool,
+
+    /// Format of the current time (e.g. "25:00" or "00:25:00")
+    #[serde(default)]
+    pub time_format: TimeFormat,
+
+    /// Short breaks last for the given fraction of the preceding focus interval (e.g. 0.2 for a
+    /// 5 minute break after 25 minutes of focus) instead of `minor_break`.
+    /// The break lasts at least [MIN_RATIO_BREAK] seconds, but never longer than `major_break`.
+    #[serde(default)]
+    pub break_ratio: Option<f32>,
+
+    /// Grace period in seconds after a focus interval has run out, during which the timer counts
+    /// up (e.g. to finish a thought) before the break starts.
+    /// The break starts as soon as the grace period has elapsed or the timer is skipped, while
+    /// playing/pausing the timer pauses the overtime. The end of the focus interval (e.g. its
+    /// notification) is only reported once the break starts.
+    #[serde(default)]
+    pub overtime_secs: Option<u64>,
+}
+
+/// Minimum length of a break computed via [PomodoroTimerConfig::break_ratio] in seconds
+pub const MIN_RATIO_BREAK: u64 = 60;
+
+impl PomodoroTimerConfig {
+    /// Length of the short break following a focus interval which has been running for
+    /// `focus_secs` (if a [Self::break_ratio] has been configured)
+    pub fn ratio_break(&self, focus_secs: u64) -> Option<u64> {
+        self.break_ratio.map(|ratio| {
+            ((focus_secs as f32 * ratio).round() as u64)
+                .max(MIN_RATIO_BREAK)
+                .min(self.major_break)
+        })
+    }
 }
 
 impl Default for PomodoroTimerConfig {
@@ -35,6 +96,44 @@ impl Default for PomodoroTimerConfig {
             intervals: 4,
             postpone_limit: 0,
             postpone_timer: 300,
+            max_rounds: None,
+            strict_breaks: false,
+            auto_start_focus: false,
+            pause_after_long_break: false,
+            time_format: TimeFormat::default(),
+            break_ratio: None,
+            overtime_secs: None,
+        }
+    }
+}
+
+/// Deserializes a duration in seconds, which might either be given as plain (integer) number
+/// or as human readable string (see [parse_duration]).
+fn deserialize_duration<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct DurationVisitor;
+
+    impl<'de> Visitor<'de> for DurationVisitor {
+        type Value = u64;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a number of seconds or a duration string like \"25m\"")
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            Ok(value)
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            u64::try_from(value).map_err(|_| E::custom("duration must not be negative"))
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            parse_duration(value).map_err(E::custom)
         }
     }
+
+    deserializer.deserialize_any(DurationVisitor)
 }
@@ -3,6 +3,8 @@
     missing_copy_implementations,
     missing_debug_implementations
 )]
+// `is_multiple_of` would require Rust 1.87
+#![allow(unknown_lints, clippy::manual_is_multiple_of)]
 
 //! Pomodoro/Productivity timer that can transition between various states ([Paused]/[Running]),
 //! tracks intervals and can be configured.
@@ -70,6 +72,7 @@
 pub use timer::Timer;
 pub use timer_action::TimerAction;
 
+pub mod clock;
 pub mod config;
 pub mod pomodoro_timer;
 pub mod pomodoro_timer_action;
@@ -1,6 +1,9 @@
 //! Pomodoro timer implementation.
 //! When instantiated this runs instances of [Timer] internally and allows the transitioning
 //! between various states like [Interval], [ShortBreak] or [LongBreak].
+//! Alternatively a single focus session without any breaks can be run via
+//! [PomodoroTimer::one_shot] and an open-ended one, which counts up, via
+//! [PomodoroTimer::stopwatch].
 //!
 //! To communicate with "the outside world" two distinct closures are used:
 //!
@@ -10,14 +13,23 @@
 //! [OnTick] will be called on every tick of a running internal timer and can be used to
 //! reveive the current timer state and to send [PomodoroTimerActions].
 //!
+//! Both closures are [std::rc::Rc]s by default. If they should be created on another thread
+//! (e.g. inside a tokio task), [PomodoroTimer::new_send] accepts [SendOnTimerEnd] and
+//! [SendOnTick] closures instead.
+//!
 mod interval;
 mod long_break;
 mod on_end_handler;
 mod on_tick_handler;
+mod one_shot;
+mod overtime;
 mod postponed_long_break;
 mod postponed_short_break;
+mod session_complete;
 mod short_break;
 mod state;
+mod stopwatch;
 
-pub use on_end_handler::TimerKind;
+pub use on_end_handler::{SendOnTimerEnd, TimerKind};
+pub use on_tick_handler::SendOnTick;
 pub use state::{PomodoroTimer, ViewState};
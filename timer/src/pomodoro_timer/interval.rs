@@ -1,6 +1,9 @@
+use std::cell::Cell;
 use std::marker::PhantomData;
+use std::rc::Rc;
 
 use crate::{
+    clock::{Clock, SystemClock},
     config::PomodoroTimerConfig,
     timer::{Paused, TimerStatus, TimerTickHandler},
     Timer, TimerAction,
@@ -8,13 +11,18 @@ use crate::{
 
 use super::{
     long_break::LongBreak,
-    on_end_handler::{OnEndHandler, OnTimerEnd},
-    on_tick_handler::{OnTick, PomodoroActionHandler},
+    on_end_handler::{OnEndHandler, OnTimerEnd, SendOnTimerEnd},
+    on_tick_handler::{OnTick, PomodoroActionHandler, SendOnTick},
     short_break::ShortBreak,
-    state::{Callbacks, PomodoroState, PomodoroTimer, PomodoroTimerState, ViewState},
+    state::{
+        timer_progress, Callbacks, PomodoroState, PomodoroTimer, PomodoroTimerState, ViewState,
+    },
     TimerKind,
 };
 
+/// Notification of a focus interval, which has ended
+pub(crate) const INTERVAL_END_NOTIFICATION: &str = "Good job, take a break!";
+
 /// Pomodoro timer state designating a focus interval
 #[derive(Debug, Copy, Clone)]
 pub struct Interval {}
@@ -23,26 +31,64 @@ impl PomodoroState for Interval {}
 
 struct IntervalTickHandler {
     pomodoro_timer: PomodoroTimer<Interval>,
+
+    /// Remaining seconds of the last tick, which determine how long the interval has been
+    /// running once it has ended (see [PomodoroTimerConfig::break_ratio])
+    remaining_secs: Rc<Cell<u64>>,
 }
 
 impl PomodoroActionHandler<Interval> for IntervalTickHandler {
     fn get_timer(&self) -> PomodoroTimer<Interval> {
         self.pomodoro_timer.clone()
     }
+
+    /// Moves back to the break of the previous round, or restarts the very first interval
+    fn rewind(&self) {
+        let timer = self.get_timer();
+
+        match timer.shared_state.round {
+            0 | 1 => timer.rewind_to_interval(),
+            round => timer.rewind_to_break(round - 1),
+        }
+    }
 }
 
 impl TimerTickHandler for IntervalTickHandler {
     fn call(&mut self, status: TimerStatus) -> Option<TimerAction> {
         let callbacks = self.pomodoro_timer.callbacks.clone();
         let state = self.pomodoro_timer.shared_state;
+        self.remaining_secs.set(status.remaining_secs);
+        let current_round_secs = self
+            .pomodoro_timer
+            .config
+            .timer
+            .saturating_sub(status.remaining_secs);
 
         let result = (callbacks.on_tick)(ViewState {
+            kind: TimerKind::Interval,
             is_break: false,
             is_postponed: false,
             postpone_count: state.postponed_count,
             round: state.round,
+            intervals_until_long_break: self.pomodoro_timer.intervals_until_long_break(),
             time: status.current_time.to_string(),
+            remaining_secs: status.remaining_secs,
+            progress: timer_progress(self.pomodoro_timer.config.timer, status.remaining_secs),
             is_paused: status.is_paused,
+            goal_progress: None,
+            is_muted: false,
+            distractions: self.pomodoro_timer.distractions(),
+            history: vec![],
+            session_elapsed_secs: self.pomodoro_timer.session_elapsed_secs(current_round_secs),
+            cycle_remaining_secs: self
+                .pomodoro_timer
+                .cycle_remaining_secs(status.remaining_secs, false),
+            is_session_complete: false,
+            elapsed_secs: 0,
+            is_stopwatch: false,
+            label: None,
+            is_overtime: false,
+            can_postpone: false,
         });
 
         if let Some(action) = result {
@@ -69,32 +115,120 @@ impl PomodoroTimer<Interval> {
             callbacks: Callbacks {
                 on_timer_end,
                 on_tick,
+                clock: Rc::new(SystemClock),
+                next_interval: Rc::default(),
+                distractions: Rc::default(),
+                reloaded_config: Rc::default(),
             },
             marker: PhantomData,
         }
     }
 
+    /// Creates a new pomodoro timer just like [Self::new], but accepts thread-safe callbacks.
+    /// The callbacks can therefore be created anywhere (e.g. inside a tokio task) and moved
+    /// into the thread which runs the timer, without wrapping their captured state into [Rc]s.
+    /// NOTE: The timer itself still has to be created and run on a single thread
+    /// (e.g. via `tokio::task::spawn_blocking`), because [Self::init] never returns.
+    pub fn new_send(
+        config: PomodoroTimerConfig,
+        on_timer_end: SendOnTimerEnd,
+        on_tick: SendOnTick,
+    ) -> Self {
+        Self::new(
+            config,
+            Rc::new(move |state, msg, kind| on_timer_end(state, msg, kind)),
+            Rc::new(move |view_state| on_tick(view_state)),
+        )
+    }
+
+    /// Replaces the [SystemClock] used by all internal timers with the given clock
+    /// (e.g. a [crate::clock::MockClock] to advance time synthetically inside tests)
+    pub fn with_clock(mut self, clock: Rc<dyn Clock>) -> Self {
+        self.callbacks.clock = clock;
+        self
+    }
+
     /// Runs the timer so that the inner timer loop is started and the [OnTick]
     /// closure is being called continously.
     /// NOTE: This does not mean that the timer starts counting.
     /// The internal [Timer] will be initialized in a paused state, waiting for
     /// a [TimerAction:PlayPause]-action (triggered in turn by a [PomodoroTimerAction::PlayPause])
     pub fn init(self) {
+        self.run(false)
+    }
+
+    /// Runs the timer just like [Self::init], but the first interval starts counting right away.
+    /// All following timers are still initialized in a paused state (unless
+    /// [PomodoroTimerConfig::auto_start_focus] is set, which starts focus intervals after breaks).
+    pub fn init_running(self) {
+        self.run(true)
+    }
+
+    fn run(self, is_running: bool) {
         let is_major_break = self.shared_state.round % self.config.intervals == 0;
 
-        Timer::<Paused>::new(
-            self.config.timer,
+        // A pending override only applies to this interval
+        let current = self.take_focus_override();
+
+        // Distractions are counted per focus interval
+        self.callbacks.distractions.set(0);
+
+        let focus_secs = current.config.timer;
+        let remaining_secs = Rc::new(Cell::new(focus_secs));
+
+        // WHY:
+        // The last tick might still report a few remaining seconds, although the interval has
+        // run out completely
+        let on_timer_end = self.callbacks.on_timer_end.clone();
+        let end_remaining_secs = remaining_secs.clone();
+
+        // NOTE: With an overtime, the interval only ends with its overtime (see [super::overtime::Overtime])
+        let has_overtime = self.config.overtime_secs.is_some_and(|secs| secs > 0);
+
+        let timer = Timer::<Paused>::new(
+            focus_secs,
             Some(OnEndHandler {
-                on_timer_end: self.callbacks.on_timer_end.clone(),
+                on_timer_end: Rc::new(move |state, msg, kind| {
+                    end_remaining_secs.set(0);
+
+                    if !has_overtime {
+                        on_timer_end(state, msg, kind)
+                    }
+                }),
                 state: self.shared_state,
-                notification: Some("Good job, take a break!"),
+                notification: Some(INTERVAL_END_NOTIFICATION),
                 kind: TimerKind::Interval,
             }),
             Some(IntervalTickHandler {
-                pomodoro_timer: self.clone(),
+                pomodoro_timer: current,
+                remaining_secs: remaining_secs.clone(),
             }),
         )
-        .init();
+        .with_clock(self.callbacks.clock.clone())
+        .with_time_format(self.config.time_format);
+
+        if is_running {
+            timer.unpause();
+        } else {
+            timer.init();
+        }
+
+        // NOTE: Only an interval which has run out goes into overtime (a skipped one doesn't)
+        let has_run_out = remaining_secs.get() == 0;
+
+        // NOTE: An explicitly requested break duration (see [Self::set_next_interval]) takes
+        // precedence
+        let mut next_interval = self.callbacks.next_interval.get();
+        if !is_major_break && next_interval.break_secs.is_none() {
+            next_interval.break_secs = self
+                .config
+                .ratio_break(focus_secs.saturating_sub(remaining_secs.get()));
+            self.callbacks.next_interval.set(next_interval);
+        }
+
+        if has_run_out && has_overtime {
+            return self.overtime().init(is_major_break);
+        }
 
         Self::next(
             self.config,
@@ -104,7 +238,7 @@ impl PomodoroTimer<Interval> {
         )
     }
 
-    fn next(
+    pub(crate) fn next(
         config: PomodoroTimerConfig,
         callbacks: Callbacks,
         shared_state: PomodoroTimerState,
@@ -114,6 +248,7 @@ impl PomodoroTimer<Interval> {
             postponed_count: 0,
             ..shared_state
         };
+        let config = callbacks.take_reloaded_config(config);
 
         if is_major_break {
             PomodoroTimer {
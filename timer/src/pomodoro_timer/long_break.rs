@@ -12,7 +12,10 @@ use super::{
     on_end_handler::OnEndHandler,
     on_tick_handler::{PomodoroActionHandler, PostponeHandlerConfig},
     postponed_long_break::PostponedLongBreak,
-    state::{Callbacks, PomodoroState, PomodoroTimer, PomodoroTimerState, ViewState},
+    session_complete::{SessionComplete, SESSION_COMPLETE_NOTIFICATION},
+    state::{
+        timer_progress, Callbacks, PomodoroState, PomodoroTimer, PomodoroTimerState, ViewState,
+    },
     TimerKind,
 };
 
@@ -58,7 +61,7 @@ impl PomodoroActionHandler<LongBreak> for LongBreakTickHandler {
                 None
             }
             PomodoroTimerAction::PlayPause => Some(TimerAction::PlayPause),
-            PomodoroTimerAction::Skip => Some(TimerAction::End),
+            PomodoroTimerAction::Skip if !config.strict_breaks => Some(TimerAction::End),
             PomodoroTimerAction::SetTimer(time) => Some(TimerAction::SetTimer(time)),
 
             PomodoroTimerAction::ResetTimer => {
@@ -66,6 +69,21 @@ impl PomodoroActionHandler<LongBreak> for LongBreakTickHandler {
                 None
             }
 
+            PomodoroTimerAction::PreviousInterval => {
+                self.rewind();
+                None
+            }
+
+            PomodoroTimerAction::SetNextInterval { focus, break_secs } => {
+                self.pomodoro_timer.set_next_interval(focus, break_secs);
+                None
+            }
+
+            PomodoroTimerAction::ReloadConfig(config) => {
+                self.pomodoro_timer.reload_config(config);
+                None
+            }
+
             _ => None,
         }
     }
@@ -75,14 +93,39 @@ impl TimerTickHandler for LongBreakTickHandler {
     fn call(&mut self, status: TimerStatus) -> Option<TimerAction> {
         let callbacks = self.pomodoro_timer.callbacks.clone();
         let state = self.pomodoro_timer.shared_state;
+        let config = self.pomodoro_timer.config;
+        let current_round_secs =
+            (config.timer + self.pomodoro_timer.postponed_secs() + config.major_break)
+                .saturating_sub(status.remaining_secs);
 
         let result = (callbacks.on_tick)(ViewState {
+            kind: TimerKind::LongBreak,
             is_break: true,
             is_postponed: false,
             postpone_count: state.postponed_count,
             round: state.round,
+            intervals_until_long_break: self.pomodoro_timer.intervals_until_long_break(),
             time: status.current_time.to_string(),
+            remaining_secs: status.remaining_secs,
+            progress: timer_progress(config.major_break, status.remaining_secs),
             is_paused: status.is_paused,
+            goal_progress: None,
+            is_muted: false,
+            distractions: self.pomodoro_timer.distractions(),
+            history: vec![],
+            session_elapsed_secs: self.pomodoro_timer.session_elapsed_secs(current_round_secs),
+            cycle_remaining_secs: self
+                .pomodoro_timer
+                .cycle_remaining_secs(status.remaining_secs, true),
+            is_session_complete: false,
+            elapsed_secs: 0,
+            is_stopwatch: false,
+            label: None,
+            is_overtime: false,
+            can_postpone: Self::can_postpone(PostponeHandlerConfig {
+                postpone_limit: config.postpone_limit,
+                postponed_count: state.postponed_count,
+            }),
         });
 
         if let Some(action) = result {
@@ -101,20 +144,37 @@ impl PomodoroTimer<LongBreak> {
             postponed_count: self.shared_state.postponed_count,
         };
 
+        // A pending override only applies to this break
+        let current = self.take_break_override();
+
         Timer::<Paused>::new(
-            self.config.major_break,
+            current.config.major_break,
             Some(OnEndHandler {
                 on_timer_end: self.callbacks.on_timer_end.clone(),
                 state: self.shared_state,
-                notification: Some("Break is over"),
-                kind: TimerKind::Break,
+                notification: Some(if self.is_last_round() {
+                    SESSION_COMPLETE_NOTIFICATION
+                } else {
+                    "Break is over"
+                }),
+                kind: TimerKind::LongBreak,
             }),
             Some(LongBreakTickHandler {
-                pomodoro_timer: self.clone(),
+                pomodoro_timer: current,
             }),
         )
+        .with_clock(self.callbacks.clock.clone())
+        .with_time_format(self.config.time_format)
         .init();
 
+        if self.is_last_round() {
+            return PomodoroTimer::<SessionComplete>::complete(
+                self.config,
+                self.callbacks,
+                self.shared_state,
+            );
+        }
+
         Self::next(self.config, self.callbacks, next_shared_state)
     }
 
@@ -133,12 +193,20 @@ impl PomodoroTimer<LongBreak> {
     }
 
     fn next(config: PomodoroTimerConfig, callbacks: Callbacks, shared_state: PomodoroTimerState) {
-        PomodoroTimer {
+        let config = callbacks.take_reloaded_config(config);
+
+        let timer = PomodoroTimer {
             shared_state,
             config,
             callbacks,
             marker: PhantomData::<Interval>,
+        };
+
+        // A full cycle ends after the long break, which is a natural point to stop
+        if config.auto_start_focus && !config.pause_after_long_break {
+            timer.init_running();
+        } else {
+            timer.init();
         }
-        .init();
     }
 }
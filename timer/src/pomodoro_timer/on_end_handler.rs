@@ -1,21 +1,59 @@
+use serde::{Deserialize, Serialize};
 use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::timer::TimerEndHandler;
 
 use super::state::PomodoroTimerState;
 
 /// Describes pomodoro timer kind
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TimerKind {
     /// Always used when the current timer is not a break timer
+    #[default]
     Interval,
 
-    /// Only used for breaks
+    /// Only used for short breaks
     Break,
+
+    /// Only used for long breaks
+    LongBreak,
+
+    /// Postponed short break.
+    /// Only reported through [super::ViewState::kind] - the end of a postponed break is reported
+    /// as [TimerKind::Interval].
+    PostponedBreak,
+
+    /// Postponed long break.
+    /// Only reported through [super::ViewState::kind] - the end of a postponed break is reported
+    /// as [TimerKind::Interval].
+    PostponedLongBreak,
+
+    /// All rounds of the session have been completed.
+    /// Only reported through [super::ViewState::kind].
+    SessionComplete,
+}
+
+impl TimerKind {
+    /// Denotes if this is a (short or long) break, which is not postponed
+    pub fn is_break(&self) -> bool {
+        matches!(self, TimerKind::Break | TimerKind::LongBreak)
+    }
+
+    /// Denotes if this is a postponed (short or long) break
+    pub fn is_postponed(&self) -> bool {
+        matches!(
+            self,
+            TimerKind::PostponedBreak | TimerKind::PostponedLongBreak
+        )
+    }
 }
 
 pub type OnTimerEnd = Rc<dyn Fn(PomodoroTimerState, Option<&str>, TimerKind)>;
 
+/// Thread-safe variant of [OnTimerEnd] (see [super::PomodoroTimer::new_send])
+pub type SendOnTimerEnd = Arc<dyn Fn(PomodoroTimerState, Option<&str>, TimerKind) + Send + Sync>;
+
 /// Handler which is passed to our timer implementation
 pub struct OnEndHandler {
     pub on_timer_end: OnTimerEnd,
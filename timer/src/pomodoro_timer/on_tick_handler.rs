@@ -1,4 +1,5 @@
 use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::{pomodoro_timer_action::PomodoroTimerAction, TimerAction};
 
@@ -9,6 +10,9 @@ use super::{
 
 pub type OnTick = Rc<dyn Fn(ViewState) -> Option<PomodoroTimerAction>>;
 
+/// Thread-safe variant of [OnTick] (see [PomodoroTimer::new_send])
+pub type SendOnTick = Arc<dyn Fn(ViewState) -> Option<PomodoroTimerAction> + Send + Sync>;
+
 pub struct PostponeHandlerConfig {
     pub postpone_limit: u16,
     pub postponed_count: u16,
@@ -25,6 +29,12 @@ pub trait PomodoroActionHandler<S: PomodoroState> {
 
     fn get_timer(&self) -> PomodoroTimer<S>;
 
+    /// Moves back one step inside the pomodoro cycle.
+    /// By default this is the focus interval of the current round.
+    fn rewind(&self) {
+        PomodoroActionHandler::<S>::get_timer(self).rewind_to_interval();
+    }
+
     fn handle_action(&self, action: PomodoroTimerAction) -> Option<TimerAction> {
         let timer = PomodoroActionHandler::<S>::get_timer(self);
 
@@ -39,6 +49,26 @@ pub trait PomodoroActionHandler<S: PomodoroState> {
 
             PomodoroTimerAction::SetTimer(time) => Some(TimerAction::SetTimer(time)),
 
+            PomodoroTimerAction::PreviousInterval => {
+                PomodoroActionHandler::<S>::rewind(self);
+                None
+            }
+
+            PomodoroTimerAction::SetNextInterval { focus, break_secs } => {
+                timer.set_next_interval(focus, break_secs);
+                None
+            }
+
+            PomodoroTimerAction::ReloadConfig(config) => {
+                timer.reload_config(config);
+                None
+            }
+
+            PomodoroTimerAction::LogDistraction => {
+                timer.log_distraction();
+                None
+            }
+
             _ => None,
         }
     }
@@ -0,0 +1,136 @@
+use std::marker::PhantomData;
+
+use crate::{
+    config::PomodoroTimerConfig,
+    pomodoro_timer_action::PomodoroTimerAction,
+    timer::{Running, TimerStatus, TimerTickHandler},
+    Timer, TimerAction,
+};
+
+use super::{
+    interval::Interval,
+    on_end_handler::OnEndHandler,
+    on_tick_handler::PomodoroActionHandler,
+    state::{timer_progress, PomodoroState, PomodoroTimer, ViewState},
+    TimerKind,
+};
+
+/// Pomodoro timer state designating a single focus session, which does not transition into
+/// a break once it has ended
+#[derive(Debug, Copy, Clone)]
+pub struct OneShot {}
+
+impl PomodoroState for OneShot {}
+
+struct OneShotTickHandler {
+    pomodoro_timer: PomodoroTimer<OneShot>,
+}
+
+impl PomodoroActionHandler<OneShot> for OneShotTickHandler {
+    fn get_timer(&self) -> PomodoroTimer<OneShot> {
+        self.pomodoro_timer.clone()
+    }
+
+    fn handle_action(&self, action: PomodoroTimerAction) -> Option<TimerAction> {
+        match action {
+            PomodoroTimerAction::PlayPause => Some(TimerAction::PlayPause),
+            PomodoroTimerAction::Skip => Some(TimerAction::End),
+
+            // There is no pomodoro cycle to reset to, so we simply start the session over
+            PomodoroTimerAction::ResetTimer => {
+                Some(TimerAction::SetTimer(self.pomodoro_timer.config.timer))
+            }
+
+            PomodoroTimerAction::SetTimer(time) => Some(TimerAction::SetTimer(time)),
+
+            PomodoroTimerAction::LogDistraction => {
+                self.pomodoro_timer.log_distraction();
+                None
+            }
+
+            _ => None,
+        }
+    }
+}
+
+impl TimerTickHandler for OneShotTickHandler {
+    fn call(&mut self, status: TimerStatus) -> Option<TimerAction> {
+        let callbacks = self.pomodoro_timer.callbacks.clone();
+        let state = self.pomodoro_timer.shared_state;
+        let current_round_secs = self
+            .pomodoro_timer
+            .config
+            .timer
+            .saturating_sub(status.remaining_secs);
+
+        let result = (callbacks.on_tick)(ViewState {
+            kind: TimerKind::Interval,
+            is_break: false,
+            is_postponed: false,
+            postpone_count: state.postponed_count,
+            round: state.round,
+            intervals_until_long_break: self.pomodoro_timer.intervals_until_long_break(),
+            time: status.current_time.to_string(),
+            remaining_secs: status.remaining_secs,
+            progress: timer_progress(self.pomodoro_timer.config.timer, status.remaining_secs),
+            is_paused: status.is_paused,
+            goal_progress: None,
+            is_muted: false,
+            distractions: self.pomodoro_timer.distractions(),
+            history: vec![],
+            session_elapsed_secs: self.pomodoro_timer.session_elapsed_secs(current_round_secs),
+            cycle_remaining_secs: status.remaining_secs,
+            is_session_complete: false,
+            elapsed_secs: 0,
+            is_stopwatch: false,
+            label: None,
+            is_overtime: false,
+            can_postpone: false,
+        });
+
+        if let Some(action) = result {
+            self.handle_action(action)
+        } else {
+            None
+        }
+    }
+}
+
+impl PomodoroTimer<Interval> {
+    /// Turns the pomodoro timer into a single focus session of `time` seconds.
+    /// Instead of transitioning into a break, [PomodoroTimer::<OneShot>::init] returns once the
+    /// session has ended (or has been skipped).
+    pub fn one_shot(self, time: u64) -> PomodoroTimer<OneShot> {
+        PomodoroTimer {
+            shared_state: self.shared_state,
+            config: PomodoroTimerConfig {
+                timer: time,
+                ..self.config
+            },
+            callbacks: self.callbacks,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl PomodoroTimer<OneShot> {
+    /// Runs the focus session right away and blocks until it has ended.
+    /// The [OnTimerEnd] closure is called once, when the timer runs out.
+    pub fn init(self) {
+        Timer::<Running>::new(
+            self.config.timer,
+            Some(OnEndHandler {
+                on_timer_end: self.callbacks.on_timer_end.clone(),
+                state: self.shared_state,
+                notification: Some("Focus session finished!"),
+                kind: TimerKind::Interval,
+            }),
+            Some(OneShotTickHandler {
+                pomodoro_timer: self.clone(),
+            }),
+        )
+        .with_clock(self.callbacks.clock.clone())
+        .with_time_format(self.config.time_format)
+        .init();
+    }
+}
@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use crate::pomodoro_timer_action::PomodoroTimerAction;
+
+use super::{
+    interval::{Interval, INTERVAL_END_NOTIFICATION},
+    state::{PomodoroState, PomodoroTimer, ViewState},
+    TimerKind,
+};
+
+/// Pomodoro timer state designating the grace period after a focus interval has run out
+/// (see [crate::config::PomodoroTimerConfig::overtime_secs]), which counts up until the break
+/// starts
+#[derive(Debug, Copy, Clone)]
+pub struct Overtime {}
+
+impl PomodoroState for Overtime {}
+
+impl PomodoroTimer<Interval> {
+    /// Turns the focus interval, which has just run out, into its overtime
+    pub(crate) fn overtime(self) -> PomodoroTimer<Overtime> {
+        PomodoroTimer {
+            shared_state: self.shared_state,
+            config: self.config,
+            callbacks: self.callbacks,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl PomodoroTimer<Overtime> {
+    /// Counts up right away and transitions into the following break, once the grace period
+    /// has elapsed or the user skips the timer. Playing/pausing the timer pauses the overtime
+    /// (and its grace period).
+    ///
+    /// NOTE: The focus interval only ends with its overtime, which is why the `on_timer_end`
+    /// callback of the interval (e.g. its notification) is called right before the break starts.
+    /// An overtime which is reset or rewound doesn't end the interval at all.
+    pub(crate) fn init(self, is_major_break: bool) {
+        let clock = self.callbacks.clock.clone();
+        let grace = Duration::from_secs(self.config.overtime_secs.unwrap_or_default());
+
+        // Overtime which has elapsed before the current pause (or before it has been resumed)
+        let mut elapsed_before = Duration::ZERO;
+
+        // Time at which the overtime has last been resumed (`None` while it is paused)
+        let mut resumed_at = Some(clock.now());
+
+        loop {
+            let elapsed = elapsed_before
+                + resumed_at.map_or(Duration::ZERO, |resumed_at| {
+                    clock.now().saturating_duration_since(resumed_at)
+                });
+            if elapsed >= grace {
+                break;
+            }
+
+            let elapsed_secs = elapsed.as_secs();
+            let remaining_secs = (grace - elapsed).as_secs();
+
+            let action = (self.callbacks.on_tick)(ViewState {
+                kind: TimerKind::Interval,
+                is_break: false,
+                is_postponed: false,
+                postpone_count: 0,
+                round: self.shared_state.round,
+                intervals_until_long_break: self.intervals_until_long_break(),
+                time: format!("+{}", self.config.time_format.format(elapsed_secs)),
+                remaining_secs,
+                progress: 1.0,
+                is_paused: resumed_at.is_none(),
+                goal_progress: None,
+                is_muted: false,
+                distractions: self.distractions(),
+                history: vec![],
+                session_elapsed_secs: self.session_elapsed_secs(self.config.timer),
+                cycle_remaining_secs: self.cycle_remaining_secs(0, false),
+                is_session_complete: false,
+                elapsed_secs,
+                is_stopwatch: false,
+                label: None,
+                is_overtime: true,
+                can_postpone: false,
+            });
+
+            match action {
+                Some(PomodoroTimerAction::PlayPause) => match resumed_at.take() {
+                    Some(_) => elapsed_before = elapsed,
+                    None => resumed_at = Some(clock.now()),
+                },
+
+                Some(PomodoroTimerAction::Skip) => break,
+
+                Some(PomodoroTimerAction::ResetTimer) => {
+                    return PomodoroTimer::<Interval>::reset(self.config, self.callbacks).init();
+                }
+
+                Some(PomodoroTimerAction::PreviousInterval) => return self.rewind_to_interval(),
+
+                Some(PomodoroTimerAction::SetNextInterval { focus, break_secs }) => {
+                    self.set_next_interval(focus, break_secs)
+                }
+
+                Some(PomodoroTimerAction::ReloadConfig(config)) => self.reload_config(config),
+
+                Some(PomodoroTimerAction::LogDistraction) => self.log_distraction(),
+
+                _ => {}
+            }
+        }
+
+        (self.callbacks.on_timer_end)(
+            self.shared_state,
+            Some(INTERVAL_END_NOTIFICATION),
+            TimerKind::Interval,
+        );
+
+        PomodoroTimer::<Interval>::next(
+            self.config,
+            self.callbacks,
+            self.shared_state,
+            is_major_break,
+        )
+    }
+}
@@ -10,7 +10,9 @@ use super::{
     long_break::LongBreak,
     on_end_handler::OnEndHandler,
     on_tick_handler::PomodoroActionHandler,
-    state::{Callbacks, PomodoroState, PomodoroTimer, PomodoroTimerState, ViewState},
+    state::{
+        timer_progress, Callbacks, PomodoroState, PomodoroTimer, PomodoroTimerState, ViewState,
+    },
     TimerKind,
 };
 
@@ -34,14 +36,35 @@ impl TimerTickHandler for PostponeLongBreakTickHandler {
     fn call(&mut self, status: TimerStatus) -> Option<TimerAction> {
         let callbacks = self.pomodoro_timer.callbacks.clone();
         let state = self.pomodoro_timer.shared_state;
+        let config = self.pomodoro_timer.config;
+        let current_round_secs = (config.timer + self.pomodoro_timer.postponed_secs())
+            .saturating_sub(status.remaining_secs);
 
         let result = (callbacks.on_tick)(ViewState {
+            kind: TimerKind::PostponedLongBreak,
             is_break: false,
             is_postponed: true,
             postpone_count: state.postponed_count,
             round: state.round,
+            intervals_until_long_break: self.pomodoro_timer.intervals_until_long_break(),
             time: status.current_time.to_string(),
+            remaining_secs: status.remaining_secs,
+            progress: timer_progress(config.postpone_timer, status.remaining_secs),
             is_paused: status.is_paused,
+            goal_progress: None,
+            is_muted: false,
+            distractions: self.pomodoro_timer.distractions(),
+            history: vec![],
+            session_elapsed_secs: self.pomodoro_timer.session_elapsed_secs(current_round_secs),
+            cycle_remaining_secs: self
+                .pomodoro_timer
+                .cycle_remaining_secs(status.remaining_secs, false),
+            is_session_complete: false,
+            elapsed_secs: 0,
+            is_stopwatch: false,
+            label: None,
+            is_overtime: false,
+            can_postpone: false,
         });
 
         if let Some(action) = result {
@@ -67,6 +90,8 @@ impl PomodoroTimer<PostponedLongBreak> {
                 pomodoro_timer: self.clone(),
             }),
         )
+        .with_clock(self.callbacks.clock.clone())
+        .with_time_format(self.config.time_format)
         .init();
 
         Self::next(self.config, self.callbacks, self.shared_state)
@@ -0,0 +1,147 @@
+use std::marker::PhantomData;
+
+use crate::{
+    config::PomodoroTimerConfig,
+    pomodoro_timer_action::PomodoroTimerAction,
+    timer::{Paused, TimerStatus, TimerTickHandler},
+    Timer, TimerAction,
+};
+
+use super::{
+    interval::Interval,
+    on_end_handler::OnEndHandler,
+    on_tick_handler::PomodoroActionHandler,
+    state::{Callbacks, PomodoroState, PomodoroTimer, PomodoroTimerState, ViewState},
+    TimerKind,
+};
+
+/// Notification which is sent at the end of the last break of a session
+pub(crate) const SESSION_COMPLETE_NOTIFICATION: &str = "Session complete - well done!";
+
+/// Pomodoro timer state designating a session, which has completed all configured rounds
+/// (see [crate::config::PomodoroTimerConfig::max_rounds]).
+/// The timer stays in this state until it is reset.
+#[derive(Debug, Copy, Clone)]
+pub struct SessionComplete {}
+
+impl PomodoroState for SessionComplete {}
+
+struct SessionCompleteTickHandler {
+    pomodoro_timer: PomodoroTimer<SessionComplete>,
+}
+
+impl PomodoroActionHandler<SessionComplete> for SessionCompleteTickHandler {
+    fn get_timer(&self) -> PomodoroTimer<SessionComplete> {
+        self.pomodoro_timer.clone()
+    }
+
+    /// Moves back to the last break of the session
+    fn rewind(&self) {
+        let timer = self.get_timer();
+        let round = timer.shared_state.round;
+
+        timer.rewind_to_break(round);
+    }
+
+    fn handle_action(&self, action: PomodoroTimerAction) -> Option<TimerAction> {
+        let timer = self.get_timer();
+
+        match action {
+            PomodoroTimerAction::ResetTimer => {
+                PomodoroTimer::<Interval>::reset(timer.config, timer.callbacks).init();
+                None
+            }
+
+            PomodoroTimerAction::PreviousInterval => {
+                self.rewind();
+                None
+            }
+
+            // Applied once the timer is reset
+            PomodoroTimerAction::ReloadConfig(config) => {
+                timer.reload_config(config);
+                None
+            }
+
+            // There is nothing left to play, skip or postpone
+            _ => None,
+        }
+    }
+}
+
+impl TimerTickHandler for SessionCompleteTickHandler {
+    fn call(&mut self, status: TimerStatus) -> Option<TimerAction> {
+        let callbacks = self.pomodoro_timer.callbacks.clone();
+        let state = self.pomodoro_timer.shared_state;
+        let config = self.pomodoro_timer.config;
+
+        let break_secs = if state.round % config.intervals == 0 {
+            config.major_break
+        } else {
+            config.minor_break
+        };
+        let current_round_secs = config.timer + self.pomodoro_timer.postponed_secs() + break_secs;
+
+        let result = (callbacks.on_tick)(ViewState {
+            kind: TimerKind::SessionComplete,
+            is_break: false,
+            is_postponed: false,
+            postpone_count: state.postponed_count,
+            round: state.round,
+            intervals_until_long_break: 0,
+            time: status.current_time.to_string(),
+            remaining_secs: status.remaining_secs,
+            progress: 1.0,
+            is_paused: status.is_paused,
+            goal_progress: None,
+            is_muted: false,
+            distractions: self.pomodoro_timer.distractions(),
+            history: vec![],
+            session_elapsed_secs: self.pomodoro_timer.session_elapsed_secs(current_round_secs),
+            cycle_remaining_secs: 0,
+            is_session_complete: true,
+            elapsed_secs: 0,
+            is_stopwatch: false,
+            label: None,
+            is_overtime: false,
+            can_postpone: false,
+        });
+
+        if let Some(action) = result {
+            self.handle_action(action)
+        } else {
+            None
+        }
+    }
+}
+
+impl PomodoroTimer<SessionComplete> {
+    /// Waits (without ever ending on its own) until the timer is reset
+    pub(crate) fn init(self) {
+        Timer::<Paused>::new(
+            0,
+            None::<OnEndHandler>,
+            Some(SessionCompleteTickHandler {
+                pomodoro_timer: self.clone(),
+            }),
+        )
+        .with_clock(self.callbacks.clock.clone())
+        .with_time_format(self.config.time_format)
+        .init();
+    }
+
+    /// Completes the session after the break of the last round
+    pub(crate) fn complete(
+        config: PomodoroTimerConfig,
+        callbacks: Callbacks,
+        shared_state: PomodoroTimerState,
+    ) {
+        PomodoroTimer {
+            shared_state,
+            config,
+            callbacks,
+            marker: PhantomData::<SessionComplete>,
+        }
+        .init();
+    }
+}
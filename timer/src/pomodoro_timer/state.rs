@@ -1,7 +1,14 @@
-use super::{interval::Interval, on_end_handler::OnTimerEnd, on_tick_handler::OnTick};
+use super::{
+    interval::Interval,
+    long_break::LongBreak,
+    on_end_handler::{OnTimerEnd, TimerKind},
+    on_tick_handler::OnTick,
+    short_break::ShortBreak,
+};
+use crate::clock::Clock;
 use crate::config::PomodoroTimerConfig;
 use serde::{Deserialize, Serialize};
-use std::{fmt::Debug, marker::PhantomData};
+use std::{cell::Cell, fmt::Debug, marker::PhantomData, rc::Rc};
 
 /// General trait describing the various states a pomodoro timer can be in
 pub trait PomodoroState {}
@@ -20,12 +27,18 @@ pub struct PomodoroTimerState {
 }
 
 /// Information that will be handed to the [on_tick] closure continously
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ViewState {
-    /// Denotes if the current timer is a break timer
+    /// Kind of the current timer
+    #[serde(default)]
+    pub kind: TimerKind,
+
+    /// Denotes if the current timer is a break timer.
+    /// Kept for compatibility, prefer [Self::kind] instead.
     pub is_break: bool,
 
-    /// Denotes if the timer is currently in a postponed state or not
+    /// Denotes if the timer is currently in a postponed state or not.
+    /// Kept for compatibility, prefer [Self::kind] instead.
     pub is_postponed: bool,
 
     /// Denotes how often the current timer has already been postponed
@@ -34,17 +47,129 @@ pub struct ViewState {
     /// Denotes the current interval round
     pub round: u64,
 
+    /// Denotes how many intervals remain until the next long break
+    #[serde(default)]
+    pub intervals_until_long_break: u64,
+
     /// Denotes the current time of the timer
     pub time: String,
 
+    /// Denotes the remaining time of the timer in seconds (the unformatted counterpart of [Self::time])
+    #[serde(default)]
+    pub remaining_secs: u64,
+
+    /// Progress of the current timer from 0.0 (just started) to 1.0 (ended), relative to the
+    /// configured duration of the current [TimerKind] (or postpone timer)
+    #[serde(default)]
+    pub progress: f32,
+
     /// Denotes if the timer is currently paused
     pub is_paused: bool,
+
+    /// Completed focus intervals of the current day and the daily goal (completed, goal).
+    /// The timer itself does not track this, so it is always `None` unless a caller
+    /// (e.g. the zentime server) fills it in.
+    #[serde(default)]
+    pub goal_progress: Option<(u64, u64)>,
+
+    /// Denotes if notifications (bell and OS-notification) are currently muted.
+    /// The timer itself does not track this, so it is always `false` unless a caller
+    /// (e.g. the zentime server) fills it in.
+    #[serde(default)]
+    pub is_muted: bool,
+
+    /// Distractions which have been logged during the current (or, while on a break, the
+    /// most recent) focus interval (see [crate::pomodoro_timer_action::PomodoroTimerAction::LogDistraction])
+    #[serde(default)]
+    pub distractions: u16,
+
+    /// Kinds of the timers which have been completed during the current session (oldest first).
+    /// The timer itself does not track this, so it is always empty unless a caller
+    /// (e.g. the zentime server) fills it in.
+    #[serde(default)]
+    pub history: Vec<TimerKind>,
+
+    /// Estimated seconds of all focus intervals and breaks of the current session (assuming
+    /// no pauses). Postponed breaks of previous rounds are not included.
+    #[serde(default)]
+    pub session_elapsed_secs: u64,
+
+    /// Estimated seconds until the next long break starts (assuming no pauses)
+    #[serde(default)]
+    pub cycle_remaining_secs: u64,
+
+    /// Denotes if all rounds of the session have been completed
+    /// (see [PomodoroTimerConfig::max_rounds]). The timer waits for a reset in this case.
+    /// Kept for compatibility, prefer [Self::kind] instead.
+    #[serde(default)]
+    pub is_session_complete: bool,
+
+    /// Seconds the stopwatch has been counting (excluding pauses) or the overtime of the focus
+    /// interval (see [Self::is_overtime]).
+    /// Only set in stopwatch mode (see [PomodoroTimer::stopwatch]) or during overtime, 0 otherwise.
+    #[serde(default)]
+    pub elapsed_secs: u64,
+
+    /// Denotes if the timer is a stopwatch, which counts up instead of down
+    #[serde(default)]
+    pub is_stopwatch: bool,
+
+    /// Label of the current timer (e.g. the task one is focusing on).
+    /// The timer itself does not track this, so it is always `None` unless a caller
+    /// (e.g. the zentime server) fills it in.
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// Denotes if the focus interval has run out and the timer is counting up during its
+    /// grace period (see [PomodoroTimerConfig::overtime_secs]). [Self::elapsed_secs] holds the
+    /// overtime and [Self::remaining_secs] the rest of the grace period in that case.
+    #[serde(default)]
+    pub is_overtime: bool,
+
+    /// Denotes if the current break can still be postponed
+    /// (see [PomodoroTimerConfig::postpone_limit]). Always `false` outside of breaks.
+    #[serde(default)]
+    pub can_postpone: bool,
+}
+
+/// One-time overrides of the configured durations
+/// (see [crate::pomodoro_timer_action::PomodoroTimerAction::SetNextInterval])
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NextInterval {
+    /// Duration of the next focus interval in seconds
+    pub focus: Option<u64>,
+
+    /// Duration of the next (short or long) break in seconds
+    pub break_secs: Option<u64>,
 }
 
 #[derive(Clone)]
 pub struct Callbacks {
     pub on_timer_end: OnTimerEnd,
     pub on_tick: OnTick,
+
+    /// Source of the current time, which is handed to each internal [crate::Timer]
+    pub clock: Rc<dyn Clock>,
+
+    /// Pending duration overrides. They are shared between all transitions (just like the
+    /// callbacks themselves) and are consumed by the next timer of the matching kind.
+    pub next_interval: Rc<Cell<NextInterval>>,
+
+    /// Distractions logged during the current focus interval.
+    /// This is shared just like [Self::next_interval] and reset whenever a focus interval starts.
+    pub distractions: Rc<Cell<u16>>,
+
+    /// Reloaded configuration, which is shared just like [Self::next_interval] and is consumed
+    /// by the next timer (see [crate::pomodoro_timer_action::PomodoroTimerAction::ReloadConfig])
+    pub reloaded_config: Rc<Cell<Option<PomodoroTimerConfig>>>,
+}
+
+impl Callbacks {
+    /// Returns the reloaded configuration (if any), which is consumed in the process, or the
+    /// given configuration otherwise
+    pub(crate) fn take_reloaded_config(&self, config: PomodoroTimerConfig) -> PomodoroTimerConfig {
+        self.reloaded_config.take().unwrap_or(config)
+    }
 }
 
 impl Debug for Callbacks {
@@ -83,8 +208,192 @@ impl<S: PomodoroState + Debug> Debug for PomodoroTimer<S> {
 }
 
 impl<S: PomodoroState> PomodoroTimer<S> {
+    /// Stores overrides for the durations of the next focus interval and/or break
+    pub(crate) fn set_next_interval(&self, focus: Option<u64>, break_secs: Option<u64>) {
+        let next_interval = self.callbacks.next_interval.get();
+
+        self.callbacks.next_interval.set(NextInterval {
+            focus: focus.or(next_interval.focus),
+            break_secs: break_secs.or(next_interval.break_secs),
+        });
+    }
+
+    /// Stores a reloaded configuration, which is applied once the next timer starts
+    pub(crate) fn reload_config(&self, config: PomodoroTimerConfig) {
+        self.callbacks.reloaded_config.set(Some(config));
+    }
+
+    /// Returns the current timer with its focus interval duration replaced by a pending
+    /// override (if any), which is consumed in the process
+    pub(crate) fn take_focus_override(&self) -> Self {
+        let mut next_interval = self.callbacks.next_interval.get();
+        let mut config = self.config;
+
+        if let Some(focus) = next_interval.focus.take() {
+            config.timer = focus;
+            self.callbacks.next_interval.set(next_interval);
+        }
+
+        self.with_config(config)
+    }
+
+    /// Returns the current timer with its break durations replaced by a pending override
+    /// (if any), which is consumed in the process
+    pub(crate) fn take_break_override(&self) -> Self {
+        let mut next_interval = self.callbacks.next_interval.get();
+        let mut config = self.config;
+
+        if let Some(break_secs) = next_interval.break_secs.take() {
+            config.minor_break = break_secs;
+            config.major_break = break_secs;
+            self.callbacks.next_interval.set(next_interval);
+        }
+
+        self.with_config(config)
+    }
+
+    /// Counts a distraction during the current focus interval
+    pub(crate) fn log_distraction(&self) {
+        let distractions = &self.callbacks.distractions;
+        distractions.set(distractions.get().saturating_add(1));
+    }
+
+    /// Distractions logged during the current (or most recent) focus interval
+    pub(crate) fn distractions(&self) -> u16 {
+        self.callbacks.distractions.get()
+    }
+
+    fn with_config(&self, config: PomodoroTimerConfig) -> Self {
+        PomodoroTimer {
+            config,
+            callbacks: self.callbacks.clone(),
+            shared_state: self.shared_state,
+            marker: PhantomData,
+        }
+    }
+
     /// Resets the pomodoro timer to the very first interval
     pub fn reset(config: PomodoroTimerConfig, callbacks: Callbacks) -> PomodoroTimer<Interval> {
+        let config = callbacks.take_reloaded_config(config);
+
         PomodoroTimer::new(config, callbacks.on_timer_end, callbacks.on_tick)
+            .with_clock(callbacks.clock)
+    }
+
+    /// Moves back to the focus interval of the current round, which starts paused
+    pub(crate) fn rewind_to_interval(self) {
+        PomodoroTimer {
+            shared_state: PomodoroTimerState {
+                round: self.shared_state.round,
+                postponed_count: 0,
+            },
+            config: self.config,
+            callbacks: self.callbacks,
+            marker: PhantomData::<Interval>,
+        }
+        .init();
+    }
+
+    /// Moves back to the (short or long) break which follows the focus interval of the given
+    /// round. The break starts paused.
+    pub(crate) fn rewind_to_break(self, round: u64) {
+        let shared_state = PomodoroTimerState {
+            round,
+            postponed_count: 0,
+        };
+
+        if round % self.config.intervals == 0 {
+            PomodoroTimer {
+                shared_state,
+                config: self.config,
+                callbacks: self.callbacks,
+                marker: PhantomData::<LongBreak>,
+            }
+            .init();
+        } else {
+            PomodoroTimer {
+                shared_state,
+                config: self.config,
+                callbacks: self.callbacks,
+                marker: PhantomData::<ShortBreak>,
+            }
+            .init();
+        }
+    }
+
+    /// Number of intervals remaining until the next long break.
+    /// If the current round is an exact multiple of the configured intervals, the full interval
+    /// count is returned (instead of zero).
+    pub(crate) fn intervals_until_long_break(&self) -> u64 {
+        let intervals = self.config.intervals;
+
+        self.shared_state
+            .round
+            .checked_rem(intervals)
+            .map_or(0, |rest| intervals - rest)
     }
+
+    /// Estimated seconds of the current session, given the seconds which have passed during
+    /// the current round (assuming no pauses)
+    pub(crate) fn session_elapsed_secs(&self, current_round_secs: u64) -> u64 {
+        let config = self.config;
+        let completed_rounds = self.shared_state.round.saturating_sub(1);
+        let long_breaks = completed_rounds.checked_div(config.intervals).unwrap_or(0);
+        let short_breaks = completed_rounds - long_breaks;
+
+        completed_rounds * config.timer
+            + short_breaks * config.minor_break
+            + long_breaks * config.major_break
+            + current_round_secs
+    }
+
+    /// Denotes if the current round is the last one of the session
+    /// (see [PomodoroTimerConfig::max_rounds])
+    pub(crate) fn is_last_round(&self) -> bool {
+        self.config
+            .max_rounds
+            .is_some_and(|max_rounds| self.shared_state.round >= max_rounds)
+    }
+
+    /// Seconds of all postpone timers of the current round (including a running one)
+    pub(crate) fn postponed_secs(&self) -> u64 {
+        u64::from(self.shared_state.postponed_count) * self.config.postpone_timer
+    }
+
+    /// Estimated seconds until the next long break starts, given the remaining seconds of the
+    /// current timer (assuming no pauses)
+    pub(crate) fn cycle_remaining_secs(&self, remaining_secs: u64, is_break: bool) -> u64 {
+        let config = self.config;
+
+        // Focus intervals which follow the one of the current round until the next long break
+        let rounds_left = self
+            .shared_state
+            .round
+            .checked_rem(config.intervals)
+            .map_or(0, |rest| (config.intervals - rest) % config.intervals);
+
+        if !is_break {
+            return remaining_secs + rounds_left * (config.minor_break + config.timer);
+        }
+
+        // A long break is running, so the next one follows after a whole cycle
+        if rounds_left == 0 {
+            return remaining_secs
+                + config.intervals * config.timer
+                + config.intervals.saturating_sub(1) * config.minor_break;
+        }
+
+        remaining_secs + rounds_left * config.timer + (rounds_left - 1) * config.minor_break
+    }
+}
+
+/// Progress of a timer with the given duration from 0.0 to 1.0.
+/// Timers which have been set beyond their duration (see [crate::pomodoro_timer_action::PomodoroTimerAction::SetTimer]) are
+/// reported as not having progressed at all.
+pub(crate) fn timer_progress(duration_secs: u64, remaining_secs: u64) -> f32 {
+    if duration_secs == 0 {
+        return 1.0;
+    }
+
+    duration_secs.saturating_sub(remaining_secs) as f32 / duration_secs as f32
 }
@@ -0,0 +1,100 @@
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use crate::pomodoro_timer_action::PomodoroTimerAction;
+
+use super::{
+    interval::Interval,
+    state::{PomodoroState, PomodoroTimer, ViewState},
+    TimerKind,
+};
+
+/// Pomodoro timer state designating an open-ended focus session, which counts up from zero
+/// until it is stopped
+#[derive(Debug, Copy, Clone)]
+pub struct Stopwatch {}
+
+impl PomodoroState for Stopwatch {}
+
+impl PomodoroTimer<Interval> {
+    /// Turns the pomodoro timer into a stopwatch, which counts up instead of down.
+    /// [PomodoroTimer::<Stopwatch>::init] returns once the stopwatch has been stopped via
+    /// [PomodoroTimerAction::StopStopwatch].
+    pub fn stopwatch(self) -> PomodoroTimer<Stopwatch> {
+        PomodoroTimer {
+            shared_state: self.shared_state,
+            config: self.config,
+            callbacks: self.callbacks,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl PomodoroTimer<Stopwatch> {
+    /// Starts the stopwatch right away and blocks until it has been stopped.
+    /// NOTE: The `on_timer_end` callback is never called, because a stopwatch has no end on its own.
+    pub fn init(self) {
+        let clock = self.callbacks.clock.clone();
+
+        // Time counted before the last pause and the start of the current run (if running)
+        let mut counted = Duration::ZERO;
+        let mut running_since: Option<Instant> = Some(clock.now());
+
+        loop {
+            let elapsed = counted
+                + running_since.map_or(Duration::ZERO, |since| {
+                    clock.now().saturating_duration_since(since)
+                });
+            let elapsed_secs = elapsed.as_secs();
+
+            let action = (self.callbacks.on_tick)(ViewState {
+                kind: TimerKind::Interval,
+                is_break: false,
+                is_postponed: false,
+                postpone_count: 0,
+                round: self.shared_state.round,
+                intervals_until_long_break: 0,
+                time: self.config.time_format.format(elapsed_secs),
+                remaining_secs: 0,
+                progress: 0.0,
+                is_paused: running_since.is_none(),
+                goal_progress: None,
+                is_muted: false,
+                distractions: self.distractions(),
+                history: vec![],
+                session_elapsed_secs: elapsed_secs,
+                cycle_remaining_secs: 0,
+                is_session_complete: false,
+                elapsed_secs,
+                is_stopwatch: true,
+                label: None,
+                is_overtime: false,
+                can_postpone: false,
+            });
+
+            match action {
+                Some(PomodoroTimerAction::PlayPause) => {
+                    running_since = match running_since {
+                        Some(_) => {
+                            counted = elapsed;
+                            None
+                        }
+                        None => Some(clock.now()),
+                    };
+                }
+
+                Some(PomodoroTimerAction::StopStopwatch) => return,
+
+                // Starts counting from zero again (keeping the stopwatch paused or running)
+                Some(PomodoroTimerAction::ResetTimer) => {
+                    counted = Duration::ZERO;
+                    running_since = running_since.map(|_| clock.now());
+                }
+
+                Some(PomodoroTimerAction::LogDistraction) => self.log_distraction(),
+
+                _ => {}
+            }
+        }
+    }
+}
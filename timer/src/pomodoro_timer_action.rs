@@ -1,5 +1,7 @@
 //! Action enum that can be passed to the timer on each tick to interact with it
 
+use crate::config::PomodoroTimerConfig;
+
 /// Various control actions to transition into new states
 #[derive(Debug, Copy, Clone)]
 pub enum PomodoroTimerAction {
@@ -15,9 +17,37 @@ pub enum PomodoroTimerAction {
     /// Reset timer
     ResetTimer,
 
+    /// Move back to the previous timer (e.g. from a break to its focus interval or from a
+    /// focus interval to the break of the previous round). The rewound timer starts paused.
+    PreviousInterval,
+
     /// Postpone a break
     PostponeBreak,
 
     /// Set current timer to a specific time in seconds
     SetTimer(u64),
+
+    /// Counts a distraction (or interruption) during the current focus interval.
+    /// This is ignored during breaks.
+    LogDistraction,
+
+    /// Overrides the duration (in seconds) of the next focus interval and/or the next break once.
+    /// Afterwards the configured durations are used again. `None` keeps a previously set
+    /// override (or the configured duration).
+    SetNextInterval {
+        /// Duration of the next focus interval
+        focus: Option<u64>,
+
+        /// Duration of the next (short or long) break
+        break_secs: Option<u64>,
+    },
+
+    /// Replaces the configuration of the timer (e.g. because the config file has been edited).
+    /// The current timer keeps running with its durations and the new configuration is applied
+    /// once the next timer starts.
+    ReloadConfig(PomodoroTimerConfig),
+
+    /// Stops the stopwatch (see [crate::pomodoro_timer::PomodoroTimer::stopwatch]).
+    /// This is ignored by all other timers.
+    StopStopwatch,
 }
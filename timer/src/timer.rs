@@ -7,8 +7,10 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display};
 
+use crate::clock::{Clock, SystemClock};
 use crate::timer_action::TimerAction;
-use crate::util::seconds_to_time;
+use crate::util::TimeFormat;
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 
 // NOTE: I tried to use the typestate approach, like it's described here:
@@ -32,6 +34,10 @@ pub struct TimerStatus {
 
     /// Denotes if timer is paused or running
     pub is_paused: bool,
+
+    /// Remaining time of the timer in seconds
+    #[serde(default)]
+    pub remaining_secs: u64,
 }
 
 /// Empty trait implemented by structs (e.g. Paused, Running)
@@ -40,6 +46,8 @@ pub trait TimerState {}
 /// State specific to a paused timer
 #[derive(Clone, Copy, Debug)]
 pub struct Paused {
+    /// NOTE: Kept with sub-second precision, so that pausing and resuming the timer many times
+    /// doesn't shift its end
     remaining_time: Duration,
 }
 
@@ -114,6 +122,12 @@ pub struct Timer<S: TimerState> {
 
     /// Internal state data associated with a certain timer state (e.g. [Paused] or [Running])
     internal_state: S,
+
+    /// Source of the current time
+    clock: Rc<dyn Clock>,
+
+    /// Format of the [CurrentTime] handed to the `on_tick` closure
+    time_format: TimeFormat,
 }
 
 impl<S: TimerState + std::fmt::Debug> Debug for Timer<S> {
@@ -123,11 +137,21 @@ impl<S: TimerState + std::fmt::Debug> Debug for Timer<S> {
             .field("on_timer_end", &"[closure] without context")
             .field("internal_state", &self.internal_state)
             .field("on_tick", &"[closure] without context")
+            .field("clock", &self.clock)
+            .field("time_format", &self.time_format)
             .finish()
     }
 }
 
-impl<S: TimerState> Timer<S> {}
+impl<S: TimerState> Timer<S> {
+    /// Replaces the default [TimeFormat] (mm:ss) of the timer
+    pub fn with_time_format(self, time_format: TimeFormat) -> Self {
+        Self {
+            time_format,
+            ..self
+        }
+    }
+}
 
 /// Implementation of the [Paused] state for [Timer]
 impl Timer<Paused> {
@@ -145,19 +169,29 @@ impl Timer<Paused> {
             on_timer_end: on_timer_end.map(|x| Box::new(x) as Box<dyn TimerEndHandler>),
             on_tick: on_tick.map(|x| Box::new(x) as Box<dyn TimerTickHandler>),
             internal_state: Paused { remaining_time },
+            clock: Rc::new(SystemClock),
+            time_format: TimeFormat::default(),
         }
     }
 
+    /// Replaces the [SystemClock] of the timer with the given clock
+    pub fn with_clock(self, clock: Rc<dyn Clock>) -> Self {
+        Self { clock, ..self }
+    }
+
     /// Puts the paused timer into a waiting state waiting for input (e.g. to unpause the timer
     /// and transition it into a running state).
     pub fn init(mut self) {
         loop {
-            let time = self.internal_state.remaining_time.as_secs();
+            let time = whole_secs(self.internal_state.remaining_time);
 
-            let Some(ref mut callback) = self.on_tick else { continue };
+            let Some(ref mut callback) = self.on_tick else {
+                continue;
+            };
             if let Some(action) = callback.call(TimerStatus {
                 is_paused: true,
-                current_time: CurrentTime(seconds_to_time(time)),
+                current_time: CurrentTime(self.time_format.format(time)),
+                remaining_secs: time,
             }) {
                 match action {
                     TimerAction::SetTimer(time) => {
@@ -178,14 +212,16 @@ impl Timer<Paused> {
     }
 
     /// Transitions the paused timer into a running timer
-    fn unpause(self) {
+    pub(crate) fn unpause(self) {
         Timer {
             on_timer_end: self.on_timer_end,
             on_tick: self.on_tick,
             time: self.time,
             internal_state: Running {
-                target_time: Instant::now() + self.internal_state.remaining_time,
+                target_time: self.clock.now() + self.internal_state.remaining_time,
             },
+            clock: self.clock,
+            time_format: self.time_format,
         }
         .init()
     }
@@ -200,14 +236,29 @@ impl Timer<Running> {
         T: TimerTickHandler + 'static,
     {
         let remaining_time = Duration::from_secs(time);
+        let clock = Rc::new(SystemClock);
 
         Self {
             time,
             on_timer_end: on_timer_end.map(|x| Box::new(x) as Box<dyn TimerEndHandler>),
             on_tick: on_tick.map(|x| Box::new(x) as Box<dyn TimerTickHandler>),
             internal_state: Running {
-                target_time: Instant::now() + remaining_time,
+                target_time: clock.now() + remaining_time,
             },
+            clock,
+            time_format: TimeFormat::default(),
+        }
+    }
+
+    /// Replaces the [SystemClock] of the timer with the given clock.
+    /// The timer starts counting down from the current time of the new clock.
+    pub fn with_clock(self, clock: Rc<dyn Clock>) -> Self {
+        Self {
+            internal_state: Running {
+                target_time: clock.now() + Duration::from_secs(self.time),
+            },
+            clock,
+            ..self
         }
     }
 
@@ -219,8 +270,13 @@ impl Timer<Running> {
             on_tick: self.on_tick,
             on_timer_end: self.on_timer_end,
             internal_state: Paused {
-                remaining_time: self.internal_state.target_time - Instant::now(),
+                remaining_time: self
+                    .internal_state
+                    .target_time
+                    .saturating_duration_since(self.clock.now()),
             },
+            clock: self.clock,
+            time_format: self.time_format,
         }
         .init();
     }
@@ -228,13 +284,16 @@ impl Timer<Running> {
     /// Runs the timer and awaits input.
     /// Depending on the input [TimerInputAction] the timer might transition into a paused state or skip to the next interval.
     pub fn init(mut self) {
-        while self.internal_state.target_time > Instant::now() {
-            let time = (self.internal_state.target_time - Instant::now()).as_secs();
+        while self.internal_state.target_time > self.clock.now() {
+            let time = whole_secs(self.internal_state.target_time - self.clock.now());
 
-            let Some(ref mut callback) = self.on_tick else { continue };
+            let Some(ref mut callback) = self.on_tick else {
+                continue;
+            };
             if let Some(action) = callback.call(TimerStatus {
                 is_paused: false,
-                current_time: CurrentTime(seconds_to_time(time)),
+                current_time: CurrentTime(self.time_format.format(time)),
+                remaining_secs: time,
             }) {
                 match action {
                     TimerAction::PlayPause => {
@@ -245,7 +304,8 @@ impl Timer<Running> {
                     // can resume execution
                     TimerAction::End => return,
                     TimerAction::SetTimer(time) => {
-                        self.internal_state.target_time = Instant::now() + Duration::from_secs(time)
+                        self.internal_state.target_time =
+                            self.clock.now() + Duration::from_secs(time)
                     }
                 }
             }
@@ -256,3 +316,10 @@ impl Timer<Running> {
         }
     }
 }
+
+/// Rounds the remaining time up to whole seconds, so that a running timer starts at its full
+/// duration and only shows zero once it has actually ended (no matter how often it has been
+/// paused in between)
+fn whole_secs(remaining_time: Duration) -> u64 {
+    remaining_time.as_secs() + u64::from(remaining_time.subsec_nanos() > 0)
+}
@@ -1,8 +1,124 @@
 //! Small helper fns
 
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::Display;
+
 /// Transform a duration into a formatted timer string like "29:30" (mm:ss)
 pub fn seconds_to_time(duration: u64) -> String {
     let min = duration / 60;
     let sec = duration % 60;
     format!("{:02}:{:02}", min, sec)
 }
+
+/// Format of the current time of a timer (see [crate::timer::CurrentTime])
+///
+/// ## Example
+///
+/// ```
+/// use zentime_rs_timer::util::TimeFormat;
+///
+/// assert_eq!(TimeFormat::MinutesSeconds.format(5400), "90:00");
+/// assert_eq!(TimeFormat::HoursMinutesSeconds.format(1770), "00:29:30");
+/// assert_eq!(TimeFormat::Auto.format(3600), "60:00");
+/// assert_eq!(TimeFormat::Auto.format(5400), "01:30:00");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeFormat {
+    /// e.g. "29:30" (minutes exceed 59 for long timers, see [seconds_to_time])
+    #[default]
+    #[serde(rename = "mm:ss")]
+    MinutesSeconds,
+
+    /// e.g. "00:29:30"
+    #[serde(rename = "hh:mm:ss")]
+    HoursMinutesSeconds,
+
+    /// "hh:mm:ss" for durations above 60 minutes and "mm:ss" otherwise
+    #[serde(rename = "auto")]
+    Auto,
+}
+
+impl TimeFormat {
+    /// Transform a duration in seconds into a formatted timer string
+    pub fn format(self, duration: u64) -> String {
+        match self {
+            TimeFormat::MinutesSeconds => seconds_to_time(duration),
+            TimeFormat::Auto if duration <= 3600 => seconds_to_time(duration),
+            TimeFormat::HoursMinutesSeconds | TimeFormat::Auto => format!(
+                "{:02}:{:02}:{:02}",
+                duration / 3600,
+                duration % 3600 / 60,
+                duration % 60
+            ),
+        }
+    }
+}
+
+/// Error returned by [parse_duration], if a duration string could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDurationError(String);
+
+impl Display for ParseDurationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid duration \"{}\" (expected e.g. \"90s\", \"25m\" or \"1h30m\")",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseDurationError {}
+
+/// Parses a human readable duration like "25m", "1h30m", "1.5m" or "90s" into seconds.
+/// A plain number without any unit is interpreted as seconds.
+///
+/// ## Example
+///
+/// ```
+/// use zentime_rs_timer::util::parse_duration;
+///
+/// assert_eq!(parse_duration("90"), Ok(90));
+/// assert_eq!(parse_duration("90s"), Ok(90));
+/// assert_eq!(parse_duration("25m"), Ok(1500));
+/// assert_eq!(parse_duration("1.5m"), Ok(90));
+/// assert_eq!(parse_duration("1h30m"), Ok(5400));
+/// assert!(parse_duration("25 minutes").is_err());
+/// ```
+pub fn parse_duration(duration: &str) -> Result<u64, ParseDurationError> {
+    let error = || ParseDurationError(duration.to_string());
+    let trimmed = duration.trim();
+
+    if let Ok(seconds) = trimmed.parse::<u64>() {
+        return Ok(seconds);
+    }
+
+    let mut total_seconds = 0.0;
+    let mut number = String::new();
+
+    for character in trimmed.chars() {
+        match character {
+            '0'..='9' | '.' => number.push(character),
+            'h' | 'm' | 's' => {
+                let value = number.parse::<f64>().map_err(|_| error())?;
+                let factor = match character {
+                    'h' => 3600.0,
+                    'm' => 60.0,
+                    _ => 1.0,
+                };
+
+                total_seconds += value * factor;
+                number.clear();
+            }
+            _ => return Err(error()),
+        }
+    }
+
+    // Either nothing has been parsed at all or there is a trailing number without unit
+    if trimmed.is_empty() || !number.is_empty() {
+        return Err(error());
+    }
+
+    Ok(total_seconds.round() as u64)
+}
@@ -0,0 +1,751 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::thread;
+use std::time::Duration;
+use zentime_rs_timer::clock::MockClock;
+use zentime_rs_timer::config::{PomodoroTimerConfig, MIN_RATIO_BREAK};
+use zentime_rs_timer::pomodoro_timer::{PomodoroTimer, TimerKind, ViewState};
+use zentime_rs_timer::pomodoro_timer_action::PomodoroTimerAction;
+use zentime_rs_timer::util::TimeFormat;
+
+/// Something the pomodoro timer reported through one of its callbacks
+#[derive(Debug)]
+enum Report {
+    /// A timer of the given kind has ended (with or without a notification)
+    End(TimerKind, bool),
+    Tick(ViewState),
+}
+
+/// Runs a pomodoro timer driven by a [MockClock] on a separate thread.
+/// `decide` is called on every tick and may return an action for the timer. Otherwise the clock
+/// is advanced by ten seconds.
+/// Reports are sent over a rendezvous channel, so that the timer only advances while the test is
+/// receiving. Otherwise its recursive state transitions could overflow the stack in the meantime.
+fn run_timer(
+    config: PomodoroTimerConfig,
+    decide: impl Fn(&ViewState) -> Option<PomodoroTimerAction> + Send + 'static,
+) -> Receiver<Report> {
+    run_timer_with(config, false, decide)
+}
+
+/// Runs a pomodoro timer just like [run_timer], but lets the first interval start counting right
+/// away if `start_running` is set (see [PomodoroTimer::init_running])
+fn run_timer_with(
+    config: PomodoroTimerConfig,
+    start_running: bool,
+    decide: impl Fn(&ViewState) -> Option<PomodoroTimerAction> + Send + 'static,
+) -> Receiver<Report> {
+    let (report_tx, report_rx) = sync_channel(0);
+
+    thread::spawn(move || {
+        let clock = Rc::new(MockClock::default());
+        let tick_clock = clock.clone();
+        let end_tx = report_tx.clone();
+
+        let timer = PomodoroTimer::new(
+            config,
+            Rc::new(move |_, msg, kind| {
+                end_tx.send(Report::End(kind, msg.is_some())).ok();
+            }),
+            Rc::new(move |view_state| {
+                let action = decide(&view_state);
+
+                if report_tx.send(Report::Tick(view_state)).is_err() {
+                    // The test has finished, but the pomodoro timer never returns
+                    loop {
+                        thread::park();
+                    }
+                }
+
+                if action.is_none() {
+                    tick_clock.advance(Duration::from_secs(10));
+                }
+
+                action
+            }),
+        )
+        .with_clock(clock);
+
+        if start_running {
+            timer.init_running();
+        } else {
+            timer.init();
+        }
+    });
+
+    report_rx
+}
+
+/// Collects reports until `count` timers have ended
+fn collect_reports(
+    report_rx: &Receiver<Report>,
+    count: usize,
+) -> (Vec<(TimerKind, bool)>, Vec<ViewState>) {
+    let mut ends = vec![];
+    let mut ticks = vec![];
+
+    while ends.len() < count {
+        match report_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("Timer did not report in time")
+        {
+            Report::End(kind, has_notification) => ends.push((kind, has_notification)),
+            Report::Tick(view_state) => ticks.push(view_state),
+        }
+    }
+
+    (ends, ticks)
+}
+
+fn config() -> PomodoroTimerConfig {
+    PomodoroTimerConfig {
+        timer: 60,
+        minor_break: 20,
+        major_break: 40,
+        intervals: 2,
+        postpone_limit: 2,
+        postpone_timer: 30,
+        max_rounds: None,
+        strict_breaks: false,
+        auto_start_focus: false,
+        pause_after_long_break: false,
+        time_format: TimeFormat::default(),
+        break_ratio: None,
+        overtime_secs: None,
+    }
+}
+
+#[test]
+fn long_break_follows_configured_number_of_intervals() {
+    let report_rx = run_timer(config(), |view_state| {
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (ends, _) = collect_reports(&report_rx, 8);
+    let kinds: Vec<TimerKind> = ends.into_iter().map(|(kind, _)| kind).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            TimerKind::Interval,
+            TimerKind::Break,
+            TimerKind::Interval,
+            TimerKind::LongBreak,
+            TimerKind::Interval,
+            TimerKind::Break,
+            TimerKind::Interval,
+            TimerKind::LongBreak,
+        ]
+    );
+}
+
+#[test]
+fn postponing_respects_postpone_limit() {
+    // Postpone count of the break we have already tried to postpone
+    let requested_postpone = Cell::new(None);
+
+    let report_rx = run_timer(config(), move |view_state| {
+        if view_state.is_break && requested_postpone.get() != Some(view_state.postpone_count) {
+            requested_postpone.set(Some(view_state.postpone_count));
+            return Some(PomodoroTimerAction::PostponeBreak);
+        }
+
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (ends, ticks) = collect_reports(&report_rx, 4);
+
+    // The focus interval, two postponed breaks (without notification) and the actual break
+    assert_eq!(
+        ends,
+        vec![
+            (TimerKind::Interval, true),
+            (TimerKind::Interval, false),
+            (TimerKind::Interval, false),
+            (TimerKind::Break, true),
+        ]
+    );
+    assert_eq!(
+        ticks.iter().map(|state| state.postpone_count).max(),
+        Some(2)
+    );
+
+    // Postponing is only offered during breaks, until the limit has been reached
+    assert!(ticks
+        .iter()
+        .filter(|state| !state.is_break)
+        .all(|state| !state.can_postpone));
+    assert!(ticks
+        .iter()
+        .filter(|state| state.kind == TimerKind::Break)
+        .all(|state| state.can_postpone == (state.postpone_count < 2)));
+}
+
+#[test]
+fn session_completes_after_max_rounds() {
+    let config = PomodoroTimerConfig {
+        max_rounds: Some(3),
+        ..config()
+    };
+
+    let report_rx = run_timer(config, |view_state| {
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (ends, _) = collect_reports(&report_rx, 6);
+
+    // The session is complete after the break of the third round and the timer waits for a reset
+    let complete_state = loop {
+        match report_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("Timer did not report in time")
+        {
+            Report::Tick(view_state) if view_state.is_session_complete => break view_state,
+            Report::Tick(_) => continue,
+            Report::End(kind, _) => panic!("Timer continued with {:?}", kind),
+        }
+    };
+
+    assert_eq!(ends.last(), Some(&(TimerKind::Break, true)));
+    assert_eq!(complete_state.round, 3);
+}
+
+#[test]
+fn previous_interval_rewinds_across_long_break() {
+    let has_rewound = Cell::new(false);
+
+    let report_rx = run_timer(config(), move |view_state| {
+        if !has_rewound.get() && !view_state.is_break && view_state.round == 3 {
+            has_rewound.set(true);
+            return Some(PomodoroTimerAction::PreviousInterval);
+        }
+
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (ends, ticks) = collect_reports(&report_rx, 5);
+    let kinds: Vec<TimerKind> = ends.into_iter().map(|(kind, _)| kind).collect();
+
+    // The focus interval of round three moves back to the (paused) long break of round two
+    let rewound_state = ticks
+        .iter()
+        .skip_while(|state| state.round < 3)
+        .nth(1)
+        .expect("Timer did not rewind");
+
+    assert!(rewound_state.is_break && rewound_state.is_paused);
+    assert_eq!(rewound_state.round, 2);
+    assert_eq!(
+        kinds,
+        vec![
+            TimerKind::Interval,
+            TimerKind::Break,
+            TimerKind::Interval,
+            TimerKind::LongBreak,
+            TimerKind::LongBreak,
+        ]
+    );
+}
+
+#[test]
+fn progress_is_relative_to_duration_of_current_timer() {
+    let report_rx = run_timer(config(), |view_state| {
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (_, ticks) = collect_reports(&report_rx, 4);
+
+    // Progress of focus interval, short break and long break after half of their duration
+    let halfway: Vec<(bool, u64)> = ticks
+        .iter()
+        .filter(|view_state| (view_state.progress - 0.5).abs() < f32::EPSILON)
+        .map(|view_state| (view_state.is_break, view_state.remaining_secs))
+        .collect();
+
+    assert_eq!(
+        halfway,
+        vec![(false, 30), (true, 10), (false, 30), (true, 20)]
+    );
+    assert!(ticks
+        .iter()
+        .all(|view_state| (0.0..=1.0).contains(&view_state.progress)));
+}
+
+#[test]
+fn init_running_only_starts_first_interval_right_away() {
+    // Never unpause the timer
+    let report_rx = run_timer_with(config(), true, |_| None);
+
+    let (ends, ticks) = collect_reports(&report_rx, 1);
+
+    assert_eq!(ends, vec![(TimerKind::Interval, true)]);
+    assert!(ticks.iter().all(|view_state| !view_state.is_paused));
+
+    // The following break waits for play/pause
+    let Report::Tick(next_state) = report_rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("Timer did not report in time")
+    else {
+        panic!("Expected a tick of the following break")
+    };
+
+    assert!(next_state.kind.is_break());
+    assert!(next_state.is_paused);
+}
+
+#[test]
+fn next_interval_override_is_only_applied_once() {
+    let has_overridden = Cell::new(false);
+
+    let report_rx = run_timer(config(), move |view_state| {
+        if !has_overridden.replace(true) {
+            return Some(PomodoroTimerAction::SetNextInterval {
+                focus: Some(30),
+                break_secs: Some(50),
+            });
+        }
+
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (_, ticks) = collect_reports(&report_rx, 5);
+
+    // Full duration of every timer, taken from its first tick
+    let mut durations: Vec<(TimerKind, u64)> = ticks
+        .iter()
+        .filter(|view_state| view_state.progress == 0.0)
+        .map(|view_state| (view_state.kind, view_state.remaining_secs))
+        .collect();
+    durations.dedup();
+
+    // The override does not touch the already created first interval
+    assert_eq!(
+        durations,
+        vec![
+            (TimerKind::Interval, 60),
+            (TimerKind::Break, 50),
+            (TimerKind::Interval, 30),
+            (TimerKind::LongBreak, 40),
+            (TimerKind::Interval, 60),
+        ]
+    );
+}
+
+#[test]
+fn reloaded_config_applies_from_the_next_timer_on() {
+    let has_reloaded = Cell::new(false);
+
+    let report_rx = run_timer(config(), move |view_state| {
+        if !has_reloaded.replace(true) {
+            return Some(PomodoroTimerAction::ReloadConfig(PomodoroTimerConfig {
+                timer: 30,
+                minor_break: 10,
+                ..config()
+            }));
+        }
+
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (_, ticks) = collect_reports(&report_rx, 4);
+
+    // Full duration of every timer, taken from its first tick
+    let mut durations: Vec<(TimerKind, u64)> = ticks
+        .iter()
+        .filter(|view_state| view_state.progress == 0.0)
+        .map(|view_state| (view_state.kind, view_state.remaining_secs))
+        .collect();
+    durations.dedup();
+
+    // The already running first interval keeps its duration
+    assert_eq!(
+        durations,
+        vec![
+            (TimerKind::Interval, 60),
+            (TimerKind::Break, 10),
+            (TimerKind::Interval, 30),
+            (TimerKind::LongBreak, 40),
+        ]
+    );
+}
+
+#[test]
+fn distractions_are_counted_per_focus_interval() {
+    let has_logged_during_break = Cell::new(false);
+
+    let report_rx = run_timer(config(), move |view_state| {
+        if view_state.is_paused {
+            return Some(PomodoroTimerAction::PlayPause);
+        }
+
+        // Breaks should ignore distractions
+        if view_state.kind.is_break() && !has_logged_during_break.replace(true) {
+            return Some(PomodoroTimerAction::LogDistraction);
+        }
+
+        (view_state.round == 1
+            && view_state.kind == TimerKind::Interval
+            && view_state.distractions < 2)
+            .then_some(PomodoroTimerAction::LogDistraction)
+    });
+
+    let (_, ticks) = collect_reports(&report_rx, 3);
+
+    let mut distractions: Vec<(TimerKind, u16)> = ticks
+        .iter()
+        .map(|view_state| (view_state.kind, view_state.distractions))
+        .collect();
+    distractions.dedup();
+
+    // The break still shows the distractions of its interval
+    assert_eq!(
+        distractions,
+        vec![
+            (TimerKind::Interval, 0),
+            (TimerKind::Interval, 1),
+            (TimerKind::Interval, 2),
+            (TimerKind::Break, 2),
+            (TimerKind::Interval, 0),
+        ]
+    );
+}
+
+#[test]
+fn strict_breaks_can_not_be_skipped() {
+    let config = PomodoroTimerConfig {
+        strict_breaks: true,
+        ..config()
+    };
+    // Round and kind of the last timer we have already tried to skip
+    let requested_skip = Cell::new(None);
+
+    let report_rx = run_timer(config, move |view_state| {
+        if view_state.is_paused {
+            return Some(PomodoroTimerAction::PlayPause);
+        }
+
+        let timer = Some((view_state.round, view_state.kind));
+        (requested_skip.replace(timer) != timer).then_some(PomodoroTimerAction::Skip)
+    });
+
+    // Skipped timers do not report their end
+    let (ends, ticks) = collect_reports(&report_rx, 1);
+    assert_eq!(ends, vec![(TimerKind::Break, true)]);
+
+    // The focus interval has been skipped right away, while the break ran its course
+    let remaining_secs = |kind| {
+        ticks
+            .iter()
+            .filter(|view_state| view_state.kind == kind)
+            .map(|view_state| view_state.remaining_secs)
+            .min()
+    };
+    assert_eq!(remaining_secs(TimerKind::Interval), Some(60));
+    assert_eq!(remaining_secs(TimerKind::Break), Some(10));
+}
+
+#[test]
+fn focus_after_long_break_waits_if_pause_after_long_break_is_set() {
+    let config = PomodoroTimerConfig {
+        auto_start_focus: true,
+        pause_after_long_break: true,
+        ..config()
+    };
+
+    // Only breaks are unpaused, focus intervals have to start on their own
+    let report_rx = run_timer_with(config, true, |view_state| {
+        (view_state.is_paused && view_state.kind.is_break())
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (ends, ticks) = collect_reports(&report_rx, 4);
+    let kinds: Vec<TimerKind> = ends.into_iter().map(|(kind, _)| kind).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            TimerKind::Interval,
+            TimerKind::Break,
+            TimerKind::Interval,
+            TimerKind::LongBreak,
+        ]
+    );
+    assert!(ticks
+        .iter()
+        .filter(|view_state| view_state.kind == TimerKind::Interval)
+        .all(|view_state| !view_state.is_paused));
+
+    // The focus interval of the next cycle waits for play/pause
+    let Report::Tick(next_state) = report_rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("Timer did not report in time")
+    else {
+        panic!("Expected a tick of the following focus interval")
+    };
+
+    assert_eq!(next_state.kind, TimerKind::Interval);
+    assert!(next_state.is_paused);
+}
+
+#[test]
+fn stopwatch_counts_up_without_pauses_until_it_is_stopped() {
+    let (report_tx, report_rx) = sync_channel(0);
+
+    let stopwatch = thread::spawn(move || {
+        let clock = Rc::new(MockClock::default());
+        let tick_clock = clock.clone();
+        let has_paused = Cell::new(false);
+        let paused_ticks = Cell::new(0);
+
+        PomodoroTimer::new(
+            config(),
+            Rc::new(|_, _, _| panic!("A stopwatch never ends on its own")),
+            Rc::new(move |view_state| {
+                // Pauses for 30 seconds after 30 seconds and stops after one minute
+                let action = if view_state.is_paused {
+                    paused_ticks.set(paused_ticks.get() + 1);
+                    (paused_ticks.get() > 3).then_some(PomodoroTimerAction::PlayPause)
+                } else if view_state.elapsed_secs >= 60 {
+                    Some(PomodoroTimerAction::StopStopwatch)
+                } else if view_state.elapsed_secs == 30 && !has_paused.replace(true) {
+                    Some(PomodoroTimerAction::PlayPause)
+                } else {
+                    None
+                };
+
+                report_tx.send(view_state).ok();
+                if action.is_none() {
+                    tick_clock.advance(Duration::from_secs(10));
+                }
+
+                action
+            }),
+        )
+        .with_clock(clock)
+        .stopwatch()
+        .init();
+    });
+
+    let ticks: Vec<ViewState> = report_rx.iter().collect();
+    stopwatch
+        .join()
+        .expect("Stopwatch should return once it has been stopped");
+
+    assert!(ticks.iter().all(|view_state| view_state.is_stopwatch));
+    assert_eq!(
+        ticks.first().map(|view_state| view_state.time.as_str()),
+        Some("00:00")
+    );
+    assert!(ticks
+        .iter()
+        .filter(|view_state| view_state.is_paused)
+        .all(|view_state| view_state.elapsed_secs == 30));
+
+    // The 30 paused seconds are not counted
+    assert_eq!(
+        ticks.last().map(|view_state| view_state.elapsed_secs),
+        Some(60)
+    );
+}
+
+#[test]
+fn auto_time_format_only_shows_hours_above_sixty_minutes() {
+    let config = PomodoroTimerConfig {
+        timer: 3620,
+        time_format: TimeFormat::Auto,
+        ..config()
+    };
+
+    let report_rx = run_timer(config, |view_state| {
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (_, ticks) = collect_reports(&report_rx, 1);
+    let times: Vec<&str> = ticks.iter().map(|state| state.time.as_str()).collect();
+
+    assert_eq!(times[..3], ["01:00:20", "01:00:20", "01:00:10"]);
+    assert!(times.contains(&"60:00"));
+}
+
+#[test]
+fn break_ratio_determines_short_break_length() {
+    let config = PomodoroTimerConfig {
+        timer: 200,
+        major_break: 300,
+        intervals: 4,
+        break_ratio: Some(0.5),
+        ..config()
+    };
+
+    // The second interval is skipped after 40 seconds, which results in the minimum break length
+    let report_rx = run_timer(config, |view_state| match view_state.kind {
+        TimerKind::Interval if view_state.round == 2 && view_state.remaining_secs <= 160 => {
+            Some(PomodoroTimerAction::Skip)
+        }
+        _ => view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause),
+    });
+
+    let (_, ticks) = collect_reports(&report_rx, 4);
+    let break_lengths: Vec<u64> = ticks
+        .iter()
+        .filter(|state| state.kind == TimerKind::Break && state.is_paused)
+        .map(|state| state.remaining_secs)
+        .collect();
+
+    assert_eq!(break_lengths[0], 100);
+    assert!(break_lengths.contains(&MIN_RATIO_BREAK));
+}
+
+#[test]
+fn overtime_counts_up_until_grace_period_has_elapsed_or_timer_is_skipped() {
+    let config = PomodoroTimerConfig {
+        overtime_secs: Some(30),
+        ..config()
+    };
+
+    // The overtime of the second interval is skipped right away
+    let report_rx = run_timer(config, |view_state| {
+        if view_state.is_overtime && view_state.round == 2 {
+            return Some(PomodoroTimerAction::Skip);
+        }
+
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (ends, ticks) = collect_reports(&report_rx, 4);
+    let kinds: Vec<TimerKind> = ends.into_iter().map(|(kind, _)| kind).collect();
+    let overtime: Vec<(u64, u64, &str)> = ticks
+        .iter()
+        .filter(|state| state.is_overtime)
+        .map(|state| (state.round, state.elapsed_secs, state.time.as_str()))
+        .collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            TimerKind::Interval,
+            TimerKind::Break,
+            TimerKind::Interval,
+            TimerKind::LongBreak,
+        ]
+    );
+    assert_eq!(
+        overtime,
+        vec![
+            (1, 0, "+00:00"),
+            (1, 10, "+00:10"),
+            (1, 20, "+00:20"),
+            (2, 0, "+00:00"),
+        ]
+    );
+}
+
+#[test]
+fn interval_ends_with_its_overtime() {
+    let config = PomodoroTimerConfig {
+        overtime_secs: Some(30),
+        ..config()
+    };
+
+    let report_rx = run_timer(config, |view_state| {
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let mut reports = vec![];
+    while !matches!(reports.last(), Some(Report::End(..))) {
+        reports.push(
+            report_rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("Timer did not report in time"),
+        );
+    }
+
+    // The interval (and its notification) only ends after the overtime
+    assert!(matches!(
+        reports.last(),
+        Some(Report::End(TimerKind::Interval, true))
+    ));
+    assert!(reports
+        .iter()
+        .any(|report| matches!(report, Report::Tick(state) if state.is_overtime)));
+}
+
+#[test]
+fn play_pause_pauses_the_overtime() {
+    let config = PomodoroTimerConfig {
+        overtime_secs: Some(30),
+        ..config()
+    };
+
+    // Pauses the overtime once it has counted 10 seconds and resumes it after a while
+    let paused_ticks = Cell::new(0);
+    let report_rx = run_timer(config, move |view_state| {
+        if view_state.is_overtime {
+            return match (view_state.is_paused, view_state.elapsed_secs) {
+                (false, 10) if paused_ticks.get() == 0 => {
+                    paused_ticks.set(1);
+                    Some(PomodoroTimerAction::PlayPause)
+                }
+                (true, _) if paused_ticks.get() < 3 => {
+                    paused_ticks.set(paused_ticks.get() + 1);
+                    None
+                }
+                (true, _) => Some(PomodoroTimerAction::PlayPause),
+                _ => None,
+            };
+        }
+
+        view_state
+            .is_paused
+            .then_some(PomodoroTimerAction::PlayPause)
+    });
+
+    let (ends, ticks) = collect_reports(&report_rx, 2);
+    let overtime: Vec<(u64, bool)> = ticks
+        .iter()
+        .filter(|state| state.is_overtime)
+        .map(|state| (state.elapsed_secs, state.is_paused))
+        .collect();
+
+    assert_eq!(
+        ends,
+        vec![(TimerKind::Interval, true), (TimerKind::Break, true)]
+    );
+
+    // The overtime doesn't advance while it is paused
+    assert_eq!(
+        overtime,
+        vec![
+            (0, false),
+            (10, false),
+            (10, true),
+            (10, true),
+            (10, true),
+            (10, false),
+            (20, false),
+        ]
+    );
+}
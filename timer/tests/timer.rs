@@ -0,0 +1,96 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+use zentime_rs_timer::clock::MockClock;
+use zentime_rs_timer::timer::{Paused, Timer, TimerEndHandler, TimerStatus, TimerTickHandler};
+use zentime_rs_timer::TimerAction;
+
+/// Running time which passes between two ticks of a running timer
+const TICK: Duration = Duration::from_millis(250);
+
+/// Time which passes while the timer is paused
+const PAUSE: Duration = Duration::from_millis(1_700);
+
+#[derive(Debug, Default)]
+struct Recording {
+    /// Time which has passed while the timer was running
+    running_time: Duration,
+
+    /// Remaining seconds reported by every running tick (with the running time at that tick)
+    running_ticks: Vec<(Duration, u64)>,
+    pause_count: u32,
+    has_ended: bool,
+}
+
+struct OnEnd(Rc<RefCell<Recording>>);
+
+impl TimerEndHandler for OnEnd {
+    fn call(&mut self) {
+        self.0.borrow_mut().has_ended = true;
+    }
+}
+
+/// Pauses the timer after every third running tick and resumes it on the next tick
+struct PauseRepeatedly {
+    clock: Rc<MockClock>,
+    recording: Rc<RefCell<Recording>>,
+    ticks_since_resume: u32,
+}
+
+impl TimerTickHandler for PauseRepeatedly {
+    fn call(&mut self, status: TimerStatus) -> Option<TimerAction> {
+        let mut recording = self.recording.borrow_mut();
+
+        if status.is_paused {
+            self.clock.advance(PAUSE);
+            self.ticks_since_resume = 0;
+            return Some(TimerAction::PlayPause);
+        }
+
+        let running_time = recording.running_time;
+        recording
+            .running_ticks
+            .push((running_time, status.remaining_secs));
+
+        if self.ticks_since_resume == 3 {
+            recording.pause_count += 1;
+            return Some(TimerAction::PlayPause);
+        }
+
+        self.ticks_since_resume += 1;
+        self.clock.advance(TICK);
+        recording.running_time += TICK;
+
+        None
+    }
+}
+
+#[test]
+fn pausing_repeatedly_does_not_shift_the_end_of_the_timer() {
+    let clock = Rc::new(MockClock::default());
+    let recording = Rc::new(RefCell::new(Recording::default()));
+
+    Timer::<Paused>::new(
+        10,
+        Some(OnEnd(recording.clone())),
+        Some(PauseRepeatedly {
+            clock: clock.clone(),
+            recording: recording.clone(),
+            ticks_since_resume: 0,
+        }),
+    )
+    .with_clock(clock)
+    .init();
+
+    let recording = recording.borrow();
+    assert!(recording.has_ended);
+    assert!(recording.pause_count > 10);
+    assert_eq!(recording.running_time, Duration::from_secs(10));
+
+    // Partial seconds are rounded up, e.g. the timer shows 10 seconds until a full second has
+    // passed
+    for (running_time, remaining_secs) in &recording.running_ticks {
+        let remaining = Duration::from_secs(10) - *running_time;
+        assert_eq!(*remaining_secs, remaining.as_secs_f64().ceil() as u64);
+    }
+}